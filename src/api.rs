@@ -0,0 +1,114 @@
+use std::io::Read;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::error::Error;
+
+// larger bodies are no registrations of a club
+const MAX_BODY_SIZE: u64 = 1024 * 1024;
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Deserialize)]
+pub struct NewRegistrationAthlete {
+    pub id: Uuid,
+    #[serde(rename="age-category")]
+    pub age_category: String,
+    #[serde(rename="weight-category")]
+    pub weight_category: String
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewRegistration {
+    pub name: String,
+    pub place: String,
+    pub date: NaiveDate,
+    pub athletes: Vec<NewRegistrationAthlete>
+}
+
+#[derive(Debug)]
+pub enum ApiRequest {
+    ListAthletes,
+    CreateRegistration(NewRegistration),
+    Export
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: String
+}
+
+impl ApiResponse {
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self { status, body: body.into() }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::new(status, serde_json::json!({ "error": message }).to_string())
+    }
+}
+
+/// A request handed to the app, which answers on `respond` after handling it in its next frame.
+#[derive(Debug)]
+pub struct ApiCall {
+    pub request: ApiRequest,
+    pub respond: Sender<ApiResponse>
+}
+
+/// Checks the token and maps method and path to a request of the API.
+pub fn parse_request(method: &str, path: &str, authorization: Option<&str>, body: &str, token: &str)
+-> Result<ApiRequest, ApiResponse> {
+    if token.is_empty() || authorization.and_then(|authorization| authorization.strip_prefix("Bearer ")) != Some(token) {
+        return Err(ApiResponse::error(401, "missing or wrong token"));
+    }
+    match (method, path.split('?').next().unwrap_or_default()) {
+        ("GET", "/athletes") => Ok(ApiRequest::ListAthletes),
+        ("POST", "/registration") => serde_json::from_str(body).map(ApiRequest::CreateRegistration)
+            .map_err(|err| ApiResponse::error(400, &err.to_string())),
+        ("POST", "/export") => Ok(ApiRequest::Export),
+        _ => Err(ApiResponse::error(404, "unknown endpoint"))
+    }
+}
+
+/// Serves the API on localhost only, `wake` is called for each request, so the app handles it without user input.
+pub fn start_api(port: u16, token: String, wake: impl Fn() + Send + 'static) -> Result<Receiver<ApiCall>, Error> {
+    if token.is_empty() {
+        return Err(Error::Config(String::from("the local API needs a token")));
+    }
+    let server = tiny_http::Server::http(("127.0.0.1", port)).map_err(|err| Error::Network(err.to_string()))?;
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            let response = if let Err(err) = request.as_reader().take(MAX_BODY_SIZE).read_to_string(&mut body) {
+                ApiResponse::error(400, &err.to_string())
+            }
+            else {
+                let authorization = request.headers().iter().find(|header| header.field.equiv("Authorization"))
+                    .map(|header| header.value.as_str().to_owned());
+                match parse_request(request.method().as_str(), request.url(), authorization.as_deref(), &body, &token) {
+                    Ok(api_request) => {
+                        let (respond, response) = mpsc::channel();
+                        if sender.send(ApiCall { request: api_request, respond }).is_err() {
+                            // the app was closed
+                            break;
+                        }
+                        wake();
+                        response.recv_timeout(RESPONSE_TIMEOUT).unwrap_or_else(|_| ApiResponse::error(503, "the app did not respond"))
+                    }
+                    Err(response) => response
+                }
+            };
+            let header = tiny_http::Header::from_bytes("Content-Type", "application/json").expect("unreachable");
+            if let Err(err) = request.respond(tiny_http::Response::from_string(response.body).with_status_code(response.status)
+                .with_header(header)) {
+                log::warn!("failed to respond to API-request, due to {err}");
+            }
+        }
+    });
+    Ok(receiver)
+}
@@ -0,0 +1,224 @@
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::Error;
+use crate::utils::{get_config_dir, DRAFT_EXTENSION};
+
+const PROG_ID_PREFIX: &str = "e-melder";
+const SHORTCUT_NAME: &str = "e-melder.lnk";
+const DESKTOP_ENTRY_NAME: &str = "e-melder.desktop";
+const MIME_PACKAGE_NAME: &str = "e-melder.xml";
+const ICON_NAME: &str = "e-melder.svg";
+const MIME_TYPES: [&str; 2] = ["application/x-e-melder-tournament", "application/x-e-melder-draft"];
+
+const MIME_PACKAGE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<mime-info xmlns="http://www.freedesktop.org/standards/shared-mime-info">
+    <mime-type type="application/x-e-melder-tournament">
+        <comment>Tournament-file of the e-melder</comment>
+        <comment xml:lang="de">Turnier-Datei des e-melders</comment>
+        <glob pattern="*.dm4"/>
+    </mime-type>
+    <mime-type type="application/x-e-melder-draft">
+        <comment>Draft of a registration</comment>
+        <comment xml:lang="de">Entwurf einer Meldung</comment>
+        <glob pattern="*.emreg"/>
+    </mime-type>
+</mime-info>
+"#;
+
+const ICON: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 64 64">
+    <rect x="4" y="4" width="56" height="56" rx="10" fill="#1f5fa8"/>
+    <text x="32" y="44" font-family="sans-serif" font-size="36" font-weight="bold" fill="#ffffff" text-anchor="middle">e</text>
+</svg>
+"##;
+
+/// The extensions opened with the app, the tournament-files and the exported drafts.
+pub const ASSOCIATED_EXTENSIONS: [&str; 2] = ["dm4", DRAFT_EXTENSION];
+
+fn classes_key(name: &str) -> String {
+    // the classes of the current user need no administrator
+    format!(r"HKCU\Software\Classes\{name}")
+}
+
+/// The `reg`-commands associating the extensions with `exe`, so double-clicking a file opens it in the app.
+pub fn association_commands(exe: &Path) -> Vec<Vec<String>> {
+    let exe = exe.display();
+    ASSOCIATED_EXTENSIONS.iter().flat_map(|extension| {
+        let prog_id = format!("{PROG_ID_PREFIX}.{extension}");
+        [
+            (classes_key(&format!(".{extension}")), prog_id.clone()),
+            (classes_key(&prog_id), format!("e-melder {extension}")),
+            (classes_key(&format!(r"{prog_id}\DefaultIcon")), format!("\"{exe}\",0")),
+            (classes_key(&format!(r"{prog_id}\shell\open\command")), format!("\"{exe}\" \"%1\""))
+        ].map(|(key, value)| {
+            ["add", &key, "/ve", "/d", &value, "/f"].map(String::from).to_vec()
+        })
+    }).collect()
+}
+
+/// The `reg`-commands removing the associations again.
+pub fn unassociation_commands() -> Vec<Vec<String>> {
+    ASSOCIATED_EXTENSIONS.iter().flat_map(|extension| {
+        let prog_id = format!("{PROG_ID_PREFIX}.{extension}");
+        [
+            ["delete", &classes_key(&format!(".{extension}")), "/ve", "/f"].map(String::from).to_vec(),
+            ["delete", &classes_key(&prog_id), "/f"].map(String::from).to_vec()
+        ]
+    }).collect()
+}
+
+/// The PowerShell-script creating a shortcut at `link` to `exe`.
+pub fn shortcut_script(link: &Path, exe: &Path) -> String {
+    // single quotes are escaped by doubling them in PowerShell
+    let quote = |path: &Path| path.display().to_string().replace('\'', "''");
+    format!("$shortcut = (New-Object -ComObject WScript.Shell).CreateShortcut('{}'); $shortcut.TargetPath = '{}'; \
+        $shortcut.WorkingDirectory = '{}'; $shortcut.Save()", quote(link), quote(exe), quote(exe.parent().unwrap_or(exe)))
+}
+
+/// The shortcuts in the start-menu and on the desktop.
+pub fn shortcut_paths() -> Result<Vec<PathBuf>, Error> {
+    let start_menu = get_config_dir()?
+        .join("Microsoft").join("Windows").join("Start Menu").join("Programs");
+    let mut paths = vec![start_menu.join(SHORTCUT_NAME)];
+    if let Some(home) = home::home_dir() {
+        paths.push(home.join("Desktop").join(SHORTCUT_NAME));
+    }
+    Ok(paths)
+}
+
+pub fn register_file_associations() -> Result<(), Error> {
+    let exe = env::current_exe().map_err(Error::io("failed to get the path of the app"))?;
+    for args in association_commands(&exe) {
+        run("reg", &args)?;
+    }
+    Ok(())
+}
+
+pub fn unregister_file_associations() -> Result<(), Error> {
+    for args in unassociation_commands() {
+        run("reg", &args)?;
+    }
+    Ok(())
+}
+
+pub fn create_shortcuts() -> Result<(), Error> {
+    let exe = env::current_exe().map_err(Error::io("failed to get the path of the app"))?;
+    for link in shortcut_paths()? {
+        run("powershell", &["-NoProfile", "-NonInteractive", "-Command", &shortcut_script(&link, &exe)].map(String::from))?;
+    }
+    Ok(())
+}
+
+pub fn remove_shortcuts() -> Result<(), Error> {
+    for link in shortcut_paths()? {
+        match std::fs::remove_file(&link) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                return Err(Error::io(format!("failed to remove {}", link.display()))(err));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn run(program: &str, args: &[String]) -> Result<(), Error> {
+    let mut command = Command::new(program);
+    command.args(args);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        // the app has no console, which would flash up for each command otherwise
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = command.output().map_err(Error::io(format!("failed to run {program}")))?;
+    if output.status.success() {
+        Ok(())
+    }
+    else {
+        Err(Error::Validation(format!("{program} failed with {}: {}", output.status, String::from_utf8_lossy(&output.stderr).trim())))
+    }
+}
+
+/// The data-directory of the user, where desktop-environments look for applications, icons and MIME-types.
+pub fn get_data_dir() -> Result<PathBuf, Error> {
+    // try using $XDG_DATA_HOME, otherwise use ~/.local/share
+    match env::var("XDG_DATA_HOME") {
+        Ok(path) if !path.is_empty() => Ok(PathBuf::from(path)),
+        _ => Ok(home::home_dir().ok_or_else(|| Error::Config(String::from("could not locate data directory")))?
+            .join(".local").join("share"))
+    }
+}
+
+/// The desktop-entry starting `exe` with a file, as of the Desktop Entry Specification.
+pub fn desktop_entry(exe: &Path) -> String {
+    // reserved characters in quoted arguments are escaped by a backslash, which is escaped again as in any string-value
+    let exe: String = exe.display().to_string().chars().flat_map(|c| {
+        if matches!(c, '"' | '`' | '$' | '\\') { vec!['\\', c] } else { vec![c] }
+    }).collect::<String>().replace('\\', "\\\\");
+    format!("[Desktop Entry]\nType=Application\nName=e-melder\nComment=Registrations for judo-tournaments\n\
+        Comment[de]=Meldungen für Judo-Turniere\nExec=\"{exe}\" %f\nIcon=e-melder\nTerminal=false\nCategories=Office;\n\
+        MimeType={};\n", MIME_TYPES.join(";"))
+}
+
+fn desktop_files(data_dir: &Path) -> [PathBuf; 3] {
+    [data_dir.join("applications").join(DESKTOP_ENTRY_NAME), data_dir.join("mime").join("packages").join(MIME_PACKAGE_NAME),
+        data_dir.join("icons").join("hicolor").join("scalable").join("apps").join(ICON_NAME)]
+}
+
+pub fn is_desktop_entry_installed(data_dir: &Path) -> bool {
+    data_dir.join("applications").join(DESKTOP_ENTRY_NAME).exists()
+}
+
+/// Adds the app to the application-menu and lists it for opening the tournament-files and drafts.
+pub fn install_desktop_entry(data_dir: &Path, exe: &Path) -> Result<(), Error> {
+    let [entry, package, icon] = desktop_files(data_dir);
+    for (path, contents) in [(&entry, desktop_entry(exe)), (&package, MIME_PACKAGE.to_owned()), (&icon, ICON.to_owned())] {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(Error::io(format!("failed to create {}", parent.display())))?;
+        }
+        std::fs::write(path, contents).map_err(Error::io(format!("failed to write {}", path.display())))?;
+    }
+    refresh_desktop_databases(data_dir);
+    Ok(())
+}
+
+pub fn uninstall_desktop_entry(data_dir: &Path) -> Result<(), Error> {
+    for path in desktop_files(data_dir) {
+        match std::fs::remove_file(&path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                return Err(Error::io(format!("failed to remove {}", path.display()))(err));
+            }
+            _ => {}
+        }
+    }
+    refresh_desktop_databases(data_dir);
+    Ok(())
+}
+
+fn refresh_desktop_databases(data_dir: &Path) {
+    // the caches are rebuilt by the desktop-environment eventually, if the tools are missing
+    for (program, dir) in [("update-mime-database", data_dir.join("mime")), ("update-desktop-database", data_dir.join("applications"))] {
+        if let Err(err) = run(program, &[dir.display().to_string()]) {
+            log::warn!("failed to run {program}, due to {err}");
+        }
+    }
+}
+
+pub fn install_xdg_integration() -> Result<(), Error> {
+    let exe = env::current_exe().map_err(Error::io("failed to get the path of the app"))?;
+    install_desktop_entry(&get_data_dir()?, &exe)?;
+    for mime_type in MIME_TYPES {
+        // only a default, the user may choose another application
+        if let Err(err) = run("xdg-mime", &["default", DESKTOP_ENTRY_NAME, mime_type].map(String::from)) {
+            log::warn!("failed to set the default application for {mime_type}, due to {err}");
+        }
+    }
+    Ok(())
+}
+
+pub fn uninstall_xdg_integration() -> Result<(), Error> {
+    uninstall_desktop_entry(&get_data_dir()?)
+}
@@ -0,0 +1,148 @@
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+pub const DEFAULT_SUBJECT_TEMPLATE: &str = "Meldung {club}: {name} am {date}";
+pub const DEFAULT_BODY_TEMPLATE: &str = "Hallo,\n\nanbei unsere Meldung für {name} am {date} in {place}.\n\nViele Grüße\n{club}";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SmtpSecurity {
+    // usually on port 587
+    #[default]
+    StartTls,
+    // usually on port 465
+    Tls
+}
+
+impl SmtpSecurity {
+    pub const ALL: [SmtpSecurity; 2] = [Self::StartTls, Self::Tls];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::StartTls => "STARTTLS",
+            Self::Tls => "SSL/TLS"
+        }
+    }
+}
+
+/// The SMTP-server and templates for mailing the tournament-files to the organizer.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct EmailSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    #[serde(default)]
+    pub user: String,
+    #[serde(default)]
+    pub password: String,
+    #[serde(default)]
+    pub from: String,
+    #[serde(default)]
+    pub recipient: String,
+    #[serde(default = "default_subject_template", rename = "subject-template")]
+    pub subject_template: String,
+    #[serde(default = "default_body_template", rename = "body-template")]
+    pub body_template: String
+}
+
+fn default_port() -> u16 {
+    587
+}
+
+fn default_subject_template() -> String {
+    DEFAULT_SUBJECT_TEMPLATE.to_owned()
+}
+
+fn default_body_template() -> String {
+    DEFAULT_BODY_TEMPLATE.to_owned()
+}
+
+impl Default for EmailSettings {
+    fn default() -> Self {
+        Self { enabled: false, host: String::new(), port: default_port(), security: SmtpSecurity::default(), user: String::new(),
+            password: String::new(), from: String::new(), recipient: String::new(), subject_template: default_subject_template(),
+            body_template: default_body_template() }
+    }
+}
+
+impl EmailSettings {
+    pub fn is_configured(&self) -> bool {
+        self.enabled && !self.host.trim().is_empty() && !self.from.trim().is_empty()
+    }
+}
+
+/// A registration to be mailed, editable before sending.
+#[derive(Clone, Debug)]
+pub struct RegistrationEmail {
+    pub recipient: String,
+    pub subject: String,
+    pub body: String,
+    pub attachments: Vec<PathBuf>
+}
+
+impl RegistrationEmail {
+    /// Fills the templates, which know the placeholders {name}, {date}, {place} and {club}.
+    pub fn new(settings: &EmailSettings, name: &str, date: NaiveDate, place: &str, club: &str, attachments: Vec<PathBuf>) -> Self {
+        let render = |template: &str| template.replace("{name}", name).replace("{date}", &date.format("%d.%m.%Y").to_string())
+            .replace("{place}", place).replace("{club}", club);
+        Self { recipient: settings.recipient.clone(), subject: render(&settings.subject_template), body: render(&settings.body_template),
+            attachments }
+    }
+
+    pub fn to_message(&self, settings: &EmailSettings) -> Result<Message, Error> {
+        let from: Mailbox = settings.from.trim().parse().map_err(|err| Error::Validation(format!("{}: {err}", settings.from)))?;
+        let mut builder = Message::builder().from(from).subject(&self.subject);
+        // several recipients are separated by commas, e.g. the organizer and the own coach
+        for recipient in self.recipient.split(',').map(str::trim).filter(|recipient| !recipient.is_empty()) {
+            let recipient: Mailbox = recipient.parse().map_err(|err| Error::Validation(format!("{recipient}: {err}")))?;
+            builder = builder.to(recipient);
+        }
+        let mut multipart = MultiPart::mixed().singlepart(SinglePart::plain(self.body.clone()));
+        for path in &self.attachments {
+            multipart = multipart.singlepart(attachment(path)?);
+        }
+        builder.multipart(multipart).map_err(|err| Error::Validation(err.to_string()))
+    }
+
+    pub fn send(&self, settings: &EmailSettings) -> Result<(), Error> {
+        let message = self.to_message(settings)?;
+        let host = settings.host.trim();
+        let builder = match settings.security {
+            SmtpSecurity::StartTls => SmtpTransport::starttls_relay(host),
+            SmtpSecurity::Tls => SmtpTransport::relay(host)
+        }.map_err(|err| Error::Network(format!("{host}: {err}")))?.port(settings.port);
+        let builder = if settings.user.is_empty() {
+            builder
+        }
+        else {
+            builder.credentials(Credentials::new(settings.user.clone(), settings.password.clone()))
+        };
+        builder.build().send(&message).map_err(|err| Error::Network(format!("{host}: {err}")))?;
+        Ok(())
+    }
+}
+
+fn attachment(path: &Path) -> Result<SinglePart, Error> {
+    let contents = std::fs::read(path).map_err(Error::io(format!("failed to read {}", path.display())))?;
+    let file_name = path.file_name().map(|file_name| file_name.to_string_lossy().into_owned()).unwrap_or_default();
+    let content_type = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("pdf") => "application/pdf",
+        Some("csv") => "text/csv",
+        _ => "application/octet-stream"
+    };
+    Ok(Attachment::new(file_name).body(contents, ContentType::parse(content_type).map_err(|err| Error::Validation(err.to_string()))?))
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    Config(String),
+    Io { context: String, source: io::Error },
+    Parse { context: String, source: serde_json::Error },
+    Validation(String),
+    Network(String),
+    Cancelled
+}
+
+impl Error {
+    pub fn io(context: impl Into<String>) -> impl FnOnce(io::Error) -> Self {
+        // for use in `map_err`
+        let context = context.into();
+        move |source| Self::Io { context, source }
+    }
+
+    pub fn parse(context: impl Into<String>) -> impl FnOnce(serde_json::Error) -> Self {
+        let context = context.into();
+        move |source| Self::Parse { context, source }
+    }
+
+    pub fn translation_key(&self) -> &'static str {
+        match self {
+            Self::Config(_) => "error.config",
+            Self::Io { .. } => "error.io",
+            Self::Parse { .. } => "error.parse",
+            Self::Validation(_) => "error.validation",
+            Self::Network(_) => "error.network",
+            Self::Cancelled => "error.cancelled"
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Config(context) | Self::Validation(context) | Self::Network(context) => write!(f, "{context}"),
+            Self::Io { context, source } => write!(f, "{context}: {source}"),
+            Self::Parse { context, source } => write!(f, "{context}: {source}"),
+            Self::Cancelled => write!(f, "cancelled by the user")
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Parse { source, .. } => Some(source),
+            _ => None
+        }
+    }
+}
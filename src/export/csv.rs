@@ -0,0 +1,19 @@
+use std::path::Path;
+
+use crate::error::Error;
+use crate::tournament_info::{Club, CsvExport, Exam, Tournament};
+use crate::utils::write_csv_export;
+
+/// Writes the athletes of `tournament` as CSV, e.g. for organizers without an import of the tournament-files.
+pub fn write_entry_list_csv(path: &Path, tournament: &Tournament) -> Result<(), Error> {
+    let export = CsvExport::default();
+    write_csv_export(path, &export, &export.render_tournament(tournament))
+        .map_err(Error::io(format!("failed to write {}", path.display())))
+}
+
+/// Writes the candidates of a Kyu-examination as CSV, e.g. for the examiners or the state association.
+pub fn write_exam_csv(path: &Path, exam: &Exam, club: &Club) -> Result<(), Error> {
+    let export = CsvExport::default();
+    write_csv_export(path, &export, &export.render_exam(exam, club))
+        .map_err(Error::io(format!("failed to write {}", path.display())))
+}
@@ -0,0 +1,86 @@
+use std::io;
+use std::path::Path;
+
+use chrono::{NaiveDate, Utc};
+
+use crate::error::Error;
+
+const LINE_END: &str = "\r\n";
+// lines are folded after this many octets, as of RFC 5545
+const MAX_LINE_LENGTH: usize = 75;
+
+/// The identifier of the event of a tournament, so registering again replaces the event instead of adding another.
+pub fn event_uid(name: &str, date: NaiveDate) -> String {
+    let slug: String = name.chars().map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' }).collect();
+    format!("{}-{slug}@e-melder", date.format("%Y%m%d"))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace('\n', "\\n")
+}
+
+fn fold(line: &str) -> String {
+    // continuation-lines start with a space, characters are never split
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str(LINE_END);
+            folded.push(' ');
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+/// The all-day event of a tournament.
+pub fn render_event(name: &str, place: &str, date: NaiveDate) -> String {
+    let end = date.succ_opt().unwrap_or(date);
+    [
+        String::from("BEGIN:VEVENT"),
+        format!("UID:{}", event_uid(name, date)),
+        format!("DTSTAMP:{}", Utc::now().format("%Y%m%dT%H%M%SZ")),
+        format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+        format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")),
+        format!("SUMMARY:{}", escape(name)),
+        format!("LOCATION:{}", escape(place)),
+        String::from("END:VEVENT")
+    ].iter().map(|line| fold(line) + LINE_END).collect()
+}
+
+/// Adds the event to `calendar`, replacing an earlier one of the same tournament.
+pub fn add_event(calendar: &str, name: &str, place: &str, date: NaiveDate) -> String {
+    let uid = format!("UID:{}", event_uid(name, date));
+    let mut events = Vec::new();
+    let mut event: Option<Vec<&str>> = None;
+    for line in calendar.lines() {
+        if line == "BEGIN:VEVENT" {
+            event = Some(Vec::new());
+        }
+        if let Some(lines) = &mut event {
+            lines.push(line);
+            if line == "END:VEVENT" {
+                // the UID might be folded like any other line
+                let unfolded = lines.join("\n").replace("\n ", "");
+                if !unfolded.lines().any(|line| line == uid) {
+                    events.push(lines.join(LINE_END) + LINE_END);
+                }
+                event = None;
+            }
+        }
+    }
+    events.push(render_event(name, place, date));
+    format!("BEGIN:VCALENDAR{LINE_END}VERSION:2.0{LINE_END}PRODID:-//e-melder//Meldungen//DE{LINE_END}{}END:VCALENDAR{LINE_END}",
+        events.concat())
+}
+
+pub fn write_calendar_event(path: &Path, name: &str, place: &str, date: NaiveDate) -> Result<(), Error> {
+    let calendar = match std::fs::read_to_string(path) {
+        Ok(calendar) => calendar,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(Error::io(format!("failed to read {}", path.display()))(err))
+    };
+    std::fs::write(path, add_event(&calendar, name, place, date)).map_err(Error::io(format!("failed to write {}", path.display())))
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Document, Object, Stream};
+
+use crate::error::Error;
+use crate::tournament_info::{Club, Exam, Tournament};
+use crate::utils::{string_to_windows_1252_bytes, translate};
+
+// A4 in points
+const PAGE_WIDTH: i64 = 595;
+const PAGE_HEIGHT: i64 = 842;
+const MARGIN: i64 = 50;
+const ROW_HEIGHT: i64 = 16;
+const FONT_SIZE: i64 = 10;
+const TITLE_SIZE: i64 = 16;
+// the left edges of the number, surname, given name, birth year, belt and weight category or examined belt
+const COLUMNS: [i64; 6] = [MARGIN, 80, 220, 360, 430, 500];
+
+/// Renders the printable entry list (Meldeliste) of `tournament`.
+pub fn render_entry_list(tournament: &Tournament, translations: &HashMap<String, String>) -> Result<Vec<u8>, Error> {
+    let mut header = vec![
+        format!("{}: {}", translate!("entry_list.tournament", translations), tournament.get_name()),
+        format!("{}: {}, {}", translate!("entry_list.date_place", translations), tournament.get_date().format("%d.%m.%Y"),
+            tournament.get_place()),
+        format!("{}: {} ({})", translate!("entry_list.category", translations), tournament.get_age_category(),
+            tournament.get_gender_category().render())
+    ];
+    if !tournament.get_club().get_name().is_empty() {
+        header.push(format!("{}: {}", translate!("entry_list.club", translations), tournament.get_club().get_name()));
+    }
+    let column_names = ["entry_list.number", "entry_list.sur_name", "entry_list.given_name", "entry_list.birth_year",
+        "entry_list.belt", "entry_list.weight_category"].map(|key| translate!(key, translations));
+    let rows: Vec<[String; 6]> = tournament.get_athletes().iter().enumerate().map(|(index, athlete)| [
+        (index + 1).to_string(), athlete.get_sur_name().to_owned(), athlete.get_given_name().to_owned(),
+        athlete.get_birth_year().to_string(), athlete.get_belt().to_display(), athlete.get_weight_category().to_string()
+    ]).collect();
+    render_list(&translate!("entry_list.title", translations), &header, &column_names, &rows)
+}
+
+pub fn write_entry_list(path: &Path, tournament: &Tournament, translations: &HashMap<String, String>) -> Result<(), Error> {
+    let bytes = render_entry_list(tournament, translations)?;
+    std::fs::write(path, bytes).map_err(Error::io(format!("failed to write {}", path.display())))
+}
+
+/// Renders the printable list of the candidates of a Kyu-examination.
+pub fn render_exam_list(exam: &Exam, club: &Club, translations: &HashMap<String, String>) -> Result<Vec<u8>, Error> {
+    let mut header = vec![format!("{}: {}, {}", translate!("entry_list.date_place", translations), exam.date.format("%d.%m.%Y"),
+        exam.place)];
+    if !club.get_name().is_empty() {
+        header.push(format!("{}: {}", translate!("entry_list.club", translations), club.get_name()));
+    }
+    let column_names = ["entry_list.number", "entry_list.sur_name", "entry_list.given_name", "entry_list.birth_year",
+        "entry_list.belt", "exam.list.target"].map(|key| translate!(key, translations));
+    let rows: Vec<[String; 6]> = exam.candidates.iter().enumerate().map(|(index, candidate)| [
+        (index + 1).to_string(), candidate.get_sur_name().to_owned(), candidate.get_given_name().to_owned(),
+        candidate.get_birth_year().to_string(), candidate.get_belt().to_display(), candidate.get_target().to_display()
+    ]).collect();
+    render_list(&translate!("exam.list.title", translations), &header, &column_names, &rows)
+}
+
+pub fn write_exam_list(path: &Path, exam: &Exam, club: &Club, translations: &HashMap<String, String>) -> Result<(), Error> {
+    let bytes = render_exam_list(exam, club, translations)?;
+    std::fs::write(path, bytes).map_err(Error::io(format!("failed to write {}", path.display())))
+}
+
+fn render_list(title: &str, header: &[String], column_names: &[String; 6], rows: &[[String; 6]]) -> Result<Vec<u8>, Error> {
+    let mut pages = Vec::new();
+    let mut operations = Vec::new();
+    let mut y = PAGE_HEIGHT - MARGIN - TITLE_SIZE;
+    text(&mut operations, "F2", TITLE_SIZE, MARGIN, y, title);
+    y -= ROW_HEIGHT * 2;
+    for line in header {
+        text(&mut operations, "F1", FONT_SIZE, MARGIN, y, line);
+        y -= ROW_HEIGHT;
+    }
+    y -= ROW_HEIGHT;
+    table_row(&mut operations, "F2", y, column_names);
+    for row in rows {
+        y -= ROW_HEIGHT;
+        if y < MARGIN {
+            // the column names are repeated on every page
+            pages.push(std::mem::take(&mut operations));
+            y = PAGE_HEIGHT - MARGIN - FONT_SIZE;
+            table_row(&mut operations, "F2", y, column_names);
+            y -= ROW_HEIGHT;
+        }
+        table_row(&mut operations, "F1", y, row);
+    }
+    pages.push(operations);
+
+    let mut document = Document::with_version("1.5");
+    let pages_id = document.new_object_id();
+    // the standard fonts need no embedding and know the German umlauts in WinAnsiEncoding
+    let regular_id = document.add_object(dictionary! {
+        "Type" => "Font", "Subtype" => "Type1", "BaseFont" => "Helvetica", "Encoding" => "WinAnsiEncoding"
+    });
+    let bold_id = document.add_object(dictionary! {
+        "Type" => "Font", "Subtype" => "Type1", "BaseFont" => "Helvetica-Bold", "Encoding" => "WinAnsiEncoding"
+    });
+    let resources_id = document.add_object(dictionary! {
+        "Font" => dictionary! { "F1" => regular_id, "F2" => bold_id }
+    });
+    let mut kids = Vec::new();
+    for operations in pages {
+        let content = Content { operations }.encode().map_err(|err| Error::Validation(format!("failed to render list: {err}")))?;
+        let content_id = document.add_object(Stream::new(dictionary! {}, content));
+        kids.push(Object::from(document.add_object(dictionary! {
+            "Type" => "Page", "Parent" => pages_id, "Contents" => content_id
+        })));
+    }
+    let count = i64::try_from(kids.len()).unwrap_or(i64::MAX);
+    document.objects.insert(pages_id, Object::Dictionary(dictionary! {
+        "Type" => "Pages", "Kids" => kids, "Count" => count, "Resources" => resources_id,
+        "MediaBox" => vec![0.into(), 0.into(), PAGE_WIDTH.into(), PAGE_HEIGHT.into()]
+    }));
+    let catalog_id = document.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+    document.trailer.set("Root", catalog_id);
+    document.compress();
+
+    let mut bytes = Vec::new();
+    document.save_to(&mut bytes).map_err(|err| Error::Validation(format!("failed to render list: {err}")))?;
+    Ok(bytes)
+}
+
+fn table_row(operations: &mut Vec<Operation>, font: &str, y: i64, cells: &[String; 6]) {
+    for (x, cell) in COLUMNS.into_iter().zip(cells) {
+        text(operations, font, FONT_SIZE, x, y, cell);
+    }
+}
+
+fn text(operations: &mut Vec<Operation>, font: &str, size: i64, x: i64, y: i64, text: &str) {
+    operations.extend([
+        Operation::new("BT", vec![]),
+        Operation::new("Tf", vec![font.into(), size.into()]),
+        Operation::new("Td", vec![x.into(), y.into()]),
+        Operation::new("Tj", vec![Object::string_literal(string_to_windows_1252_bytes(text))]),
+        Operation::new("ET", vec![])
+    ]);
+}
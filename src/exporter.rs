@@ -0,0 +1,132 @@
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::tournament_info::{Club, RegisteringAthlete};
+
+/// How long a plugin may run, before it is killed, e.g. when it waits for input, which never comes.
+pub const PLUGIN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The registration as passed to exporters, external ones receive it as JSON on stdin.
+#[derive(Debug, Serialize)]
+pub struct Registration<'a> {
+    pub name: &'a str,
+    pub place: &'a str,
+    pub date: NaiveDate,
+    pub club: &'a Club,
+    pub athletes: &'a [RegisteringAthlete]
+}
+
+pub trait Exporter {
+    fn name(&self) -> String;
+
+    fn export(&self, registration: &Registration, output_dir: &Path) -> Result<(), Error>;
+}
+
+/// An executable in the plugins-directory, run in the output-directory with the registration on stdin.
+#[derive(Clone, Debug)]
+pub struct PluginExporter {
+    path: PathBuf
+}
+
+impl PluginExporter {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Runs the plugin with `input` on stdin, it is killed after `timeout`.
+    pub fn run(&self, input: Vec<u8>, output_dir: &Path, timeout: Duration) -> Result<(), Error> {
+        let mut child = Command::new(&self.path).current_dir(output_dir)
+            .stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::piped()).spawn()
+            .map_err(Error::io(format!("failed to start {}", self.path.display())))?;
+        // stdin and stderr are handled by threads of their own, so a plugin writing a lot to stderr
+        // before reading its input does not block both sides
+        let writer = child.stdin.take().map(|mut stdin| std::thread::spawn(move || stdin.write_all(&input)));
+        let reader = child.stderr.take().map(|mut stderr| std::thread::spawn(move || {
+            let mut output = Vec::new();
+            let _ = stderr.read_to_end(&mut output);
+            output
+        }));
+
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait().map_err(Error::io(format!("failed to run {}", self.path.display())))? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                if let Err(err) = child.kill().and_then(|()| child.wait().map(|_| ())) {
+                    log::warn!("failed to kill {}, due to {err}", self.path.display());
+                }
+                return Err(Error::Validation(format!("{} did not finish within {} seconds", self.name(), timeout.as_secs())));
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        };
+
+        if let Some(Ok(Err(err))) = writer.map(std::thread::JoinHandle::join) {
+            // a plugin might exit without reading its input
+            if err.kind() != io::ErrorKind::BrokenPipe {
+                return Err(Error::io(format!("failed to pass registration to {}", self.path.display()))(err));
+            }
+        }
+        if status.success() {
+            Ok(())
+        }
+        else {
+            let stderr = reader.and_then(|reader| reader.join().ok()).unwrap_or_default();
+            Err(Error::Validation(format!("{} failed with {status}: {}", self.name(), String::from_utf8_lossy(&stderr).trim())))
+        }
+    }
+}
+
+impl Registration<'_> {
+    /// The registration as passed to plugins.
+    pub fn to_json(&self) -> Result<Vec<u8>, Error> {
+        serde_json::to_vec(self).map_err(Error::parse("failed to serialise registration"))
+    }
+}
+
+impl Exporter for PluginExporter {
+    fn name(&self) -> String {
+        self.path.file_stem().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    fn export(&self, registration: &Registration, output_dir: &Path) -> Result<(), Error> {
+        self.run(registration.to_json()?, output_dir, PLUGIN_TIMEOUT)
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata().is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|extension| {
+        ["exe", "bat", "cmd"].iter().any(|executable| extension.eq_ignore_ascii_case(executable))
+    })
+}
+
+/// Finds the executables in `plugins_dir`, a missing directory means no plugins are installed.
+pub fn discover_plugins(plugins_dir: &Path) -> Result<Vec<PluginExporter>, Error> {
+    let entries = match std::fs::read_dir(plugins_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(Error::io(format!("failed to read {}", plugins_dir.display()))(err))
+    };
+    let mut plugins = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(Error::io(format!("failed to read {}", plugins_dir.display())))?.path();
+        if is_executable(&path) {
+            plugins.push(PluginExporter::new(path));
+        }
+    }
+    plugins.sort_by(|plugin, other| plugin.path.cmp(&other.path));
+    Ok(plugins)
+}
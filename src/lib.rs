@@ -0,0 +1,9 @@
+pub mod api;
+pub mod desktop_integration;
+pub mod email;
+pub mod error;
+pub mod export;
+pub mod exporter;
+pub mod tournament_info;
+pub mod ui;
+pub mod utils;
@@ -1,9 +1,5 @@
 #![windows_subsystem = "windows"]
 
-mod tournament_info;
-mod ui;
-mod utils;
-
 use std::fs::{create_dir_all, File};
 use std::io::Write;
 
@@ -12,9 +8,11 @@ use log4rs::append::file::FileAppender;
 use log4rs::config::{Appender, Logger, Root};
 use log4rs::encode::pattern::PatternEncoder;
 
-use utils::{crash, get_config_dir, get_config_file, get_default_config, DEFAULT_WINDOW_SIZE};
+use e_melder_gui::{translate_raw, ui};
+use e_melder_gui::utils::{crash, get_config_dir, get_config_file, get_default_config, write_schemas, DEFAULT_WINDOW_SIZE};
 #[cfg(not(feature="unstable"))]
-use utils::{get_configs, update_translations, write_language, DEFAULT_TRANSLATIONS_DE, DEFAULT_TRANSLATIONS_EN};
+use e_melder_gui::utils::{backup_files, get_backup_dir, get_configs, read_latest_version, update_translations, write_language, Progress,
+    DEFAULT_TRANSLATIONS_DE, DEFAULT_TRANSLATIONS_EN, VERSION};
 
 #[allow(clippy::too_many_lines)]
 fn main() -> Result<(), eframe::Error> {
@@ -42,6 +40,22 @@ fn main() -> Result<(), eframe::Error> {
     });
     log::info!("New run of the app");
 
+    // undocumented, for developers of tools generating files for the app
+    let mut args = std::env::args();
+    if args.any(|arg| arg == "--emit-schemas") {
+        let dir = args.next().unwrap_or_else(|| String::from("schemas"));
+        match write_schemas(std::path::Path::new(&dir)) {
+            Ok(()) => {
+                log::info!("wrote schemas to {dir}");
+            }
+            Err(err) => {
+                log::error!("failed to write schemas, due to {err}");
+                crash();
+            }
+        }
+        return Ok(());
+    }
+
     let config_file = match get_config_file() {
         Ok(config_file) => config_file,
         Err(err) => {
@@ -61,7 +75,7 @@ fn main() -> Result<(), eframe::Error> {
             }
         }
 
-        let mut config_file = match File::options().write(true).create_new(true).open(config_file) {
+        let mut config_file = match File::options().write(true).create_new(true).open(&config_file) {
             Ok(config_file) => config_file,
             Err(err) => {
                 log::error!("failed to create config-file, due to {err}");
@@ -124,6 +138,16 @@ fn main() -> Result<(), eframe::Error> {
     }
 
 
+    // the files are backed up before a new version migrates them
+    #[cfg(not(feature="unstable"))]
+    match read_latest_version() {
+        Ok(Some(latest_version)) if latest_version != VERSION => backup_before_update(&latest_version, &config_file),
+        Ok(_) => {}
+        Err(err) => {
+            log::warn!("failed to read the version of the last run, due to {err}");
+        }
+    }
+
     #[cfg(not(feature="unstable"))]
     match update_translations() {
         Ok(()) => {}
@@ -181,6 +205,10 @@ fn main() -> Result<(), eframe::Error> {
         }
     }
 
+    let kiosk_mode = std::env::args().any(|arg| arg == "--kiosk");
+    // e.g. a file opened by the file-association
+    let open_file = std::env::args().skip(1).find(|arg| !arg.starts_with("--")).map(std::path::PathBuf::from);
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size(DEFAULT_WINDOW_SIZE),
         renderer: eframe::Renderer::Wgpu,
@@ -188,10 +216,30 @@ fn main() -> Result<(), eframe::Error> {
         ..Default::default()
     };
 
-    eframe::run_native(translate_raw!("application.title").as_str(), options, Box::new(|cc| {
-        match ui::EMelderApp::new(cc) {
-            Ok(app) => Ok(Box::new(app)),
+    eframe::run_native(translate_raw!("application.title").as_str(), options, Box::new(move |cc| {
+        match ui::EMelderApp::new(cc, kiosk_mode) {
+            Ok(mut app) => {
+                if let Some(path) = &open_file {
+                    app.open_file(path);
+                }
+                Ok(Box::new(app))
+            }
             Err(err) => Err(Box::new(err))
         }
     }))
 }
+
+#[cfg(not(feature="unstable"))]
+fn backup_before_update(latest_version: &str, config_file: &std::path::Path) {
+    match get_backup_dir(latest_version) {
+        Ok(backup_dir) => {
+            match backup_files(&backup_dir, config_file, &Progress::default()) {
+                Ok(()) => log::info!("backed up files of version {latest_version} to {}", backup_dir.display()),
+                Err(err) => log::warn!("failed to back up files before the update, due to {err}")
+            }
+        }
+        Err(err) => {
+            log::warn!("failed to get backup-directory, due to {err}");
+        }
+    }
+}
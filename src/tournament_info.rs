@@ -1,11 +1,17 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use chrono::NaiveDate;
+use chrono::{DateTime, Datelike, Local, Months, NaiveDate};
 use enum_map::Enum;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use uuid::Uuid;
 
-#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+use crate::utils::{escape_html, Progress};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all="lowercase")]
 pub enum Belt {
     #[default]
@@ -31,6 +37,10 @@ pub enum Belt {
 }
 
 impl Belt {
+    pub const ALL: [Belt; 19] = [Belt::Kyu9, Belt::Kyu8, Belt::Kyu7, Belt::Kyu6, Belt::Kyu5, Belt::Kyu4, Belt::Kyu3, Belt::Kyu2,
+        Belt::Kyu1, Belt::Dan1, Belt::Dan2, Belt::Dan3, Belt::Dan4, Belt::Dan5, Belt::Dan6, Belt::Dan7, Belt::Dan8, Belt::Dan9,
+        Belt::Dan10];
+
     pub fn to_number(self) -> u8 {
         // number used for serialisation by the official application
         match self {
@@ -56,10 +66,36 @@ impl Belt {
         }
     }
 
+    pub fn from_number(number: u8) -> Option<Self> {
+        Some(match number {
+            1 => Self::Kyu9,
+            2 => Self::Kyu8,
+            3 => Self::Kyu7,
+            4 => Self::Kyu6,
+            5 => Self::Kyu5,
+            6 => Self::Kyu4,
+            7 => Self::Kyu3,
+            8 => Self::Kyu2,
+            9 => Self::Kyu1,
+            10 => Self::Dan1,
+            11 => Self::Dan2,
+            12 => Self::Dan3,
+            13 => Self::Dan4,
+            14 => Self::Dan5,
+            15 => Self::Dan6,
+            16 => Self::Dan7,
+            17 => Self::Dan8,
+            18 => Self::Dan9,
+            19 => Self::Dan10,
+            _ => return None
+        })
+    }
+
     pub fn render(self) -> String {
         format!("{}", self.to_number())
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         Some(match s {
             "kyu9" => Self::Kyu9,
@@ -133,6 +169,28 @@ impl Belt {
             Self::Dan10 => "dan10"
         })
     }
+
+    pub fn to_display(self) -> String {
+        // the common spelling, e.g. "8. Kyu"
+        let serialised = self.serialise();
+        let (kind, number) = serialised.split_at(3);
+        format!("{number}. {}", if kind == "kyu" { "Kyu" } else { "Dan" })
+    }
+
+    /// The belt examined for after this one in a Kyu-examination, none for the 1. Kyu and the Dan-grades.
+    pub fn next_kyu(self) -> Option<Self> {
+        if self.to_number() < Belt::Kyu1.to_number() { Belt::from_number(self.to_number() + 1) } else { None }
+    }
+
+    pub fn from_display(s: &str) -> Option<Self> {
+        // besides the serialised form, also the common spellings like "8. Kyu" or "1 Dan" are accepted
+        let s: String = s.chars().filter(|c| !matches!(c, '.' | ' ')).collect::<String>().to_lowercase();
+        if let Some(belt) = Self::from_str(&s) {
+            return Some(belt);
+        }
+        let (number, kind) = s.split_at(s.find(|c: char| !c.is_ascii_digit())?);
+        Self::from_str(&format!("{kind}{number}"))
+    }
 }
 
 impl FromStr for Belt {
@@ -172,6 +230,7 @@ impl WeightCategory {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         let kind = if s.starts_with('-') {
             WeightCategoryKind::Under
@@ -185,33 +244,163 @@ impl WeightCategory {
     }
 
     #[allow(clippy::inherent_to_string)]
-    fn to_string(self) -> String {
+    pub fn to_string(self) -> String {
         match self.kind {
             WeightCategoryKind::Under => format!("-{}", self.limit),
             WeightCategoryKind::Over => format!("+{}", self.limit)
         }
     }
+
+    pub fn is_default(self) -> bool {
+        // athletes of legacy files get the default, which is no weight class of any age category
+        let default = Self::default();
+        matches!(self.kind, WeightCategoryKind::Under) && self.limit == default.limit
+    }
+
+    fn order(self) -> u16 {
+        // +66 is heavier than -66
+        match self.kind {
+            WeightCategoryKind::Under => 2 * u16::from(self.limit),
+            WeightCategoryKind::Over => 2 * u16::from(self.limit) + 1
+        }
+    }
+
+    pub fn official(age_category: &str, gender_category: GenderCategory) -> &'static [&'static str] {
+        // the weight classes of the German Judo Federation, younger age categories are weighed in groups instead
+        match (normalize_age_category(age_category).as_str(), gender_category) {
+            ("U13", GenderCategory::Male) => &["-28", "-31", "-34", "-37", "-40", "-43", "-46", "-50", "-55", "+55"],
+            ("U13", GenderCategory::Female) => &["-28", "-30", "-33", "-36", "-40", "-44", "-48", "-52", "-57", "+57"],
+            ("U15", GenderCategory::Male) => &["-34", "-37", "-40", "-43", "-46", "-50", "-55", "-60", "-66", "+66"],
+            ("U15", GenderCategory::Female) => &["-33", "-36", "-40", "-44", "-48", "-52", "-57", "-63", "+63"],
+            ("U18", GenderCategory::Male) => &["-46", "-50", "-55", "-60", "-66", "-73", "-81", "-90", "+90"],
+            ("U18", GenderCategory::Female) => &["-40", "-44", "-48", "-52", "-57", "-63", "-70", "+70"],
+            ("U21", GenderCategory::Male) => &["-60", "-66", "-73", "-81", "-90", "-100", "+100"],
+            ("U21", GenderCategory::Female) => &["-48", "-52", "-57", "-63", "-70", "-78", "+78"],
+            // the adults, as suggested by `suggest_age_category`
+            ("Männer", GenderCategory::Male) => &["-60", "-66", "-73", "-81", "-90", "-100", "+100"],
+            ("Frauen", GenderCategory::Female) => &["-48", "-52", "-57", "-63", "-70", "-78", "+78"],
+            _ => &[]
+        }
+    }
+
+    /// The official class nearest to `current`, if `current` is no official class of the age- and gender-category.
+    pub fn nearest_official(current: &str, age_category: &str, gender_category: GenderCategory) -> Option<&'static str> {
+        let official = Self::official(age_category, gender_category);
+        // unparsable and default classes are reported otherwise
+        let current = Self::from_str(current).filter(|current| !current.is_default())?;
+        // the athlete fits into the next heavier class, e.g. -35 into -37
+        let nearest = official.iter().copied()
+            .find(|class| Self::from_str(class).is_some_and(|class| class.order() >= current.order()))
+            .or_else(|| official.last().copied())?;
+        (Self::from_str(nearest)?.order() != current.order()).then_some(nearest)
+    }
+
+    pub fn step(current: &str, age_category: &str, gender_category: GenderCategory, up: bool) -> Option<&'static str> {
+        let official = Self::official(age_category, gender_category);
+        let Some(current) = Self::from_str(current).map(Self::order) else {
+            // start at the lightest or heaviest class, if nothing sensible is entered yet
+            return if up { official.first() } else { official.last() }.copied();
+        };
+        let mut classes = official.iter().filter_map(|class| Some((*class, Self::from_str(class)?.order())));
+        if up {
+            classes.find(|(_, order)| *order > current).map(|(class, _)| class)
+        }
+        else {
+            classes.rfind(|(_, order)| *order < current).map(|(class, _)| class)
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Athlete {
+    // athletes of files written before ids were introduced get the nil id, see `assign_missing_ids`
+    #[serde(default = "Uuid::nil")]
+    id: Uuid,
     #[serde(rename="given")]
     given_name: String,
     #[serde(rename="sur")]
     sur_name: String,
     belt: Belt,
-    #[serde(skip)]
+    #[serde(default, rename="weight", serialize_with="crate::utils::serialize_weight_category",
+    deserialize_with="crate::utils::deserialize_weight_category")]
+    #[schemars(with = "String")]
     weight_category: WeightCategory,
     #[serde(rename="year")]
     birth_year: u16,
     #[serde(default, serialize_with="crate::utils::serialize_gender_category",
     deserialize_with="crate::utils::deserialize_gender_category")]
-    gender: GenderCategory
+    #[schemars(with = "String")]
+    gender: GenderCategory,
+    #[serde(default)]
+    group: String,
+    #[serde(default, rename="referee-license", skip_serializing_if="Option::is_none")]
+    referee_license: Option<RefereeLicense>,
+    #[serde(default, rename="consent-expiry", skip_serializing_if="Option::is_none")]
+    consent_expiry: Option<NaiveDate>,
+    #[serde(default, rename="medical-certificate-expiry", skip_serializing_if="Option::is_none")]
+    medical_certificate_expiry: Option<NaiveDate>,
+    #[serde(default, rename="last-registration", skip_serializing_if="Option::is_none")]
+    last_registration: Option<LastRegistration>
+}
+
+/// The categories an athlete was registered in most recently, prefilled when registering them again.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct LastRegistration {
+    date: NaiveDate,
+    #[serde(rename="age-category")]
+    age_category: String,
+    #[serde(rename="weight")]
+    weight_category: String
+}
+
+impl LastRegistration {
+    pub fn get_date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn get_age_category(&self) -> &str {
+        &self.age_category
+    }
+
+    pub fn get_weight_category(&self) -> &str {
+        &self.weight_category
+    }
+}
+
+/// What tells athletes apart, all records of athletes are compared by it: the id, or for records without one,
+/// e.g. of files written before ids were introduced, the name and birth year.
+#[derive(Clone, Copy)]
+struct Identity<'a> {
+    id: Uuid,
+    given_name: &'a str,
+    sur_name: &'a str,
+    birth_year: u16
+}
+
+impl Identity<'_> {
+    /// For records of the same file, where namesakes with their own ids are different athletes.
+    fn matches(self, other: Identity<'_>) -> bool {
+        if !self.id.is_nil() && !other.id.is_nil() {
+            return self.id == other.id;
+        }
+        self.has_same_name(other)
+    }
+
+    /// For records of another file or machine, which assigned its own ids to the same athletes.
+    fn matches_across(self, other: Identity<'_>) -> bool {
+        (!self.id.is_nil() && self.id == other.id) || self.has_same_name(other)
+    }
+
+    fn has_same_name(self, other: Identity<'_>) -> bool {
+        self.given_name == other.given_name && self.sur_name == other.sur_name && self.birth_year == other.birth_year
+    }
 }
 
 impl Athlete {
     pub fn new(given_name: String, sur_name: String, birth_year: u16, belt: Belt, weight_category: WeightCategory, gender: GenderCategory) -> Self {
-        Self { given_name, sur_name, belt, weight_category, birth_year, gender }
+        Self { id: Uuid::new_v4(), given_name: normalize_name(&given_name), sur_name: normalize_name(&sur_name), belt,
+            weight_category, birth_year, gender, group: String::new(),
+            referee_license: None, consent_expiry: None, medical_certificate_expiry: None, last_registration: None }
     }
 
     pub fn render(&self) -> String {
@@ -220,6 +409,10 @@ impl Athlete {
         format!(include_str!("athlete-format"), self.sur_name, self.given_name, self.belt.render(), self.weight_category.render(), self.birth_year)
     }
 
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
     pub fn get_given_name(&self) -> &str {
         &self.given_name
     }
@@ -236,6 +429,14 @@ impl Athlete {
         &mut self.belt
     }
 
+    pub fn get_weight_category(&self) -> WeightCategory {
+        self.weight_category
+    }
+
+    pub fn get_weight_category_mut(&mut self) -> &mut WeightCategory {
+        &mut self.weight_category
+    }
+
     pub fn get_birth_year(&self) -> u16 {
         self.birth_year
     }
@@ -247,9 +448,150 @@ impl Athlete {
     pub fn get_gender_mut(&mut self) -> &mut GenderCategory {
         &mut self.gender
     }
+
+    pub fn get_group(&self) -> &str {
+        &self.group
+    }
+
+    pub fn get_group_mut(&mut self) -> &mut String {
+        &mut self.group
+    }
+
+    pub fn get_referee_license(&self) -> Option<&RefereeLicense> {
+        self.referee_license.as_ref()
+    }
+
+    pub fn get_referee_license_mut(&mut self) -> &mut Option<RefereeLicense> {
+        &mut self.referee_license
+    }
+
+    pub fn get_document_expiry_mut(&mut self, document: Document) -> &mut Option<NaiveDate> {
+        match document {
+            Document::ParentalConsent => &mut self.consent_expiry,
+            Document::MedicalCertificate => &mut self.medical_certificate_expiry
+        }
+    }
+
+    pub fn get_document_expiry(&self, document: Document) -> Option<NaiveDate> {
+        match document {
+            Document::ParentalConsent => self.consent_expiry,
+            Document::MedicalCertificate => self.medical_certificate_expiry
+        }
+    }
+
+    pub fn lapsed_documents(&self, date: NaiveDate) -> Vec<Document> {
+        // documents not tracked for the athlete are not required
+        Document::ALL.into_iter().filter(|document| self.get_document_expiry(*document).is_some_and(|expiry| date > expiry))
+            .collect()
+    }
+
+    fn identity(&self) -> Identity<'_> {
+        Identity { id: self.id, given_name: &self.given_name, sur_name: &self.sur_name, birth_year: self.birth_year }
+    }
+
+    pub fn is_same_athlete(&self, registering_athlete: &RegisteringAthlete) -> bool {
+        self.identity().matches(registering_athlete.identity())
+    }
+
+    pub fn is_same_as(&self, other: &Athlete) -> bool {
+        self.identity().matches(other.identity())
+    }
+
+    /// Like `is_same_as` for an athlete of another file, e.g. of the laptop of another coach.
+    fn is_same_person(&self, other: &Athlete) -> bool {
+        self.identity().matches_across(other.identity())
+    }
+
+    fn changed_fields(&self, other: &Athlete) -> Vec<AthleteField> {
+        let mut fields = Vec::new();
+        if self.given_name != other.given_name {
+            fields.push(AthleteField::GivenName);
+        }
+        if self.sur_name != other.sur_name {
+            fields.push(AthleteField::SurName);
+        }
+        if self.birth_year != other.birth_year {
+            fields.push(AthleteField::BirthYear);
+        }
+        if self.belt != other.belt {
+            fields.push(AthleteField::Belt);
+        }
+        if self.weight_category.to_string() != other.weight_category.to_string() {
+            fields.push(AthleteField::WeightCategory);
+        }
+        if self.gender != other.gender {
+            fields.push(AthleteField::Gender);
+        }
+        if self.group != other.group {
+            fields.push(AthleteField::Group);
+        }
+        if self.referee_license != other.referee_license {
+            fields.push(AthleteField::RefereeLicense);
+        }
+        if self.consent_expiry != other.consent_expiry {
+            fields.push(AthleteField::ConsentExpiry);
+        }
+        if self.medical_certificate_expiry != other.medical_certificate_expiry {
+            fields.push(AthleteField::MedicalCertificateExpiry);
+        }
+        fields
+    }
+
+    pub fn get_last_registration(&self) -> Option<&LastRegistration> {
+        self.last_registration.as_ref()
+    }
+
+    pub fn remember_registration(&mut self, registering_athlete: &RegisteringAthlete, date: NaiveDate) {
+        self.last_registration = Some(LastRegistration {
+            date, age_category: registering_athlete.age_category.clone(), weight_category: registering_athlete.weight_category.clone()
+        });
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Document {
+    ParentalConsent,
+    MedicalCertificate
+}
+
+impl Document {
+    pub const ALL: [Document; 2] = [Document::ParentalConsent, Document::MedicalCertificate];
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RefereeLicense {
+    level: String,
+    expiry: NaiveDate
+}
+
+impl RefereeLicense {
+    pub fn new(level: String, expiry: NaiveDate) -> Self {
+        Self { level, expiry }
+    }
+
+    pub fn get_level(&self) -> &str {
+        &self.level
+    }
+
+    pub fn get_level_mut(&mut self) -> &mut String {
+        &mut self.level
+    }
+
+    pub fn get_expiry(&self) -> NaiveDate {
+        self.expiry
+    }
+
+    pub fn get_expiry_mut(&mut self) -> &mut NaiveDate {
+        &mut self.expiry
+    }
+
+    pub fn is_valid_on(&self, date: NaiveDate) -> bool {
+        // the license is valid including the day of its expiry
+        date <= self.expiry
+    }
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Sender {
     #[serde(rename="given")]
     given_name: String,
@@ -317,9 +659,13 @@ impl Sender {
     pub fn get_mail_mut(&mut self) -> &mut String {
         &mut self.mail
     }
+
+    pub fn get_phones_mut(&mut self) -> [&mut String; 4] {
+        [&mut self.private_phone, &mut self.public_phone, &mut self.fax, &mut self.mobile]
+    }
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Club {
     #[serde(rename="club")]
     name: String,
@@ -331,7 +677,12 @@ pub struct Club {
     region: String,
     state: String,
     group: String,
-    nation: String
+    nation: String,
+    #[serde(default)]
+    departments: Vec<Department>,
+    // frequently encountered clubs, e.g. of guest athletes or opponents in team events
+    #[serde(default)]
+    directory: Vec<DirectoryClub>
 }
 
 impl Club {
@@ -353,6 +704,10 @@ impl Club {
         &mut self.name
     }
 
+    pub fn get_number(&self) -> u64 {
+        self.number
+    }
+
     pub fn get_number_mut(&mut self) -> &mut u64 {
         &mut self.number
     }
@@ -380,6 +735,111 @@ impl Club {
     pub fn get_nation_mut(&mut self) -> &mut String {
         &mut self.nation
     }
+
+    pub fn get_departments(&self) -> &[Department] {
+        &self.departments
+    }
+
+    pub fn get_departments_mut(&mut self) -> &mut Vec<Department> {
+        &mut self.departments
+    }
+
+    pub fn get_directory(&self) -> &[DirectoryClub] {
+        &self.directory
+    }
+
+    pub fn get_directory_mut(&mut self) -> &mut Vec<DirectoryClub> {
+        &mut self.directory
+    }
+
+    pub fn for_department(&self, department: Option<usize>) -> Self {
+        // the departments share the sender-data, but register with their own club-number and group
+        let mut club = self.clone();
+        club.departments.clear();
+        if let Some(department) = department.and_then(|department| self.departments.get(department)) {
+            club.number = department.number;
+            club.group.clone_from(&department.group);
+        }
+        club
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Department {
+    name: String,
+    #[serde(rename="club-number")]
+    number: u64,
+    group: String
+}
+
+impl Department {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+
+    pub fn get_number_mut(&mut self) -> &mut u64 {
+        &mut self.number
+    }
+
+    pub fn get_group_mut(&mut self) -> &mut String {
+        &mut self.group
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct DirectoryClub {
+    name: String,
+    #[serde(rename="club-number")]
+    number: u64,
+    town: String
+}
+
+impl DirectoryClub {
+    pub fn new(name: String, number: u64, town: String) -> Self {
+        Self { name, number, town }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+
+    pub fn get_number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn get_number_mut(&mut self) -> &mut u64 {
+        &mut self.number
+    }
+
+    pub fn get_town(&self) -> &str {
+        &self.town
+    }
+
+    pub fn get_town_mut(&mut self) -> &mut String {
+        &mut self.town
+    }
+
+    pub fn matches(&self, query: &str) -> bool {
+        // matches the name or town case-insensitively and the club-number by its beginning
+        let query = query.trim().to_lowercase();
+        self.name.to_lowercase().contains(&query) || self.town.to_lowercase().contains(&query)
+            || format!("{:07}", self.number).starts_with(&query)
+    }
+}
+
+pub fn suggest_clubs<'a>(directory: &'a [DirectoryClub], query: &str) -> Vec<&'a DirectoryClub> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    directory.iter().filter(|club| club.matches(query)).collect()
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default, Enum)]
@@ -400,6 +860,7 @@ impl GenderCategory {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         Some(match s {
             "w" => Self::Female,
@@ -410,44 +871,334 @@ impl GenderCategory {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub enum GenderAbbreviations {
+    #[default]
+    #[serde(rename="mwg")]
+    Mwg,
+    #[serde(rename="mwd")]
+    Mwd,
+    #[serde(rename="mfx")]
+    Mfx
+}
+
+impl GenderAbbreviations {
+    pub const ALL: [GenderAbbreviations; 3] = [Self::Mwg, Self::Mwd, Self::Mfx];
+
+    pub fn render(self, gender_category: GenderCategory) -> &'static str {
+        // some organizers' imports expect other abbreviations than the official application
+        match (self, gender_category) {
+            (_, GenderCategory::Male) => "m",
+            (Self::Mwg | Self::Mwd, GenderCategory::Female) => "w",
+            (Self::Mfx, GenderCategory::Female) => "f",
+            (Self::Mwg, GenderCategory::Mixed) => "g",
+            (Self::Mwd, GenderCategory::Mixed) => "d",
+            (Self::Mfx, GenderCategory::Mixed) => "x"
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Mwg => "m/w/g",
+            Self::Mwd => "m/w/d",
+            Self::Mfx => "m/f/x"
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Tournament {
     name: String,
     date: NaiveDate,
     place: String,
+    #[serde(rename="age-category")]
     age_category: String,
+    #[serde(rename="gender-category", serialize_with="crate::utils::serialize_gender_category",
+    deserialize_with="crate::utils::deserialize_gender_category")]
     gender_category: GenderCategory,
+    #[serde(default, rename="gender-abbreviations")]
+    gender_abbreviations: GenderAbbreviations,
+    // files generated by older versions were written in ISO-8859-1
+    #[serde(default)]
+    encoding: TournamentEncoding,
     club: Club,
-    athletes: Vec<Athlete>
+    athletes: Vec<Athlete>,
+    // only set for team-competitions, the athletes of each team follow each other in `athletes`
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    teams: Vec<Team>,
+    #[serde(default, skip_serializing_if="Coach::is_empty")]
+    coach: Coach
+}
+
+/// Whether athletes are registered individually in their weight-categories or grouped into named teams, e.g. for Kata.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all="lowercase")]
+pub enum Competition {
+    #[default]
+    Individual,
+    Team
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Team {
+    name: String,
+    size: usize
+}
+
+impl Team {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+}
+
+/// The name of a team as it is written to the files, which know no escaping, so quotes, commas and line-breaks are replaced.
+pub fn sanitize_team_name(name: &str) -> String {
+    name.replace('"', "'").replace([',', '\n', '\r'], " ").split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// The coach accompanying the athletes to a tournament, the one of the last registration is the default for the next.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Coach {
+    name: String,
+    phone: String
+}
+
+impl Coach {
+    pub fn new(name: String, phone: String) -> Self {
+        Self { name, phone }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.name.trim().is_empty() && self.phone.trim().is_empty()
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+
+    pub fn get_phone(&self) -> &str {
+        &self.phone
+    }
+
+    pub fn get_phone_mut(&mut self) -> &mut String {
+        &mut self.phone
+    }
+
+    fn render(&self) -> String {
+        // left out without a coach, so the files stay the same as before
+        if self.is_empty() {
+            return String::new();
+        }
+        format!("Betreuer={}\nBetreuerTelefon={}\n", self.name.trim(), self.phone.trim())
+    }
 }
 
 impl Tournament {
     pub fn new(name: String, date: NaiveDate, place: String, age_category: String, gender: GenderCategory, club: Club, athletes: Vec<Athlete>) -> Self {
         Self {
-            name, date, place, age_category, gender_category: gender, club, athletes
+            name, date, place, age_category, gender_category: gender, gender_abbreviations: GenderAbbreviations::default(),
+            encoding: TournamentEncoding::default(), club, athletes, teams: Vec::new(), coach: Coach::default()
         }
     }
 
     pub fn render(&self) -> String {
+        if !self.teams.is_empty() {
+            return self.render_teams();
+        }
         // the formet here resembles toml, but is not toml, the date is in the usual German format
         format!(
             include_str!("tournament-format"),
             self.club.sender.render(self.club.get_name()), self.name, self.date.format("%d.%m.%Y"), self.place,
-            self.age_category, self.gender_category.render(), self.gender_category.render(), self.club.render(), render(&self.athletes), self.athletes.len()
+            self.age_category, self.gender_abbreviations.render(self.gender_category),
+            self.gender_abbreviations.render(self.gender_category), self.coach.render(), self.club.render(), render(&self.athletes),
+            self.athletes.len()
         )
     }
 
-    pub fn get_name(&self) -> &str {
-        &self.name
+    /// Team-files are marked by `Type=M` and list the teams in a `[Mannschaften]` section, each as `<number>=""1","<name>""`.
+    /// The athletes follow in the order of their teams, each with the number of its team as an additional last value.
+    fn render_teams(&self) -> String {
+        let teams: Vec<String> = self.teams.iter().enumerate()
+            .map(|(index, team)| format!("{}=\"\"1\",\"{}\"\"", index + 1, team.name)).collect();
+        let members: Vec<String> = self.team_numbers().zip(&self.athletes).enumerate()
+            .map(|(index, (team, athlete))| format!("{}=\"\"1\",{},\"{team}\"\"", index + 1, athlete.render())).collect();
+        format!(
+            include_str!("team-format"),
+            self.club.sender.render(self.club.get_name()), self.name, self.date.format("%d.%m.%Y"), self.place,
+            self.age_category, self.gender_abbreviations.render(self.gender_category),
+            self.gender_abbreviations.render(self.gender_category), self.coach.render(), self.club.render(), teams.join("\n"),
+            self.teams.len(), members.join("\n"), self.athletes.len()
+        )
     }
 
-    pub fn get_age_category(&self) -> &str {
-        &self.age_category
+    /// The number of the team of each athlete, counted from 1 as in the files.
+    fn team_numbers(&self) -> impl Iterator<Item=usize> + '_ {
+        self.teams.iter().enumerate().flat_map(|(index, team)| std::iter::repeat_n(index + 1, team.size))
     }
 
-    pub fn get_gender_category(&self) -> GenderCategory {
-        self.gender_category
+    pub fn get_teams(&self) -> &[Team] {
+        &self.teams
     }
-}
+
+    pub fn get_coach(&self) -> &Coach {
+        &self.coach
+    }
+
+    pub fn set_coach(&mut self, coach: Coach) {
+        self.coach = coach;
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_gender_abbreviations(&self) -> GenderAbbreviations {
+        self.gender_abbreviations
+    }
+
+    pub fn set_gender_abbreviations(&mut self, gender_abbreviations: GenderAbbreviations) {
+        self.gender_abbreviations = gender_abbreviations;
+    }
+
+    pub fn get_encoding(&self) -> TournamentEncoding {
+        self.encoding
+    }
+
+    pub fn set_encoding(&mut self, encoding: TournamentEncoding) {
+        self.encoding = encoding;
+    }
+
+    pub fn get_age_category(&self) -> &str {
+        &self.age_category
+    }
+
+    pub fn get_gender_category(&self) -> GenderCategory {
+        self.gender_category
+    }
+
+    pub fn get_date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn get_place(&self) -> &str {
+        &self.place
+    }
+
+    pub fn get_athletes(&self) -> &[Athlete] {
+        &self.athletes
+    }
+
+    pub fn get_club(&self) -> &Club {
+        &self.club
+    }
+
+    /// The athletes of an imported file for continuing the registration, known athletes keep their ids.
+    pub fn to_registering_athletes(&self, known: &[Athlete]) -> Vec<RegisteringAthlete> {
+        self.athletes.iter().map(|athlete| {
+            // the parsed athletes have no ids, so they are only matched by their names
+            let known = known.iter().find(|known| known.is_same_as(athlete)).unwrap_or(athlete);
+            let mut registering_athlete = RegisteringAthlete::from_athlete(known);
+            registering_athlete.weight_category = athlete.weight_category.to_string();
+            registering_athlete.age_category.clone_from(&self.age_category);
+            registering_athlete.gender_category = self.gender_category;
+            registering_athlete
+        }).zip(self.team_numbers().map(Some).chain(std::iter::repeat(None))).map(|(mut registering_athlete, team)| {
+            if let Some(team) = team.and_then(|team| self.teams.get(team - 1)) {
+                registering_athlete.team.clone_from(&team.name);
+            }
+            registering_athlete
+        }).collect()
+    }
+}
+
+/// Reads a tournament-file as written by `Tournament::render` or the official application, the club is not read,
+/// as the own club is used for continuing the registration.
+pub fn parse_tournament(text: &str) -> Option<Tournament> {
+    let mut section = "";
+    let (mut name, mut date, mut place, mut age_category, mut gender_category) = (None, None, None, None, None);
+    let mut athletes = Vec::new();
+    let mut teams = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            section = header;
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match (section, key) {
+            ("Meldung", "Turnier") => name = Some(value.to_owned()),
+            ("Meldung", "Datum") => date = NaiveDate::parse_from_str(value, "%d.%m.%Y").ok(),
+            ("Meldung", "Ort") => place = Some(value.to_owned()),
+            // the gender is repeated in parentheses
+            ("Meldung", "Altersgruppe") => age_category = Some(value.rsplit_once(" (").map_or(value, |(age, _)| age).to_owned()),
+            ("Meldung", "Geschlecht") => gender_category = parse_gender_abbreviation(value),
+            ("Mannschaften", _) if key.parse::<usize>().is_ok() => teams.push(parse_team_name(value)?),
+            ("Teilnehmer", _) if key.parse::<usize>().is_ok() => athletes.push(value.to_owned()),
+            _ => {}
+        }
+    }
+    let (age_category, gender_category) = (age_category?, gender_category?);
+    let mut members = athletes.iter().map(|athlete| {
+        let team = if teams.is_empty() { 0 } else { parse_team_number(athlete).filter(|team| (1..=teams.len()).contains(team))? };
+        Some((team, parse_tournament_athlete(athlete, &age_category, gender_category)?))
+    }).collect::<Option<Vec<(usize, Athlete)>>>()?;
+    // the athletes of a team are expected to follow each other, but are not required to
+    members.sort_by_key(|(team, _)| *team);
+    let mut tournament = Tournament::new(name?, date?, place?, age_category, gender_category, Club::default(), Vec::new());
+    tournament.teams = teams.into_iter().enumerate()
+        .map(|(index, name)| Team { name, size: members.iter().filter(|(team, _)| *team == index + 1).count() }).collect();
+    tournament.athletes = members.into_iter().map(|(_, athlete)| athlete).collect();
+    Some(tournament)
+}
+
+fn parse_team_name(value: &str) -> Option<String> {
+    // e.g. ""1","Musterstadt I""
+    Some(value.strip_prefix("\"\"1\",\"")?.strip_suffix("\"\"")?.to_owned())
+}
+
+fn parse_team_number(value: &str) -> Option<usize> {
+    // the number of the team is the last value of an athlete of a team-file
+    value.strip_suffix('"')?.rsplit(',').next()?.trim_matches('"').parse().ok()
+}
+
+fn parse_gender_abbreviation(abbreviation: &str) -> Option<GenderCategory> {
+    // all abbreviations of `GenderAbbreviations`
+    match abbreviation {
+        "m" => Some(GenderCategory::Male),
+        "w" | "f" => Some(GenderCategory::Female),
+        "g" | "d" | "x" => Some(GenderCategory::Mixed),
+        _ => None
+    }
+}
+
+fn parse_tournament_athlete(value: &str, age_category: &str, gender_category: GenderCategory) -> Option<Athlete> {
+    // e.g. ""1","Mustermann","Erika","3","36","","2012","","","","","""
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+    let fields: Vec<&str> = value.split(',').map(|field| field.trim_matches('"')).collect();
+    let (sur_name, given_name) = (*fields.get(1)?, *fields.get(2)?);
+    let belt = Belt::from_number(fields.get(3)?.parse().ok()?)?;
+    let weight_category = match *fields.get(4)? {
+        // over-categories are written without their limit, so the heaviest official one is assumed
+        "" => WeightCategory::official(age_category, gender_category).last().and_then(|class| WeightCategory::from_str(class))
+            .unwrap_or_default(),
+        limit => WeightCategory::from_str(&format!("-{limit}"))?
+    };
+    let birth_year = fields.get(6)?.parse().ok()?;
+    // the files carry no ids, so the athletes are told apart by their names, see `Identity`
+    let mut athlete = Athlete::new(given_name.to_owned(), sur_name.to_owned(), birth_year, belt, weight_category, gender_category);
+    athlete.id = Uuid::nil();
+    Some(athlete)
+}
 
 fn render(athletes: &[Athlete]) -> String {
     let mut ret = String::new();
@@ -463,31 +1214,86 @@ fn render(athletes: &[Athlete]) -> String {
     ret
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RegisteringAthlete {
+    id: Uuid,
+    #[serde(rename="given")]
     given_name: String,
+    #[serde(rename="sur")]
     sur_name: String,
     belt: Belt,
+    #[serde(rename="weight")]
     weight_category: String,
+    #[serde(rename="year")]
     birth_year: u16,
+    #[serde(rename="gender-category", serialize_with="crate::utils::serialize_gender_category",
+    deserialize_with="crate::utils::deserialize_gender_category")]
     gender_category: GenderCategory,
+    #[serde(serialize_with="crate::utils::serialize_gender_category",
+    deserialize_with="crate::utils::deserialize_gender_category")]
     gender: GenderCategory,
-    age_category: String
+    #[serde(rename="age-category")]
+    age_category: String,
+    #[serde(default, skip_serializing_if="String::is_empty")]
+    team: String
 }
 
 impl RegisteringAthlete {
-    pub fn new(given_name: String, sur_name: String, belt: Belt, weight_category: String, birth_year: u16, gender: GenderCategory,
-    age_category: String) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(id: Uuid, given_name: String, sur_name: String, belt: Belt, weight_category: String, birth_year: u16,
+    gender: GenderCategory, age_category: String) -> Self {
         Self {
-            given_name, sur_name, belt, weight_category, birth_year, gender_category: gender, gender, age_category
+            id, given_name, sur_name, belt, weight_category, birth_year, gender_category: gender, gender, age_category,
+            team: String::new()
         }
     }
 
     pub fn from_athlete(athlete: &Athlete) -> Self {
-        Self::new(athlete.given_name.clone(), athlete.sur_name.clone(), athlete.belt,
+        Self::new(athlete.id, athlete.given_name.clone(), athlete.sur_name.clone(), athlete.belt,
         athlete.weight_category.to_string(), athlete.birth_year, athlete.gender, String::new())
     }
 
+    /// Like `from_athlete`, with the age-category of the athlete on `date` filled in, if there is one,
+    /// and the categories of their last registration.
+    pub fn for_tournament(athlete: &Athlete, date: NaiveDate, hints: &[AgeCategoryHint]) -> Self {
+        let mut registering_athlete = Self::from_athlete(athlete);
+        if let Some(last_registration) = &athlete.last_registration {
+            if WeightCategory::from_str(&last_registration.weight_category).is_some() {
+                registering_athlete.weight_category.clone_from(&last_registration.weight_category);
+            }
+            // age-categories follow the year of birth, so the last one is only reused within the same year
+            if last_registration.date.year() == date.year() && !last_registration.age_category.is_empty() {
+                registering_athlete.age_category.clone_from(&last_registration.age_category);
+                return registering_athlete;
+            }
+        }
+        // the hints of the club take precedence over the age-categories of the federation
+        if let Some(hint) = hints.iter().find(|hint| hint.birth_year == athlete.birth_year && !hint.age_category.is_empty()) {
+            registering_athlete.age_category.clone_from(&hint.age_category);
+        }
+        else if let Some(age_category) = suggest_age_category(athlete.birth_year, athlete.gender, date) {
+            age_category.clone_into(&mut registering_athlete.age_category);
+        }
+        registering_athlete
+    }
+
+    fn to_athlete(&self) -> Option<Athlete> {
+        Some(self.to_athlete_in(WeightCategory::from_str(&self.weight_category)?))
+    }
+
+    fn to_athlete_in(&self, weight_category: WeightCategory) -> Athlete {
+        Athlete {
+            id: self.id, given_name: self.given_name.clone(), sur_name: self.sur_name.clone(), belt: self.belt,
+            weight_category, birth_year: self.birth_year,
+            gender: self.gender, group: String::new(), referee_license: None, consent_expiry: None,
+            medical_certificate_expiry: None, last_registration: None
+        }
+    }
+
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
     pub fn get_given_name(&self) -> &str {
         &self.given_name
     }
@@ -500,6 +1306,14 @@ impl RegisteringAthlete {
         self.belt
     }
 
+    pub fn get_weight_category(&self) -> &str {
+        &self.weight_category
+    }
+
+    pub fn has_default_weight_category(&self) -> bool {
+        WeightCategory::from_str(&self.weight_category).is_some_and(WeightCategory::is_default)
+    }
+
     pub fn get_weight_category_mut(&mut self) -> &mut String {
         &mut self.weight_category
     }
@@ -516,37 +1330,1071 @@ impl RegisteringAthlete {
         &mut self.gender_category
     }
 
+    pub fn get_age_category(&self) -> &str {
+        &self.age_category
+    }
+
     pub fn get_age_category_mut(&mut self) -> &mut String {
         &mut self.age_category
     }
 
+    pub fn get_team(&self) -> &str {
+        &self.team
+    }
+
+    pub fn get_team_mut(&mut self) -> &mut String {
+        &mut self.team
+    }
+
     pub fn get_gender(&self) -> GenderCategory {
         self.gender
     }
+
+    fn identity(&self) -> Identity<'_> {
+        Identity { id: self.id, given_name: &self.given_name, sur_name: &self.sur_name, birth_year: self.birth_year }
+    }
+
+    fn is_same_athlete(&self, other: &Self) -> bool {
+        // the drafts of other machines have their own ids for the same athletes
+        self.identity().matches_across(other.identity())
+    }
+}
+
+/// The athletes registered a second time in the same category, which would appear twice in its file,
+/// in other categories an athlete might be registered again, e.g. in U18 and for the men.
+pub fn find_duplicate_registrations(athletes: &[RegisteringAthlete]) -> Vec<usize> {
+    athletes.iter().enumerate().filter(|(index, athlete)| {
+        athletes[..*index].iter().any(|earlier| earlier.is_same_athlete(athlete) && earlier.gender_category == athlete.gender_category
+            && normalize_age_category(&earlier.age_category) == normalize_age_category(&athlete.age_category))
+    }).map(|(index, _)| index).collect()
+}
+
+/// A registration in preparation, shared as a single file between the coaches of a club.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Draft {
+    pub name: String,
+    pub place: String,
+    pub date: NaiveDate,
+    pub athletes: Vec<RegisteringAthlete>,
+    #[serde(default)]
+    pub competition: Competition,
+    #[serde(default)]
+    pub coach: Coach
+}
+
+/// A Kyu-examination, the athletes are registered with the belt they are examined for.
+#[derive(Clone, Debug)]
+pub struct Exam {
+    pub date: NaiveDate,
+    pub place: String,
+    pub candidates: Vec<ExamCandidate>
+}
+
+impl Default for Exam {
+    fn default() -> Self {
+        Self { date: Local::now().date_naive(), place: String::new(), candidates: Vec::new() }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ExamCandidate {
+    id: Uuid,
+    given_name: String,
+    sur_name: String,
+    birth_year: u16,
+    belt: Belt,
+    target: Belt
+}
+
+impl ExamCandidate {
+    /// Examined for the next belt by default, athletes with the 1. Kyu or a Dan-grade cannot take a Kyu-examination.
+    pub fn from_athlete(athlete: &Athlete) -> Option<Self> {
+        Some(Self {
+            id: athlete.id, given_name: athlete.given_name.clone(), sur_name: athlete.sur_name.clone(), birth_year: athlete.birth_year,
+            belt: athlete.belt, target: athlete.belt.next_kyu()?
+        })
+    }
+
+    pub fn is_same_as(&self, athlete: &Athlete) -> bool {
+        Identity { id: self.id, given_name: &self.given_name, sur_name: &self.sur_name, birth_year: self.birth_year }
+            .matches(athlete.identity())
+    }
+
+    pub fn get_given_name(&self) -> &str {
+        &self.given_name
+    }
+
+    pub fn get_sur_name(&self) -> &str {
+        &self.sur_name
+    }
+
+    pub fn get_birth_year(&self) -> u16 {
+        self.birth_year
+    }
+
+    pub fn get_belt(&self) -> Belt {
+        self.belt
+    }
+
+    pub fn get_target(&self) -> Belt {
+        self.target
+    }
+
+    pub fn get_target_mut(&mut self) -> &mut Belt {
+        &mut self.target
+    }
+
+    /// The belts an athlete might be examined for, skipping belts is allowed for experienced athletes.
+    pub fn possible_targets(&self) -> Vec<Belt> {
+        std::iter::successors(self.belt.next_kyu(), |belt| belt.next_kyu()).collect()
+    }
+}
+
+/// Returns the athletes of another draft, which are not part of `ours` yet.
+pub fn new_draft_athletes<'a>(ours: &[RegisteringAthlete], theirs: &'a [RegisteringAthlete]) -> Vec<&'a RegisteringAthlete> {
+    theirs.iter().filter(|athlete| !ours.iter().any(|our| our.is_same_athlete(athlete))).collect()
+}
+
+/// Adds the athletes of another draft for the same tournament, the categories of athletes in both drafts are kept from `ours`.
+pub fn merge_drafts(ours: &mut Vec<RegisteringAthlete>, theirs: Vec<RegisteringAthlete>) -> usize {
+    let len = ours.len();
+    for athlete in theirs {
+        if !ours.iter().any(|our| our.is_same_athlete(&athlete)) {
+            ours.push(athlete);
+        }
+    }
+    ours.len() - len
+}
+
+/// The age-category of the German Judo Federation, which is determined by the age reached in the year of the tournament.
+pub fn suggest_age_category(birth_year: u16, gender: GenderCategory, date: NaiveDate) -> Option<&'static str> {
+    let age = date.year() - i32::from(birth_year);
+    if age < 0 {
+        return None;
+    }
+    Some(match age {
+        ..=10 => "U11",
+        11..=12 => "U13",
+        13..=14 => "U15",
+        15..=17 => "U18",
+        18..=20 => "U21",
+        // the adults compete separately
+        _ => match gender {
+            GenderCategory::Male => "Männer",
+            GenderCategory::Female => "Frauen",
+            GenderCategory::Mixed => return None
+        }
+    })
+}
+
+/// A plausible belt of a young athlete, assuming regular exams from a certain age on.
+/// Exams beyond the orange belt vary too much to be guessed.
+pub fn suggest_belt(birth_year: u16, year: i32, first_exam_age: u8, years_per_exam: u8) -> Belt {
+    let age = year - i32::from(birth_year);
+    if age < i32::from(first_exam_age) {
+        return Belt::Kyu9;
+    }
+    let passed = (age - i32::from(first_exam_age)) / i32::from(years_per_exam.max(1)) + 1;
+    (0..passed.min(4)).fold(Belt::Kyu9, |belt, _| belt.inc())
+}
+
+const AGE_CATEGORY_GENDER_WORDS: [&str; 8] = ["m", "w", "d", "männlich", "weiblich", "divers", "gemischt", "mixed"];
+
+pub fn normalize_age_category(age_category: &str) -> String {
+    // e.g. "u15", "U 15" and "U15 männlich" all denote "U15", combined ones like "U18/U21" or "U13-U15" are kept
+    let mut words: Vec<&str> = age_category.split_whitespace().collect();
+    if words.len() > 1 && words.last().is_some_and(|word| AGE_CATEGORY_GENDER_WORDS.contains(&word.to_lowercase().as_str())) {
+        words.pop();
+    }
+    let compact = words.concat().to_uppercase();
+    let mut chars = compact.chars();
+    if let Some(prefix @ ('U' | 'Ü')) = chars.next() {
+        // the gender might be appended to the age, e.g. "u15w"
+        let rest = chars.as_str();
+        let digits = rest.strip_suffix(['M', 'W', 'D']).unwrap_or(rest);
+        if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+            return format!("{prefix}{digits}");
+        }
+    }
+    words.iter().map(|word| {
+        if word.chars().any(|c| c.is_ascii_digit()) {
+            return word.to_uppercase();
+        }
+        let mut chars = word.chars();
+        chars.next().map(|first| first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect())
+            .unwrap_or_default()
+    }).collect::<Vec<String>>().join(" ")
+}
+
+pub fn group_registering_athletes(registering_athletes: &[RegisteringAthlete]) -> Vec<(String, GenderCategory, Vec<usize>)> {
+    // one group per output-file, the athletes are referred to by their index
+    let mut group_indices: HashMap<(String, GenderCategory), usize> = HashMap::new();
+    let mut ret: Vec<(String, GenderCategory, Vec<usize>)> = Vec::new();
+
+    for (index, registering_athlete) in registering_athletes.iter().enumerate() {
+        // differently spelled age categories must not end up in separate files
+        let age_category = normalize_age_category(&registering_athlete.age_category);
+        let key = (age_category, registering_athlete.gender_category);
+        if let Some(group_index) = group_indices.get(&key) {
+            ret[*group_index].2.push(index);
+        }
+        else {
+            group_indices.insert(key.clone(), ret.len());
+            ret.push((key.0, key.1, vec![index]));
+        }
+    }
+    ret
 }
 
 pub fn registering_athletes_to_tournaments(registering_athletes: &[RegisteringAthlete], name: &str, date: NaiveDate,
 place: &str, club: &Club) -> Option<Vec<Tournament>> {
-    let mut tournament_meta: HashMap<(&str, GenderCategory), usize> = HashMap::new();
-    let mut ret: Vec<Tournament> = Vec::new();
+    group_registering_athletes(registering_athletes).into_iter().map(|(age_category, gender_category, indices)| {
+        let athletes = indices.iter().map(|index| registering_athletes[*index].to_athlete()).collect::<Option<Vec<Athlete>>>()?;
+        Some(Tournament::new(name.to_owned(), date, place.to_owned(), age_category, gender_category, club.clone(), athletes))
+    }).collect()
+}
+
+/// Like `registering_athletes_to_tournaments` for a team-competition, fails for athletes without a team.
+pub fn registering_teams_to_tournaments(registering_athletes: &[RegisteringAthlete], name: &str, date: NaiveDate,
+place: &str, club: &Club) -> Option<Vec<Tournament>> {
+    group_registering_athletes(registering_athletes).into_iter().map(|(age_category, gender_category, indices)| {
+        // the teams keep the order they were added in
+        let mut teams: Vec<(String, Vec<Athlete>)> = Vec::new();
+        for index in indices {
+            let registering_athlete = &registering_athletes[index];
+            let team = sanitize_team_name(&registering_athlete.team);
+            if team.is_empty() {
+                return None;
+            }
+            // team-competitions like Kata have no weight-categories
+            let athlete = registering_athlete.to_athlete_in(WeightCategory::from_str(&registering_athlete.weight_category).unwrap_or_default());
+            match teams.iter_mut().find(|(name, _)| *name == team) {
+                Some((_, athletes)) => athletes.push(athlete),
+                None => teams.push((team, vec![athlete]))
+            }
+        }
+        let mut tournament = Tournament::new(name.to_owned(), date, place.to_owned(), age_category, gender_category, club.clone(), Vec::new());
+        for (team, athletes) in teams {
+            tournament.teams.push(Team { name: team, size: athletes.len() });
+            tournament.athletes.extend(athletes);
+        }
+        Some(tournament)
+    }).collect()
+}
+
+/// Parses the lines until `progress` is cancelled, as pasting thousands of rows takes a while.
+pub fn parse_pasted_athletes(text: &str, progress: &Progress) -> Vec<Option<Athlete>> {
+    // one athlete per line, as pasted from a spreadsheet:
+    // given name, surname, birth year, belt, gender and optionally weight category and group
+    let lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).collect();
+    progress.set_total(lines.len());
+    lines.into_iter().take_while(|_| !progress.is_cancelled()).map(|line| {
+        let athlete = parse_pasted_athlete(line);
+        progress.advance();
+        athlete
+    }).collect()
+}
+
+fn parse_pasted_athlete(line: &str) -> Option<Athlete> {
+    let separator = if line.contains('\t') { '\t' } else { ';' };
+    let fields: Vec<&str> = line.split(separator).map(str::trim).collect();
+    let [given_name, sur_name, birth_year, belt, gender, rest @ ..] = fields.as_slice() else {
+        return None;
+    };
+    if given_name.is_empty() || sur_name.is_empty() {
+        return None;
+    }
+    let weight_category = match rest.first() {
+        Some(weight_category) if !weight_category.is_empty() => WeightCategory::from_str(weight_category)?,
+        _ => WeightCategory::default()
+    };
+    let mut athlete = Athlete::new((*given_name).to_owned(), (*sur_name).to_owned(), birth_year.parse().ok()?,
+        Belt::from_display(belt)?, weight_category, GenderCategory::from_str(&gender.to_lowercase())?);
+    if let Some(group) = rest.get(1) {
+        (*group).clone_into(athlete.get_group_mut());
+    }
+    Some(athlete)
+}
+
+/// The athletes of an archived season, its name is kept, as the file-name cannot hold every character, e.g. of "2024/25".
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Season {
+    pub name: String,
+    pub athletes: Vec<Athlete>
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    name: String,
+    date: NaiveDate,
+    place: String,
+    athletes: Vec<Athlete>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    invitation: Option<PathBuf>,
+    // entries of older versions do not know their files
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    files: Vec<HistoryFile>,
+    #[serde(default, skip_serializing_if="String::is_empty")]
+    notes: String
+}
+
+/// A file written for a registration, the categories are kept for re-opening the registration.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HistoryFile {
+    path: PathBuf,
+    #[serde(rename="age-category")]
+    age_category: String,
+    #[serde(rename="gender-category", serialize_with="crate::utils::serialize_gender_category",
+    deserialize_with="crate::utils::deserialize_gender_category")]
+    gender_category: GenderCategory,
+    athletes: Vec<Uuid>
+}
+
+impl HistoryFile {
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get_age_category(&self) -> &str {
+        &self.age_category
+    }
+
+    pub fn get_gender_category(&self) -> GenderCategory {
+        self.gender_category
+    }
+}
+
+impl HistoryEntry {
+    pub fn from_tournaments(name: String, date: NaiveDate, place: String, tournaments: &[Tournament]) -> Self {
+        Self {
+            name, date, place,
+            athletes: tournaments.iter().flat_map(|tournament| tournament.athletes.iter().cloned()).collect(),
+            invitation: None, files: Vec::new(), notes: String::new()
+        }
+    }
+
+    /// Records the files written for `tournaments`, `paths` are in the order of the tournaments.
+    pub fn set_files(&mut self, tournaments: &[Tournament], paths: &[PathBuf]) {
+        self.files = tournaments.iter().zip(paths).map(|(tournament, path)| HistoryFile {
+            path: path.clone(), age_category: tournament.age_category.clone(), gender_category: tournament.gender_category,
+            athletes: tournament.athletes.iter().map(|athlete| athlete.id).collect()
+        }).collect();
+    }
+
+    pub fn get_files(&self) -> &[HistoryFile] {
+        &self.files
+    }
+
+    /// The athletes as they were registered, athletes of older entries are missing their age-category.
+    pub fn to_registering_athletes(&self) -> Vec<RegisteringAthlete> {
+        self.athletes.iter().map(|athlete| {
+            let mut registering_athlete = RegisteringAthlete::from_athlete(athlete);
+            if let Some(file) = self.files.iter().find(|file| file.athletes.contains(&athlete.id)) {
+                registering_athlete.age_category.clone_from(&file.age_category);
+                registering_athlete.gender_category = file.gender_category;
+            }
+            registering_athlete
+        }).collect()
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn get_place(&self) -> &str {
+        &self.place
+    }
 
-    for registering_athlete in registering_athletes {
-        let index_opt = tournament_meta.get(&(&registering_athlete.age_category, registering_athlete.gender_category));
-        if let Some(index) = index_opt {
-            ret[*index].athletes.push(Athlete::new(registering_athlete.given_name.clone(), registering_athlete.sur_name.clone(),
-                registering_athlete.birth_year, registering_athlete.belt,
-                WeightCategory::from_str(&registering_athlete.weight_category)?, registering_athlete.gender_category));
+    pub fn get_athletes(&self) -> &[Athlete] {
+        &self.athletes
+    }
+
+    pub fn get_invitation(&self) -> Option<&Path> {
+        self.invitation.as_deref()
+    }
+
+    pub fn set_invitation(&mut self, invitation: Option<PathBuf>) {
+        self.invitation = invitation;
+    }
+
+    pub fn get_notes(&self) -> &str {
+        &self.notes
+    }
+
+    pub fn get_notes_mut(&mut self) -> &mut String {
+        &mut self.notes
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InvitationMetadata {
+    pub date: Option<NaiveDate>,
+    pub place: Option<String>
+}
+
+fn find_date(text: &str) -> Option<NaiveDate> {
+    // dates are written as "12.10.2025" or "12. 10. 2025" in German invitations
+    let words: Vec<&str> = text.split_whitespace().collect();
+    for start in 0..words.len() {
+        let mut candidate = String::new();
+        for word in words.iter().skip(start).take(3) {
+            candidate.push_str(word.trim_end_matches([',', ';']));
+            if let Ok(date) = NaiveDate::parse_from_str(&candidate, "%d.%m.%Y") {
+                return Some(date);
+            }
+        }
+    }
+    None
+}
+
+pub fn parse_invitation(text: &str) -> InvitationMetadata {
+    // the labels of the dates and places are preferred over any other date in the text, e.g. the deadline
+    const DATE_LABELS: [&str; 3] = ["datum", "termin", "wettkampftag"];
+    const PLACE_LABELS: [&str; 4] = ["austragungsort", "veranstaltungsort", "wettkampfstätte", "ort"];
+    let mut metadata = InvitationMetadata::default();
+    for line in text.lines().map(str::trim) {
+        let Some((label, value)) = line.split_once(':') else {
+            continue;
+        };
+        let label = label.trim().to_lowercase();
+        let value = value.trim();
+        if metadata.date.is_none() && DATE_LABELS.contains(&label.as_str()) {
+            metadata.date = find_date(value);
+        }
+        if metadata.place.is_none() && PLACE_LABELS.contains(&label.as_str()) && !value.is_empty() {
+            metadata.place = Some(value.to_owned());
+        }
+    }
+    if metadata.date.is_none() {
+        metadata.date = find_date(text);
+    }
+    metadata
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GeneratedFile {
+    path: PathBuf,
+    timestamp: DateTime<Local>,
+    sha256: String,
+    // kept for generating the file again
+    tournament: Tournament
+}
+
+impl GeneratedFile {
+    pub fn new(path: PathBuf, sha256: String, tournament: Tournament) -> Self {
+        Self { path, timestamp: Local::now(), sha256, tournament }
+    }
+
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get_timestamp(&self) -> DateTime<Local> {
+        self.timestamp
+    }
+
+    pub fn get_sha256(&self) -> &str {
+        &self.sha256
+    }
+
+    pub fn get_tournament(&self) -> &Tournament {
+        &self.tournament
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all="kebab-case")]
+pub enum CsvField {
+    GivenName,
+    SurName,
+    BirthYear,
+    Belt,
+    WeightCategory,
+    AgeCategory,
+    Gender,
+    GenderCategory,
+    ClubName,
+    ClubNumber,
+    TournamentName,
+    TournamentDate
+}
+
+impl CsvField {
+    pub const ALL: [CsvField; 12] = [CsvField::GivenName, CsvField::SurName, CsvField::BirthYear, CsvField::Belt,
+        CsvField::WeightCategory, CsvField::AgeCategory, CsvField::Gender, CsvField::GenderCategory, CsvField::ClubName,
+        CsvField::ClubNumber, CsvField::TournamentName, CsvField::TournamentDate];
+
+    pub fn default_header(self) -> &'static str {
+        // the portals of the German organizers use German headers
+        match self {
+            CsvField::GivenName => "Vorname",
+            CsvField::SurName => "Nachname",
+            CsvField::BirthYear => "Jahrgang",
+            CsvField::Belt => "Gürtel",
+            CsvField::WeightCategory => "Gewichtsklasse",
+            CsvField::AgeCategory => "Altersklasse",
+            CsvField::Gender => "Geschlecht",
+            CsvField::GenderCategory => "Wertungsklasse",
+            CsvField::ClubName => "Verein",
+            CsvField::ClubNumber => "Vereinsnummer",
+            CsvField::TournamentName => "Turnier",
+            CsvField::TournamentDate => "Datum"
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CsvColumn {
+    pub field: CsvField,
+    pub header: String
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all="kebab-case")]
+pub enum CsvEncoding {
+    Utf8,
+    // spreadsheet-applications only detect UTF-8 with a byte-order-mark
+    #[default]
+    Utf8Bom,
+    Latin1
+}
+
+impl CsvEncoding {
+    pub const ALL: [CsvEncoding; 3] = [CsvEncoding::Utf8, CsvEncoding::Utf8Bom, CsvEncoding::Latin1];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CsvEncoding::Utf8 => "UTF-8",
+            CsvEncoding::Utf8Bom => "UTF-8 (BOM)",
+            CsvEncoding::Latin1 => "ISO-8859-1"
+        }
+    }
+}
+
+/// The encoding of the tournament-files, newer versions of the official application also accept UTF-8.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all="kebab-case")]
+pub enum TournamentEncoding {
+    #[default]
+    Latin1,
+    Windows1252,
+    Utf8
+}
+
+impl TournamentEncoding {
+    pub const ALL: [TournamentEncoding; 3] = [TournamentEncoding::Latin1, TournamentEncoding::Windows1252, TournamentEncoding::Utf8];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TournamentEncoding::Latin1 => "ISO-8859-1",
+            TournamentEncoding::Windows1252 => "Windows-1252",
+            TournamentEncoding::Utf8 => "UTF-8"
+        }
+    }
+}
+
+/// The column-layout of the CSV-files accepted by the online-portal of an organizer.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CsvExport {
+    pub name: String,
+    pub delimiter: char,
+    #[serde(default)]
+    pub encoding: CsvEncoding,
+    #[serde(default = "default_true")]
+    pub header: bool,
+    pub columns: Vec<CsvColumn>
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for CsvExport {
+    fn default() -> Self {
+        let columns = [CsvField::SurName, CsvField::GivenName, CsvField::BirthYear, CsvField::Gender, CsvField::AgeCategory,
+            CsvField::WeightCategory, CsvField::Belt, CsvField::ClubName].into_iter()
+            .map(|field| CsvColumn { field, header: field.default_header().to_owned() }).collect();
+        Self { name: String::new(), delimiter: ';', encoding: CsvEncoding::default(), header: true, columns }
+    }
+}
+
+impl CsvExport {
+    fn quote(&self, value: &str) -> String {
+        if value.contains([self.delimiter, '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
         }
         else {
-            ret.push(
-                Tournament::new(name.to_owned(), date, place.to_owned(), registering_athlete.age_category.clone(),
-                registering_athlete.gender_category, club.clone(), vec![Athlete::new(
-                    registering_athlete.given_name.clone(), registering_athlete.sur_name.clone(), registering_athlete.birth_year,
-                    registering_athlete.belt, WeightCategory::from_str(&registering_athlete.weight_category)?, registering_athlete.gender
-                )])
-            );
-            tournament_meta.insert((&registering_athlete.age_category, registering_athlete.gender_category), ret.len() - 1);
+            value.to_owned()
+        }
+    }
+
+    pub fn render(&self, athletes: &[RegisteringAthlete], club: &Club, tournament_name: &str, date: NaiveDate) -> String {
+        let delimiter = self.delimiter.to_string();
+        let mut lines = Vec::new();
+        if self.header {
+            lines.push(self.columns.iter().map(|column| self.quote(&column.header)).collect::<Vec<_>>().join(&delimiter));
+        }
+        for athlete in athletes {
+            lines.push(self.columns.iter().map(|column| {
+                let value = match column.field {
+                    CsvField::GivenName => athlete.given_name.clone(),
+                    CsvField::SurName => athlete.sur_name.clone(),
+                    CsvField::BirthYear => athlete.birth_year.to_string(),
+                    CsvField::Belt => athlete.belt.to_display(),
+                    CsvField::WeightCategory => athlete.weight_category.clone(),
+                    CsvField::AgeCategory => athlete.age_category.clone(),
+                    CsvField::Gender => athlete.gender.render().to_owned(),
+                    CsvField::GenderCategory => athlete.gender_category.render().to_owned(),
+                    CsvField::ClubName => club.name.clone(),
+                    CsvField::ClubNumber => format!("{:07}", club.number),
+                    CsvField::TournamentName => tournament_name.to_owned(),
+                    CsvField::TournamentDate => date.format("%d.%m.%Y").to_string()
+                };
+                self.quote(&value)
+            }).collect::<Vec<_>>().join(&delimiter));
+        }
+        // line-endings as of RFC 4180
+        lines.into_iter().map(|line| line + "\r\n").collect()
+    }
+
+    /// All registrations of `history` in one file, e.g. for the funding reports requested by the state associations.
+    pub fn render_history(&self, history: &[HistoryEntry]) -> String {
+        let delimiter = self.delimiter.to_string();
+        let mut lines = Vec::new();
+        if self.header {
+            lines.push(["Datum", "Turnier", "Ort", "Nachname", "Vorname", "Jahrgang", "Geschlecht", "Gürtel", "Altersklasse",
+                "Wertungsklasse", "Gewichtsklasse"].map(|header| self.quote(header)).join(&delimiter));
+        }
+        for entry in history {
+            // entries of older versions do not know the categories of their athletes
+            for athlete in entry.to_registering_athletes() {
+                lines.push([entry.date.format("%d.%m.%Y").to_string(), entry.name.clone(), entry.place.clone(),
+                    athlete.sur_name, athlete.given_name, athlete.birth_year.to_string(), athlete.gender.render().to_owned(),
+                    athlete.belt.to_display(), athlete.age_category, athlete.gender_category.render().to_owned(),
+                    athlete.weight_category].map(|value| self.quote(&value)).join(&delimiter));
+            }
+        }
+        lines.into_iter().map(|line| line + "\r\n").collect()
+    }
+
+    /// One row per candidate of `exam`, independent of the configured columns.
+    pub fn render_exam(&self, exam: &Exam, club: &Club) -> String {
+        let delimiter = self.delimiter.to_string();
+        let mut lines = Vec::new();
+        if self.header {
+            lines.push(["Nachname", "Vorname", "Jahrgang", "Gürtel", "Prüfung zum", "Datum", "Ort", "Verein"]
+                .map(|header| self.quote(header)).join(&delimiter));
+        }
+        for candidate in &exam.candidates {
+            lines.push([candidate.sur_name.clone(), candidate.given_name.clone(), candidate.birth_year.to_string(),
+                candidate.belt.to_display(), candidate.target.to_display(), exam.date.format("%d.%m.%Y").to_string(), exam.place.clone(),
+                club.name.clone()].map(|value| self.quote(&value)).join(&delimiter));
+        }
+        lines.into_iter().map(|line| line + "\r\n").collect()
+    }
+
+    /// One row per athlete of `tournament`, independent of the configured columns.
+    pub fn render_tournament(&self, tournament: &Tournament) -> String {
+        let delimiter = self.delimiter.to_string();
+        let mut lines = Vec::new();
+        if self.header {
+            lines.push(["Nachname", "Vorname", "Jahrgang", "Gürtel", "Gewichtsklasse", "Altersklasse", "Wertungsklasse"]
+                .map(|header| self.quote(header)).join(&delimiter));
+        }
+        for athlete in &tournament.athletes {
+            lines.push([athlete.sur_name.clone(), athlete.given_name.clone(), athlete.birth_year.to_string(), athlete.belt.to_display(),
+                athlete.weight_category.to_string(), tournament.age_category.clone(), tournament.gender_category.render().to_owned()]
+                .map(|value| self.quote(&value)).join(&delimiter));
+        }
+        lines.into_iter().map(|line| line + "\r\n").collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all="kebab-case")]
+pub enum SurNameDisplay {
+    Full,
+    #[default]
+    Initial,
+    Hidden
+}
+
+impl SurNameDisplay {
+    pub const ALL: [SurNameDisplay; 3] = [SurNameDisplay::Full, SurNameDisplay::Initial, SurNameDisplay::Hidden];
+}
+
+/// The age-category prefilled for athletes born in `birth_year`, e.g. 2013 → "U13".
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AgeCategoryHint {
+    #[serde(rename="birth-year")]
+    pub birth_year: u16,
+    #[serde(rename="age-category")]
+    pub age_category: String
+}
+
+/// The entry fees in cents, e.g. higher ones for the older age-categories.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Fees {
+    #[serde(default, rename="per-athlete")]
+    pub per_athlete: u32,
+    #[serde(default, rename="per-age-category")]
+    pub per_age_category: Vec<AgeCategoryFee>
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AgeCategoryFee {
+    #[serde(rename="age-category")]
+    pub age_category: String,
+    pub fee: u32
+}
+
+impl Fees {
+    pub fn is_configured(&self) -> bool {
+        self.per_athlete > 0 || self.per_age_category.iter().any(|fee| fee.fee > 0)
+    }
+
+    /// The fee of an athlete in `age_category`, which falls back to the one per athlete.
+    pub fn fee(&self, age_category: &str) -> u32 {
+        let age_category = normalize_age_category(age_category);
+        self.per_age_category.iter().find(|fee| normalize_age_category(&fee.age_category) == age_category)
+            .map_or(self.per_athlete, |fee| fee.fee)
+    }
+
+    pub fn total(&self, athletes: &[RegisteringAthlete]) -> u32 {
+        athletes.iter().map(|athlete| self.fee(&athlete.age_category)).sum()
+    }
+
+    /// The count of athletes and their fees per written tournament, as the categories might be billed separately.
+    pub fn per_tournament(&self, tournaments: &[Tournament]) -> Vec<(String, GenderCategory, usize, u32)> {
+        tournaments.iter().map(|tournament| {
+            let count = tournament.athletes.len();
+            let fee = self.fee(&tournament.age_category).saturating_mul(u32::try_from(count).unwrap_or(u32::MAX));
+            (tournament.age_category.clone(), tournament.gender_category, count, fee)
+        }).collect()
+    }
+}
+
+/// Renders an amount in cents, e.g. 1250 → "12.50 €".
+pub fn render_fee(cents: u32) -> String {
+    format!("{}.{:02} €", cents / 100, cents % 100)
+}
+
+/// The columns of the tables of athletes, which are sorted by clicking their header.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all="kebab-case")]
+pub enum SortColumn {
+    GivenName,
+    SurName,
+    BirthYear,
+    Gender,
+    Belt
+}
+
+/// The sorting of a table, without a column the athletes keep the order they were added in.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SortingState {
+    #[serde(default)]
+    pub column: Option<SortColumn>,
+    #[serde(default)]
+    pub descending: bool
+}
+
+impl SortingState {
+    /// Clicking the sorted column again reverses the order, a third click restores the original one.
+    pub fn toggle(&mut self, column: SortColumn) {
+        *self = match self.column {
+            Some(sorted) if sorted == column && !self.descending => Self { column: Some(column), descending: true },
+            Some(sorted) if sorted == column => Self::default(),
+            _ => Self { column: Some(column), descending: false }
+        };
+    }
+
+    /// Sorts the indices of `athletes`, athletes with equal values keep their order.
+    pub fn sort(self, athletes: &[Athlete], indices: &mut [usize]) {
+        let Some(column) = self.column else {
+            return;
+        };
+        indices.sort_by(|a, b| {
+            let (a, b) = (&athletes[*a], &athletes[*b]);
+            let ordering = match column {
+                SortColumn::GivenName => a.given_name.to_lowercase().cmp(&b.given_name.to_lowercase()),
+                SortColumn::SurName => a.sur_name.to_lowercase().cmp(&b.sur_name.to_lowercase()),
+                SortColumn::BirthYear => a.birth_year.cmp(&b.birth_year),
+                SortColumn::Gender => a.gender.render().cmp(b.gender.render()),
+                SortColumn::Belt => a.belt.to_number().cmp(&b.belt.to_number())
+            };
+            if self.descending { ordering.reverse() } else { ordering }
+        });
+    }
+}
+
+/// The sorting of each table of athletes, kept in the config to survive restarts.
+#[derive(Clone, Copy, Default, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TableSorting {
+    #[serde(default)]
+    pub registering: SortingState,
+    #[serde(default)]
+    pub exam: SortingState,
+    #[serde(default)]
+    pub delete: SortingState
+}
+
+/// Which details of the athletes are published on the website of the club, the defaults reveal as little as possible.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WebsiteRoster {
+    #[serde(default, rename="sur-name")]
+    pub sur_name: SurNameDisplay,
+    #[serde(default = "default_true")]
+    pub belt: bool,
+    #[serde(default, rename="birth-year")]
+    pub birth_year: bool,
+    #[serde(default = "default_true")]
+    pub group: bool,
+    // e.g. "https://judo-musterstadt.de/fotos/{id}.jpg", no photos are included if empty
+    #[serde(default, rename="photo-url")]
+    pub photo_url: String
+}
+
+impl Default for WebsiteRoster {
+    fn default() -> Self {
+        Self { sur_name: SurNameDisplay::default(), belt: true, birth_year: false, group: true, photo_url: String::new() }
+    }
+}
+
+impl WebsiteRoster {
+    fn name(&self, athlete: &Athlete) -> String {
+        match self.sur_name {
+            SurNameDisplay::Full => format!("{} {}", athlete.given_name, athlete.sur_name),
+            SurNameDisplay::Initial => match athlete.sur_name.chars().next() {
+                Some(initial) => format!("{} {initial}.", athlete.given_name),
+                None => athlete.given_name.clone()
+            },
+            SurNameDisplay::Hidden => athlete.given_name.clone()
+        }
+    }
+
+    fn photo(&self, athlete: &Athlete) -> Option<String> {
+        if self.photo_url.is_empty() {
+            return None;
         }
+        Some(self.photo_url.replace("{id}", &athlete.id.to_string()).replace("{given}", &athlete.given_name)
+            .replace("{sur}", &athlete.sur_name))
+    }
+
+    fn sorted(athletes: &[Athlete]) -> Vec<&Athlete> {
+        let mut sorted: Vec<&Athlete> = athletes.iter().collect();
+        sorted.sort_by(|a, b| (&a.group, &a.sur_name, &a.given_name).cmp(&(&b.group, &b.sur_name, &b.given_name)));
+        sorted
     }
-    Some(ret)
+
+    /// Renders a list to be embedded in a page of the website, it is styled by the classes of its elements.
+    pub fn render_html(&self, athletes: &[Athlete]) -> String {
+        let mut ret = String::from("<ul class=\"e-melder-roster\">\n");
+        for athlete in Self::sorted(athletes) {
+            ret.push_str("<li>");
+            if let Some(photo) = self.photo(athlete) {
+                ret.push_str(&format!("<img class=\"photo\" src=\"{}\" alt=\"{}\">", escape_html(&photo),
+                    escape_html(&self.name(athlete))));
+            }
+            ret.push_str(&format!("<span class=\"name\">{}</span>", escape_html(&self.name(athlete))));
+            if self.belt {
+                ret.push_str(&format!("<span class=\"belt\">{}</span>", escape_html(&athlete.belt.to_display())));
+            }
+            if self.birth_year {
+                ret.push_str(&format!("<span class=\"year\">{}</span>", athlete.birth_year));
+            }
+            if self.group && !athlete.group.is_empty() {
+                ret.push_str(&format!("<span class=\"group\">{}</span>", escape_html(&athlete.group)));
+            }
+            ret.push_str("</li>\n");
+        }
+        ret.push_str("</ul>\n");
+        ret
+    }
+
+    pub fn render_json(&self, athletes: &[Athlete]) -> String {
+        let athletes: Vec<serde_json::Value> = Self::sorted(athletes).into_iter().map(|athlete| {
+            let mut entry = serde_json::Map::new();
+            entry.insert(String::from("name"), self.name(athlete).into());
+            if self.belt {
+                entry.insert(String::from("belt"), athlete.belt.to_display().into());
+            }
+            if self.birth_year {
+                entry.insert(String::from("year"), athlete.birth_year.into());
+            }
+            if self.group && !athlete.group.is_empty() {
+                entry.insert(String::from("group"), athlete.group.clone().into());
+            }
+            if let Some(photo) = self.photo(athlete) {
+                entry.insert(String::from("photo"), photo.into());
+            }
+            entry.into()
+        }).collect();
+        serde_json::to_string_pretty(&athletes).expect("unreachable")
+    }
+}
+
+/// The athletes, whose weight-category is no official class of their age- and gender-category, with the nearest official one.
+pub fn find_unofficial_weight_categories(registering_athletes: &[RegisteringAthlete]) -> Vec<(usize, &'static str)> {
+    registering_athletes.iter().enumerate().filter_map(|(index, athlete)| {
+        let nearest = WeightCategory::nearest_official(&athlete.weight_category, &athlete.age_category, athlete.gender_category)?;
+        Some((index, nearest))
+    }).collect()
+}
+
+/// The athletes, whose stored weight-category is no official class of the age-category they reach in the year of `date`,
+/// e.g. after moving up from U13 to U15, with the nearest official one.
+pub fn find_aged_out_athletes(athletes: &[Athlete], date: NaiveDate) -> Vec<(usize, &'static str)> {
+    athletes.iter().enumerate().filter_map(|(index, athlete)| {
+        let age_category = suggest_age_category(athlete.birth_year, athlete.gender, date)?;
+        let nearest = WeightCategory::nearest_official(&athlete.weight_category.to_string(), age_category, athlete.gender)?;
+        Some((index, nearest))
+    }).collect()
+}
+
+pub fn find_conflicts<'a>(history: &'a [HistoryEntry], registering_athletes: &'a [RegisteringAthlete], date: NaiveDate)
+-> Vec<(&'a HistoryEntry, &'a RegisteringAthlete)> {
+    // an athlete registered for another tournament on the same date is most likely double-booked
+    let mut ret = Vec::new();
+    for entry in history.iter().filter(|entry| entry.date == date) {
+        for registering_athlete in registering_athletes {
+            if entry.athletes.iter().any(|athlete| athlete.is_same_athlete(registering_athlete)) {
+                ret.push((entry, registering_athlete));
+            }
+        }
+    }
+    ret
+}
+
+pub fn assign_missing_ids(athletes: &mut [Athlete]) -> bool {
+    let mut assigned = false;
+    for athlete in athletes.iter_mut().filter(|athlete| athlete.id.is_nil()) {
+        athlete.id = Uuid::new_v4();
+        assigned = true;
+    }
+    assigned
+}
+
+/// Composes the characters of a name, e.g. entered via dead keys or an IME, which may commit "ễ" as "e" and combining marks.
+pub fn normalize_name(name: &str) -> String {
+    name.nfc().collect()
+}
+
+pub fn normalize_athlete_names(athletes: &mut [Athlete]) -> bool {
+    let mut normalized = false;
+    for athlete in athletes.iter_mut() {
+        for name in [&mut athlete.given_name, &mut athlete.sur_name] {
+            let composed = normalize_name(name);
+            if composed != *name {
+                *name = composed;
+                normalized = true;
+            }
+        }
+    }
+    normalized
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AthleteField {
+    GivenName,
+    SurName,
+    BirthYear,
+    Belt,
+    WeightCategory,
+    Gender,
+    Group,
+    RefereeLicense,
+    ConsentExpiry,
+    MedicalCertificateExpiry
+}
+
+#[derive(Clone, Debug)]
+pub enum AthleteDiff {
+    /// The athlete is only present in the other file.
+    Added(Athlete),
+    /// The athlete at `index` has different values for `fields` in the other file.
+    Changed { index: usize, theirs: Athlete, fields: Vec<AthleteField> }
+}
+
+pub fn diff_athletes(ours: &[Athlete], theirs: &[Athlete]) -> Vec<AthleteDiff> {
+    let mut diffs = Vec::new();
+    for their_athlete in theirs {
+        // a matching id takes precedence over a matching name
+        let index = ours.iter().position(|athlete| !athlete.id.is_nil() && athlete.id == their_athlete.id)
+            .or_else(|| ours.iter().position(|athlete| athlete.is_same_person(their_athlete)));
+        match index {
+            Some(index) => {
+                let fields = ours[index].changed_fields(their_athlete);
+                if !fields.is_empty() {
+                    diffs.push(AthleteDiff::Changed { index, theirs: their_athlete.clone(), fields });
+                }
+            }
+            None => diffs.push(AthleteDiff::Added(their_athlete.clone()))
+        }
+    }
+    diffs
+}
+
+/// The number of differences of another version of the athletes, e.g. of a backup about to be restored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize
+}
+
+pub fn summarize_diff(ours: &[Athlete], theirs: &[Athlete]) -> DiffSummary {
+    let diffs = diff_athletes(ours, theirs);
+    let changed = diffs.iter().filter(|diff| matches!(diff, AthleteDiff::Changed { .. })).count();
+    let removed = ours.iter().filter(|athlete| !theirs.iter().any(|their_athlete| athlete.is_same_person(their_athlete))).count();
+    DiffSummary { added: diffs.len() - changed, removed, changed }
+}
+
+pub fn merge_athletes(ours: &mut Vec<Athlete>, diffs: impl IntoIterator<Item=AthleteDiff>) {
+    for diff in diffs {
+        match diff {
+            AthleteDiff::Added(mut athlete) => {
+                if athlete.id.is_nil() || ours.iter().any(|our_athlete| our_athlete.id == athlete.id) {
+                    athlete.id = Uuid::new_v4();
+                }
+                ours.push(athlete);
+            }
+            AthleteDiff::Changed { index, theirs, .. } => {
+                // the own id is kept, so references to the athlete stay valid
+                if let Some(athlete) = ours.get_mut(index) {
+                    *athlete = Athlete { id: athlete.id, ..theirs };
+                }
+            }
+        }
+    }
+}
+
+/// A date of a tournament, which is likely mis-clicked in the calendar, e.g. in the wrong year.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnusualDate {
+    Past,
+    FarFuture
+}
+
+/// Tournaments are registered for ahead, but rarely more than a year before.
+pub fn check_tournament_date(date: NaiveDate, today: NaiveDate) -> Option<UnusualDate> {
+    if date < today {
+        Some(UnusualDate::Past)
+    }
+    else if today.checked_add_months(Months::new(12)).is_some_and(|limit| date > limit) {
+        Some(UnusualDate::FarFuture)
+    }
+    else {
+        None
+    }
+}
+
+pub fn has_start_in_season(history: &[HistoryEntry], athlete: &Athlete, season: i32) -> bool {
+    // seasons follow the calendar year
+    history.iter().filter(|entry| entry.date.year() == season)
+        .any(|entry| entry.athletes.iter().any(|registered| registered.is_same_as(athlete)))
 }
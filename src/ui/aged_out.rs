@@ -0,0 +1,62 @@
+use chrono::Local;
+use egui::Context;
+
+use crate::tournament_info::{find_aged_out_athletes, WeightCategory};
+use crate::utils::translate;
+use super::EMelderApp;
+
+impl EMelderApp {
+    /// Looks for athletes, whose weight-category is outdated by moving up an age-category, e.g. on start or a new season.
+    pub fn check_aged_out(&mut self) -> usize {
+        let aged_out = find_aged_out_athletes(&self.athletes, Local::now().date_naive());
+        let count = aged_out.len();
+        self.aged_out = (!aged_out.is_empty()).then_some(aged_out);
+        count
+    }
+
+    /// Sets the nearest official weight-category for all athletes found by `check_aged_out`.
+    pub fn update_aged_out(&mut self) {
+        let Some(aged_out) = self.aged_out.take() else {
+            return;
+        };
+        // indices are resolved defensively, as the athletes might have changed since the check
+        for (index, nearest) in aged_out {
+            if let (Some(athlete), Some(nearest)) = (self.athletes.get_mut(index), WeightCategory::from_str(nearest)) {
+                *athlete.get_weight_category_mut() = nearest;
+            }
+        }
+        self.save_athletes();
+    }
+}
+
+pub fn show_aged_out(app: &mut EMelderApp, ctx: &Context) {
+    let Some(aged_out) = &app.aged_out else {
+        return;
+    };
+    let mut update = None;
+    egui::Window::new(translate!("aged_out.title", &app.translations)).collapsible(false).resizable(false).show(ctx, |ui| {
+        ui.label(translate!("aged_out.text", &app.translations));
+        egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            for (index, nearest) in aged_out {
+                let Some(athlete) = app.athletes.get(*index) else {
+                    continue;
+                };
+                ui.label(format!("{} {} ({}): {} → {nearest}", athlete.get_given_name(), athlete.get_sur_name(),
+                    athlete.get_birth_year(), athlete.get_weight_category().to_string()));
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!app.read_only, egui::Button::new(translate!("aged_out.update", &app.translations))).clicked() {
+                update = Some(true);
+            }
+            if ui.button(translate!("aged_out.dismiss", &app.translations)).clicked() {
+                update = Some(false);
+            }
+        });
+    });
+    match update {
+        Some(true) => app.update_aged_out(),
+        Some(false) => app.aged_out = None,
+        None => {}
+    }
+}
@@ -0,0 +1,101 @@
+use egui::Ui;
+
+use crate::api::{ApiRequest, ApiResponse};
+use crate::tournament_info::{RegisteringAthlete, WeightCategory};
+use crate::utils::{translate, ExistingFiles};
+use super::EMelderApp;
+use super::registering::{can_register, find_unconfirmed, register};
+
+impl EMelderApp {
+    /// Handles a request of the local API, like the corresponding actions on the registering-page.
+    pub fn handle_api_request(&mut self, request: ApiRequest) -> ApiResponse {
+        match request {
+            ApiRequest::ListAthletes => match serde_json::to_string(&self.athletes) {
+                Ok(athletes) => ApiResponse::new(200, athletes),
+                Err(err) => ApiResponse::error(500, &err.to_string())
+            },
+            ApiRequest::CreateRegistration(registration) => {
+                if self.registering.writing.is_some() {
+                    return ApiResponse::error(409, "a registration is being written");
+                }
+                // the registration being prepared in the app is not replaced unseen
+                if !self.registering.athletes.is_empty() {
+                    return ApiResponse::error(409, "a registration is being prepared");
+                }
+                let mut athletes = Vec::new();
+                for new_athlete in registration.athletes {
+                    let Some(athlete) = self.athletes.iter().find(|athlete| athlete.get_id() == new_athlete.id) else {
+                        return ApiResponse::error(404, &format!("unknown athlete {}", new_athlete.id));
+                    };
+                    let mut registering_athlete = RegisteringAthlete::from_athlete(athlete);
+                    *registering_athlete.get_age_category_mut() = new_athlete.age_category;
+                    *registering_athlete.get_weight_category_mut() = new_athlete.weight_category;
+                    athletes.push(registering_athlete);
+                }
+                let count = athletes.len();
+                self.set_tournament(registration.name, registration.place, registration.date);
+                self.registering.athletes = athletes;
+                ApiResponse::new(201, serde_json::json!({ "athletes": count }).to_string())
+            }
+            ApiRequest::Export => {
+                if self.registering.writing.is_some() {
+                    return ApiResponse::error(409, "a registration is being written");
+                }
+                if self.registering.athletes.is_empty() {
+                    return ApiResponse::error(400, "the registration has no athletes");
+                }
+                if self.registering.name.trim().is_empty() || self.registering.place.trim().is_empty() {
+                    return ApiResponse::error(400, "the registration needs the name and place of the tournament");
+                }
+                // checked beforehand, as the writing only reports it by a notification
+                if let Some(athlete) = self.registering.athletes.iter()
+                    .find(|athlete| WeightCategory::from_str(athlete.get_weight_category()).is_none()) {
+                    return ApiResponse::error(400, &format!("invalid weight category of {} {}", athlete.get_given_name(),
+                        athlete.get_sur_name()));
+                }
+                if !can_register(self) {
+                    return ApiResponse::error(400, "the registration has duplicate athletes or athletes without a team");
+                }
+                // the questions asked before writing cannot be answered by the caller, so they have to be resolved in the app
+                if let Some(unconfirmed) = find_unconfirmed(self) {
+                    return ApiResponse::error(409, &unconfirmed);
+                }
+                // nobody can be asked about files of an earlier registration, so they are kept
+                register(self, ExistingFiles::Rename);
+                ApiResponse::new(202, serde_json::json!({ "status": "writing" }).to_string())
+            }
+        }
+    }
+
+    pub(super) fn handle_api_calls(&mut self) {
+        let Some(api) = &self.api else {
+            return;
+        };
+        let calls: Vec<_> = api.try_iter().collect();
+        for call in calls {
+            let response = self.handle_api_request(call.request);
+            // the server gave up waiting, if sending fails
+            let _ = call.respond.send(response);
+        }
+    }
+}
+
+pub fn show_api_config(app: &mut EMelderApp, ui: &mut Ui) {
+    ui.checkbox(&mut app.config.api_enabled, translate!("config.api", &app.translations));
+    if !app.config.api_enabled {
+        return;
+    }
+    ui.horizontal(|ui| {
+        ui.label(translate!("config.api.port", &app.translations));
+        ui.add(egui::DragValue::new(&mut app.config.api_port).range(1024..=65535));
+    });
+    ui.horizontal(|ui| {
+        ui.label(translate!("config.api.token", &app.translations));
+        ui.add(egui::TextEdit::singleline(&mut app.config.api_token).password(true));
+        if ui.button(translate!("config.api.generate_token", &app.translations)).clicked() {
+            app.config.api_token = uuid::Uuid::new_v4().simple().to_string();
+            ui.ctx().copy_text(app.config.api_token.clone());
+        }
+    });
+    ui.label(translate!("config.api.restart", &app.translations));
+}
@@ -1,58 +1,296 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::Receiver;
+use std::thread::JoinHandle;
+use std::time::Instant;
 
-use chrono::{Local, NaiveDate};
+use chrono::{Datelike, Local, Months, NaiveDate, NaiveDateTime};
 use eframe::CreationContext;
+use egui::scroll_area::ScrollAreaOutput;
 use egui::{TextWrapMode, Ui, Visuals};
 use egui_extras::{Column, TableBuilder};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-use crate::tournament_info::{Athlete, Belt, Club, GenderCategory,
-    RegisteringAthlete, WeightCategory};
-use crate::utils::{check_update_available, crash, get_configs, get_config_dir,
-    read_athletes, read_club, write_athletes, write_club, write_configs,
-    get_translations, UpdateAvailability, CODE_LINK, DEFAULT_BIRTH_YEAR, LANG_NAMES,
+use crate::api::{start_api, ApiCall};
+use crate::email::EmailSettings;
+use crate::error::Error;
+use crate::exporter::PluginExporter;
+use crate::tournament_info::{assign_missing_ids, normalize_athlete_names, AgeCategoryFee, AgeCategoryHint, Athlete, Belt, Club, Coach, Competition, CsvExport, Department, Draft, DirectoryClub, Document, Fees, GenderAbbreviations, GeneratedFile, GenderCategory,
+    HistoryEntry, RefereeLicense, RegisteringAthlete, Season, SortColumn, TableSorting, TournamentEncoding, UnusualDate, WeightCategory, WebsiteRoster, suggest_belt};
+use crate::utils::{check_update_available, get_configs, get_invitation_file, get_languages,
+    read_athletes, read_club, read_generated_files, read_history, write_athletes,
+    get_translations, is_valid_email, normalize_phone, ExistingFiles, ItemState, Pin, UpdateAvailability, CODE_LINK, DEFAULT_BIRTH_YEAR, DEFAULT_FILE_NAME_TEMPLATE, LANG_NAMES,
     LICENSE, LICENSE_LINK, LOWER_BOUND_BIRTH_YEAR, UPPER_BOUND_BIRTH_YEAR, VERSION, translate};
-use super::registering::show_registering;
+use super::aged_out::show_aged_out;
+use super::api::show_api_config;
+use super::belt_select::belt_select;
+use super::csv_export::show_csv_export_config;
+use super::demo::{show_demo_banner, show_demo_button, Demo};
+use super::draft::import_file;
+use super::email::{show_email_config, EmailTask};
+use super::desktop_integration::{show_desktop_integration_config, show_desktop_integration_offer};
+use super::generated::{show_generated, Verification};
+use super::history::{show_history, ForeignRegistrations, HistoryDetails};
+use super::lock::{show_auto_lock_config, show_lock, show_pin_edit};
+use super::exam::{show_exam, ExamPage};
+use super::merge::{show_merge, Merge};
+use super::notifications::{show_toasts, Toast};
+use super::paste_import::{show_paste_import, PasteImport};
+use super::plugins::PluginTask;
+use super::registering::{can_register, finish_writing, register, show_registering, show_writing, start_registering, WritingTask};
+use super::restore::{show_restore, Restore};
+use super::roster::show_roster;
+use super::save_error::{show_save_error, SaveError};
+use super::schedule::show_schedule_config;
+use super::seasons::show_seasons;
+use super::shortcuts::{pressed, shortcut_hint, REGISTER, SAVE};
+use super::sorting::sortable_header;
+use super::theme::{error_label, high_contrast_visuals, warning_label};
+use super::tour::Tour;
+use super::weight_categories::{save_weight_categories, show_weight_categories};
 
-#[derive(Default, Debug)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
 enum Mode {
     #[default]
     Registering,
+    Exam,
     Adding,
     Deleting,
+    Merge,
+    Roster,
+    Seasons,
+    History,
+    Generated,
     EditAthlete,
+    WeightCategories,
     EditClub,
     Config,
     About
 }
 
+impl Mode {
+    const NAV_PAGES: [Mode; 13] = [Mode::Registering, Mode::Exam, Mode::Adding, Mode::EditAthlete, Mode::Deleting, Mode::Merge,
+        Mode::Roster, Mode::Seasons, Mode::History, Mode::Generated, Mode::EditClub, Mode::Config, Mode::About];
+
+    fn translation_key(self) -> &'static str {
+        match self {
+            Mode::Registering => "application.register",
+            Mode::Exam => "application.exam",
+            Mode::Adding => "application.add",
+            Mode::Deleting => "application.delete",
+            Mode::Merge => "application.merge",
+            Mode::Roster => "application.roster",
+            Mode::Seasons => "application.seasons",
+            Mode::History => "application.history",
+            Mode::Generated => "application.generated",
+            Mode::EditAthlete | Mode::WeightCategories => "application.edit_athlete",
+            Mode::EditClub => "application.edit",
+            Mode::Config => "application.config",
+            Mode::About => "application.about"
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema)]
+struct NavPage {
+    mode: Mode,
+    visible: bool
+}
+
+fn default_file_name_template() -> String {
+    String::from(DEFAULT_FILE_NAME_TEMPLATE)
+}
+
+fn default_age_categories() -> Vec<String> {
+    ["U11", "U13", "U15", "U18", "U21", "Männer", "Frauen"].into_iter().map(String::from).collect()
+}
+
+fn default_nav_pages() -> Vec<NavPage> {
+    Mode::NAV_PAGES.iter().map(|mode| NavPage { mode: *mode, visible: true }).collect()
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum OpenFolder {
+    Always,
+    #[default]
+    Ask,
+    Never
+}
+
+/// How the athletes are picked on the registering page, two panes suit building large selections.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum RegisteringLayout {
+    #[default]
+    Stacked,
+    DualPane
+}
+
+impl OpenFolder {
+    const ALL: [OpenFolder; 3] = [OpenFolder::Always, OpenFolder::Ask, OpenFolder::Never];
+
+    fn translation_key(self) -> &'static str {
+        match self {
+            OpenFolder::Always => "config.open_folder.always",
+            OpenFolder::Ask => "config.open_folder.ask",
+            OpenFolder::Never => "config.open_folder.never"
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    Light,
+    Dark,
+    HighContrast
+}
+
+impl Theme {
+    const ALL: [Theme; 3] = [Theme::Light, Theme::Dark, Theme::HighContrast];
+
+    fn translation_key(self) -> &'static str {
+        match self {
+            Theme::Light => "config.theme.light",
+            Theme::Dark => "config.theme.dark",
+            Theme::HighContrast => "config.theme.high_contrast"
+        }
+    }
+
+    fn visuals(self) -> Visuals {
+        match self {
+            Theme::Light => Visuals::light(),
+            Theme::Dark => Visuals::dark(),
+            Theme::HighContrast => high_contrast_visuals()
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Role {
+    Admin,
+    Coach
+}
+
 #[derive(Debug)]
 pub(super) struct Registering {
     pub(super) athletes: Vec<RegisteringAthlete>,
     pub(super) name: String,
     pub(super) place: String,
+    pub(super) place_suggestion: usize,
+    pub(super) team_suggestion: usize,
     pub(super) date: NaiveDate,
-    pub(super) search: String
+    pub(super) search: String,
+    pub(super) only_without_start: bool,
+    pub(super) department: Option<usize>,
+    pub(super) competition: Competition,
+    pub(super) coach: Coach,
+    pub(super) invitation: Option<PathBuf>,
+    pub(super) invitation_error: Option<String>,
+    // the text of a PDF is read in the background, as large invitations take a while
+    pub(super) invitation_reading: Option<JoinHandle<Result<String, Error>>>,
+    pub(super) unusual_date: Option<UnusualDate>,
+    pub(super) default_weight_categories: Option<Vec<String>>,
+    pub(super) unofficial_weight_categories: Option<Vec<(usize, &'static str)>>,
+    pub(super) conflicts: Option<Vec<String>>,
+    pub(super) existing_files: Option<Vec<String>>,
+    pub(super) write_error: Option<String>,
+    pub(super) write_results: Vec<(String, ItemState)>,
+    pub(super) csv_export: usize,
+    pub(super) csv_export_error: Option<String>,
+    pub(super) draft_error: Option<String>,
+    pub(super) shared_drafts: Vec<(PathBuf, Draft)>,
+    // discovered when the registering-page is shown first
+    pub(super) plugins: Option<Vec<PluginExporter>>,
+    pub(super) plugin: usize,
+    pub(super) plugin_error: Option<String>,
+    pub(super) plugin_task: Option<PluginTask>,
+    pub(super) ask_open_folder: bool,
+    pub(super) confirm_discard: bool,
+    // the athletes and entries of the registration selected in the dual-pane layout, by their index
+    pub(super) pool_selection: BTreeSet<Uuid>,
+    pub(super) entry_selection: BTreeSet<usize>,
+    // the fees per written tournament of the last registration
+    pub(super) fee_summary: Option<Vec<(String, GenderCategory, usize, u32)>>,
+    pub(super) writing: Option<WritingTask>,
+    pub(super) email: Option<EmailTask>
 }
 
 impl Default for Registering {
     fn default() -> Self {
         Self {
-            athletes: Vec::new(), name: String::new(), place: String::new(),
-            date: Local::now().date_naive(), search: String::new()
+            athletes: Vec::new(), name: String::new(), place: String::new(), place_suggestion: 0, team_suggestion: 0,
+            date: Local::now().date_naive(), search: String::new(),
+            only_without_start: false, department: None, competition: Competition::Individual, coach: Coach::default(),
+            invitation: None, invitation_error: None, invitation_reading: None,
+            unusual_date: None, default_weight_categories: None, unofficial_weight_categories: None, conflicts: None,
+            existing_files: None, write_error: None, write_results: Vec::new(),
+            csv_export: 0, csv_export_error: None, draft_error: None, shared_drafts: Vec::new(),
+            plugins: None, plugin: 0, plugin_error: None, plugin_task: None,
+            ask_open_folder: false, confirm_discard: false, pool_selection: BTreeSet::new(), entry_selection: BTreeSet::new(),
+            fee_summary: None, writing: None, email: None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Seasons {
+    pub(super) name: String,
+    pub(super) carry_over: bool,
+    pub(super) archived: Option<Vec<Season>>,
+    // the index into `archived`
+    pub(super) selected: Option<usize>,
+    // set, when a season of the same name is archived already
+    pub(super) confirm_overwrite: bool,
+    // the translation-key of the error of the last archiving
+    pub(super) error: Option<&'static str>
+}
+
+impl Default for Seasons {
+    fn default() -> Self {
+        let year = Local::now().year();
+        Self {
+            name: format!("{}/{:02}", year, (year + 1) % 100), carry_over: true, archived: None, selected: None,
+            confirm_overwrite: false, error: None
         }
     }
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Session {
+    mode: Mode,
+    #[serde(rename = "register-search")]
+    register_search: String,
+    #[serde(rename = "only-without-start")]
+    only_without_start: bool,
+    #[serde(rename = "exam-search")]
+    exam_search: String,
+    #[serde(rename = "directory-search")]
+    directory_search: String,
+    // the vertical offsets of the tables by their ids
+    #[serde(rename = "scroll-offsets")]
+    scroll_offsets: HashMap<String, f32>
+}
+
+const SESSION_KEY: &str = "session";
+
 #[derive(Debug)]
 struct Adding {
     given_name: String,
     sur_name: String,
     belt: Belt,
     year: u16,
-    gender: GenderCategory
+    gender: GenderCategory,
+    group: String,
+    // a belt chosen by hand is no longer replaced by the suggestion
+    belt_chosen: bool
 }
 
 impl Adding {
@@ -66,12 +304,14 @@ impl Adding {
             sur_name: String::default(),
             belt: Belt::default(),
             year: DEFAULT_BIRTH_YEAR,
-            gender: config.default_gender_category
+            gender: config.default_gender_category,
+            group: String::default(),
+            belt_chosen: false
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Config {
     pub lang: String,
     #[serde(rename = "dark-mode")]
@@ -82,11 +322,349 @@ pub struct Config {
     pub club_file: PathBuf,
     #[serde(rename = "tournament-basedir")]
     pub tournament_basedir: PathBuf,
+    #[serde(default = "default_file_name_template", rename = "file-name-template")]
+    pub file_name_template: String,
+    #[serde(default, rename = "tournament-encoding")]
+    pub tournament_encoding: TournamentEncoding,
+    #[serde(rename = "history-file", default = "crate::utils::get_default_history_file")]
+    pub history_file: PathBuf,
+    #[serde(rename = "generated-file", default = "crate::utils::get_default_generated_file")]
+    pub generated_file: PathBuf,
     #[serde(skip_serializing, skip_deserializing)]
     pub langs: Vec<String>,
     #[serde(default, serialize_with="crate::utils::serialize_gender_category",
     deserialize_with="crate::utils::deserialize_gender_category", rename = "default-gender-category")]
-    pub default_gender_category: GenderCategory
+    #[schemars(with = "String")]
+    pub default_gender_category: GenderCategory,
+    #[serde(default, rename = "gender-abbreviations")]
+    pub gender_abbreviations: GenderAbbreviations,
+    #[serde(default, rename = "open-folder")]
+    pub open_folder: OpenFolder,
+    #[serde(default, rename = "kiosk-mode")]
+    pub kiosk_mode: bool,
+    #[serde(default, rename = "touch-mode")]
+    pub touch_mode: bool,
+    #[serde(default, rename = "role-selection")]
+    pub role_selection: bool,
+    #[serde(default, rename = "admin-pin")]
+    #[schemars(with = "String")]
+    pub admin_pin: Pin,
+    #[serde(default = "default_nav_pages", rename = "nav-pages")]
+    nav_pages: Vec<NavPage>,
+    #[serde(default, rename = "recent-athletes-files")]
+    pub recent_athletes_files: Vec<PathBuf>,
+    #[serde(default, rename = "recent-club-files")]
+    pub recent_club_files: Vec<PathBuf>,
+    #[serde(default, rename = "csv-exports")]
+    pub csv_exports: Vec<CsvExport>,
+    #[serde(default, rename = "shared-drafts-dir")]
+    pub shared_drafts_dir: Option<PathBuf>,
+    #[serde(default = "crate::utils::get_default_draft_user", rename = "draft-user")]
+    pub draft_user: String,
+    #[serde(default, rename = "previous-lang")]
+    pub previous_lang: Option<String>,
+    // configs of older versions only have `dark_mode`, which is still written for them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    theme: Option<Theme>,
+    #[serde(default, rename = "api-enabled")]
+    pub api_enabled: bool,
+    #[serde(default = "default_api_port", rename = "api-port")]
+    pub api_port: u16,
+    #[serde(default, rename = "api-token")]
+    pub api_token: String,
+    #[serde(default, rename = "website-roster")]
+    pub website_roster: WebsiteRoster,
+    #[serde(default = "default_age_categories", rename = "age-categories")]
+    pub age_categories: Vec<String>,
+    #[serde(default, rename = "age-category-hints")]
+    pub age_category_hints: Vec<AgeCategoryHint>,
+    #[serde(default)]
+    pub fees: Fees,
+    #[serde(default, rename = "belt-suggestion")]
+    pub belt_suggestion: BeltSuggestion,
+    #[serde(default, rename = "tour-completed")]
+    pub tour_completed: bool,
+    #[serde(default, rename = "desktop-integration-offered")]
+    pub desktop_integration_offered: bool,
+    #[serde(default, rename = "entry-list-pdf")]
+    pub entry_list_pdf: bool,
+    #[serde(default, rename = "entry-list-csv")]
+    pub entry_list_csv: bool,
+    // the events of the registered tournaments are added to it, e.g. for importing them into a shared calendar
+    #[serde(default, rename = "calendar-file")]
+    pub calendar_file: Option<PathBuf>,
+    #[serde(default)]
+    pub email: EmailSettings,
+    #[serde(default, rename = "auto-lock")]
+    pub auto_lock: AutoLock,
+    #[serde(default)]
+    pub schedule: Schedule,
+    // the coach of the last registration
+    #[serde(default)]
+    pub coach: Coach,
+    #[serde(default, rename = "registering-layout")]
+    pub registering_layout: RegisteringLayout,
+    #[serde(default)]
+    pub sorting: TableSorting
+}
+
+/// The belt suggested for new athletes by their birth year, e.g. Kyu 9 and 8 for beginner groups.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BeltSuggestion {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_first_exam_age", rename = "first-exam-age")]
+    pub first_exam_age: u8,
+    #[serde(default = "default_years_per_exam", rename = "years-per-exam")]
+    pub years_per_exam: u8
+}
+
+fn default_first_exam_age() -> u8 {
+    8
+}
+
+fn default_years_per_exam() -> u8 {
+    1
+}
+
+impl Default for BeltSuggestion {
+    fn default() -> Self {
+        Self { enabled: true, first_exam_age: default_first_exam_age(), years_per_exam: default_years_per_exam() }
+    }
+}
+
+impl BeltSuggestion {
+    pub fn suggest(&self, birth_year: u16) -> Option<Belt> {
+        self.enabled.then(|| suggest_belt(birth_year, Local::now().year(), self.first_exam_age, self.years_per_exam))
+    }
+}
+
+/// Backups and exports run nightly while the app is open, or on the next start, if the app was closed at that time.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Schedule {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_schedule_hour")]
+    pub hour: u32,
+    #[serde(default = "default_true")]
+    pub backup: bool,
+    #[serde(default, rename = "backup-dir")]
+    pub backup_dir: Option<PathBuf>,
+    #[serde(default, rename = "website-roster-file")]
+    pub website_roster_file: Option<PathBuf>,
+    #[serde(default, rename = "last-run")]
+    pub last_run: Option<NaiveDateTime>,
+    #[serde(default, rename = "last-error")]
+    pub last_error: Option<String>
+}
+
+fn default_schedule_hour() -> u32 {
+    2
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self { enabled: false, hour: default_schedule_hour(), backup: true, backup_dir: None, website_roster_file: None,
+            last_run: None, last_error: None }
+    }
+}
+
+impl Schedule {
+    pub fn is_due(&self, now: NaiveDateTime) -> bool {
+        let Some(today) = now.date().and_hms_opt(self.hour, 0, 0) else {
+            return false;
+        };
+        // the most recent time the jobs should have run
+        let scheduled = if now >= today { today } else { today - chrono::Duration::days(1) };
+        self.last_run.is_none_or(|last_run| last_run < scheduled)
+    }
+}
+
+/// Hides the athletes behind a PIN-prompt, when nobody used the app for a while, e.g. on a shared PC in the dojo.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AutoLock {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_lock_minutes")]
+    pub minutes: u32,
+    #[serde(default)]
+    #[schemars(with = "String")]
+    pub pin: Pin
+}
+
+fn default_lock_minutes() -> u32 {
+    10
+}
+
+impl Default for AutoLock {
+    fn default() -> Self {
+        Self { enabled: false, minutes: default_lock_minutes(), pin: Pin::default() }
+    }
+}
+
+/// Edits an amount in cents as euros, which are entered with a point or comma.
+fn fee_value(fee: &mut u32) -> egui::DragValue<'_> {
+    egui::DragValue::new(fee).speed(10.0).custom_formatter(|cents, _| format!("{:.2} €", cents / 100.0))
+        .custom_parser(|text| text.trim().trim_end_matches('€').trim().replace(',', ".").parse::<f64>().ok().map(|euros| (euros * 100.0).round()))
+}
+
+fn pick_calendar_file() -> Option<PathBuf> {
+    // an existing calendar is extended, so it is not asked for overwriting
+    rfd::FileDialog::new().set_can_create_directories(true).add_filter("iCalendar", &["ics"])
+        .set_file_name("e-melder.ics").save_file()
+}
+
+fn default_api_port() -> u16 {
+    8787
+}
+
+impl Config {
+    pub fn theme(&self) -> Theme {
+        self.theme.unwrap_or(if self.dark_mode { Theme::Dark } else { Theme::Light })
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = Some(theme);
+        self.dark_mode = theme != Theme::Light;
+    }
+}
+
+const MAX_RECENT_FILES: usize = 5;
+
+fn push_recent_file(recent_files: &mut Vec<PathBuf>, file: &Path) {
+    recent_files.retain(|recent_file| recent_file != file);
+    recent_files.insert(0, file.to_owned());
+    recent_files.truncate(MAX_RECENT_FILES);
+}
+
+pub(super) fn load_athletes(athletes_file: &Path) -> Vec<Athlete> {
+    match read_athletes(athletes_file) {
+        Ok(mut athletes) => {
+            // the ids have to be persisted, so references to the athletes stay valid across restarts
+            let assigned = assign_missing_ids(&mut athletes);
+            // names of older versions might be decomposed, so they would not match the ones entered now
+            let normalized = normalize_athlete_names(&mut athletes);
+            if assigned || normalized {
+                if let Err(err) = write_athletes(athletes_file, &athletes) {
+                    log::warn!("failed to write migrated athletes, due to {err}");
+                }
+            }
+            athletes
+        }
+        Err(err) => {
+            if err.kind() == io::ErrorKind::NotFound {
+                // e.g. at initial run or for using an alternative athletes-file
+                Vec::new()
+            }
+            else {
+                log::warn!("failed to read athletes, due to {err}");
+                Vec::new()
+            }
+        }
+    }
+}
+
+pub(super) fn load_club(club_file: &Path) -> Club {
+    match read_club(club_file) {
+        Ok(club) => club,
+        Err(err) => {
+            if err.kind() == io::ErrorKind::NotFound {
+                // e.g. at initial run or for using an alternative club-file
+                Club::default()
+            }
+            else {
+                log::warn!("failed to read club, due to {err}");
+                Club::default()
+            }
+        }
+    }
+}
+
+pub(super) fn load_history(history_file: &Path) -> Vec<HistoryEntry> {
+    match read_history(history_file) {
+        Ok(history) => history,
+        Err(err) => {
+            if err.kind() == io::ErrorKind::NotFound {
+                // e.g. before the first registration
+                Vec::new()
+            }
+            else {
+                log::warn!("failed to read history, due to {err}");
+                Vec::new()
+            }
+        }
+    }
+}
+
+fn apply_touch_mode(ctx: &egui::Context, touch_mode: bool) {
+    // bigger hit-targets for tablets at the weigh-in
+    let mut style = egui::Style { visuals: ctx.style().visuals.clone(), ..Default::default() };
+    if touch_mode {
+        style.spacing.interact_size = egui::vec2(48.0, 40.0);
+        style.spacing.button_padding = egui::vec2(12.0, 8.0);
+        style.spacing.item_spacing = egui::vec2(12.0, 8.0);
+        style.spacing.icon_width = 24.0;
+        style.spacing.icon_width_inner = 16.0;
+        style.spacing.combo_height = 400.0;
+        style.spacing.scroll.bar_width = 16.0;
+        for (text_style, font) in &mut style.text_styles {
+            if matches!(text_style, egui::TextStyle::Body | egui::TextStyle::Button) {
+                font.size = 18.0;
+            }
+        }
+    }
+    ctx.set_style(style);
+}
+
+pub(super) fn show_phone_preview(ui: &mut Ui, phone: &str, translations: &HashMap<String, String>) {
+    if phone.is_empty() {
+        return;
+    }
+    match normalize_phone(phone) {
+        Some(normalized) if normalized != phone => {
+            ui.label(format!("→ {normalized}"));
+        }
+        Some(_) => {},
+        None => {
+            ui.label(translate!("edit.phone.invalid", translations));
+        }
+    }
+}
+
+fn document_translation_key(document: Document) -> &'static str {
+    match document {
+        Document::ParentalConsent => "edit_athlete.documents.consent",
+        Document::MedicalCertificate => "edit_athlete.documents.medical_certificate"
+    }
+}
+
+fn show_document_expiry(ui: &mut Ui, id: &str, document: Document, expiry: &mut Option<NaiveDate>, today: NaiveDate,
+    translations: &HashMap<String, String>) -> bool {
+    let mut changed = false;
+    ui.label(translate!(document_translation_key(document), translations));
+    let Some(date) = expiry else {
+        if ui.small_button("+").on_hover_text(translate!("edit_athlete.documents.add", translations)).clicked() {
+            *expiry = Some(today.checked_add_months(Months::new(12)).unwrap_or(today));
+            changed = true;
+        }
+        return changed;
+    };
+    let id_salt = format!("{id}-{document:?}");
+    if ui.add(egui_extras::DatePickerButton::new(date).id_salt(&id_salt).format("%d.%m.%Y")).changed() {
+        changed = true;
+    }
+    if today > *date {
+        error_label(ui, translate!("edit_athlete.documents.expired", translations));
+    }
+    if ui.small_button("✖").on_hover_text(translate!("edit_athlete.documents.remove", translations)).clicked() {
+        *expiry = None;
+        changed = true;
+    }
+    changed
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -94,62 +672,208 @@ pub struct Config {
 pub struct EMelderApp {
     pub(super) athletes: Vec<Athlete>,
     pub(super) club: Club,
+    pub(super) history: Vec<HistoryEntry>,
+    pub(super) generated: Vec<GeneratedFile>,
+    pub(super) generated_verification: HashMap<usize, Verification>,
     pub(super) registering: Registering,
     adding: Adding,
+    pub(super) seasons: Seasons,
+    pub(super) exam: ExamPage,
+    pub(super) merge: Merge,
+    pub(super) restore: Restore,
+    directory_search: String,
+    referees_only: bool,
+    pub(super) new_weight_categories: Vec<String>,
+    pub(super) weight_categories_invalid: bool,
+    pub(super) paste_import: Option<PasteImport>,
+    pub(super) save_error: Option<SaveError>,
     mode: Mode,
     pub(super) config: Config,
     update_check_text: Option<String>,
     popup_open: bool,
-    pub(super) translations: HashMap<String, String>
+    pub(super) read_only: bool,
+    role: Option<Role>,
+    pin_input: String,
+    pin_wrong: bool,
+    // the PINs being entered in the settings, only their hashes are kept in the config
+    pub(super) admin_pin_edit: String,
+    pub(super) lock_pin_edit: String,
+    window_title: String,
+    // the language of the loaded translations, the config holds the one selected in the settings
+    active_lang: String,
+    pub(super) api: Option<Receiver<ApiCall>>,
+    pub(super) notifications: bool,
+    pub(super) toasts: Vec<Toast>,
+    // set, when closing was delayed for pending background-tasks
+    pub(super) closing: Option<Instant>,
+    pub(super) demo: Option<Box<Demo>>,
+    pub(super) tour: Tour,
+    // athletes with the nearest official weight-category, whose stored one is outdated by moving up an age-category
+    pub(super) aged_out: Option<Vec<(usize, &'static str)>>,
+    pub(super) history_details: Option<HistoryDetails>,
+    scroll_offsets: HashMap<String, f32>,
+    // the offsets of the last session, which are applied once the table is shown first
+    restored_scroll_offsets: HashMap<String, f32>,
+    pub(super) history_export_error: Option<String>,
+    pub(super) foreign_registrations: Option<ForeignRegistrations>,
+    // the message of the last change of the file-associations or shortcuts
+    pub(super) desktop_integration: Option<Result<&'static str, String>>,
+    pub(super) last_activity: Instant,
+    pub(super) locked: bool,
+    pub(super) unlock_input: String,
+    pub(super) unlock_wrong: bool,
+    // shared with the threads showing notifications, so they do not need a copy each
+    pub(super) translations: Arc<HashMap<String, String>>
 }
 
 impl EMelderApp {
-    pub fn new(cc: &CreationContext) -> io::Result<Self> {
-        let mut configs = get_configs()?;
-        let athletes = match read_athletes(&configs.athletes_file) {
-            Ok(athletes) => athletes,
-            Err(err) => {
-                if err.kind() == io::ErrorKind::NotFound {
-                    // e.g. at initial run or for using an alternative athletes-file
-                    Vec::new()
+    pub fn new(cc: &CreationContext, kiosk_mode: bool) -> Result<Self, Error> {
+        let mut app = Self::headless(get_configs()?, kiosk_mode);
+
+        cc.egui_ctx.set_visuals(app.config.theme().visuals());
+        apply_touch_mode(&cc.egui_ctx, app.config.touch_mode);
+        let session: Session = cc.storage.and_then(|storage| eframe::get_value(storage, SESSION_KEY)).unwrap_or_default();
+        app.registering.search = session.register_search;
+        app.registering.only_without_start = session.only_without_start;
+        app.exam.search = session.exam_search;
+        app.directory_search = session.directory_search;
+        app.restored_scroll_offsets = session.scroll_offsets;
+        app.mode = session.mode;
+        app.restore_draft();
+        app.check_aged_out();
+        // only first-time users are guided through their first registration
+        if !app.config.tour_completed && app.history.is_empty() {
+            app.start_tour();
+            app.mode = Mode::Registering;
+        }
+        app.notifications = true;
+        app.translations = Arc::new(get_translations(&app.config.lang)?);
+        if app.config.api_enabled {
+            let ctx = cc.egui_ctx.clone();
+            match start_api(app.config.api_port, app.config.api_token.clone(), move || ctx.request_repaint()) {
+                Ok(api) => {
+                    app.api = Some(api);
                 }
-                else {
-                    log::warn!("failed to read athletes, due to {err}");
-                    Vec::new()
+                Err(err) => {
+                    log::warn!("failed to start the local API, due to {err}");
                 }
             }
-        };
-        let club = match read_club(&configs.club_file) {
-            Ok(club) => club,
+        }
+        Ok(app)
+    }
+
+    /// Creates the app without a window, translations and desktop-notifications, e.g. for driving it from tests.
+    pub fn headless(mut configs: Config, kiosk_mode: bool) -> Self {
+        let athletes = load_athletes(&configs.athletes_file);
+        let club = load_club(&configs.club_file);
+        let history = load_history(&configs.history_file);
+        let generated = match read_generated_files(&configs.generated_file) {
+            Ok(generated) => generated,
             Err(err) => {
                 if err.kind() == io::ErrorKind::NotFound {
-                    // e.g. at initial run or for using an alternative club-file
-                    Club::default()
+                    // e.g. before the first registration
+                    Vec::new()
                 }
                 else {
-                    log::warn!("failed to read club, due to {err}");
-                    Club::default()
+                    log::warn!("failed to read generated files, due to {err}");
+                    Vec::new()
                 }
             }
         };
-        let languages = std::fs::read_dir(get_config_dir()?.join("e-melder").join("lang"))?.map(|entry| {
-            entry.unwrap_or_else(|err| {
-                log::error!("failed to read config-directory/e-melder/lang, due to {err}");
-                crash();
-            }).path().file_stem().expect("unreachable").to_str().expect("unreachable").to_owned()
-        }).collect();
-        configs.langs = languages;
-
-        let visuals = if configs.dark_mode { Visuals::dark() } else { Visuals::light() };
-        
-        cc.egui_ctx.set_visuals(visuals);
-        let lang_clone = configs.lang.clone();
+        // pages missing in the config, e.g. added by a newer version, are appended
+        for mode in Mode::NAV_PAGES {
+            if !configs.nav_pages.iter().any(|page| page.mode == mode) {
+                configs.nav_pages.push(NavPage { mode, visible: true });
+            }
+        }
+
+        // destructive actions are disabled, if either the config or the command-line requests it
+        let read_only = configs.kiosk_mode || kiosk_mode;
+        let role = if configs.role_selection { None } else { Some(Role::Admin) };
         let adding = Adding::from_config(&configs);
-        Ok(Self {
-            athletes, club, registering: Registering::default(), adding, mode: Mode::default(),
-            config: configs, popup_open: false, update_check_text: None,
-            translations: get_translations(&lang_clone)?
-        })
+        let active_lang = configs.lang.clone();
+        Self {
+            athletes, club, history, generated, generated_verification: HashMap::new(),
+            registering: Registering { coach: configs.coach.clone(), ..Registering::default() }, adding, seasons: Seasons::default(),
+            exam: ExamPage::default(), merge: Merge::default(), restore: Restore::default(), directory_search: String::new(), referees_only: false,
+            new_weight_categories: Vec::new(), weight_categories_invalid: false, paste_import: None, save_error: None,
+            mode: Mode::default(),
+            config: configs, popup_open: false, read_only, role, pin_input: String::new(), pin_wrong: false,
+            admin_pin_edit: String::new(), lock_pin_edit: String::new(),
+            window_title: String::new(), active_lang, api: None, update_check_text: None,
+            notifications: false, toasts: Vec::new(), closing: None, demo: None, tour: Tour::default(), aged_out: None,
+            history_details: None, scroll_offsets: HashMap::new(), restored_scroll_offsets: HashMap::new(), history_export_error: None, foreign_registrations: None,
+            desktop_integration: None, last_activity: Instant::now(), locked: false, unlock_input: String::new(), unlock_wrong: false,
+            translations: Arc::default()
+        }
+    }
+
+    pub fn get_athletes(&self) -> &[Athlete] {
+        &self.athletes
+    }
+
+    pub fn add_athlete(&mut self, athlete: Athlete) {
+        self.athletes.push(athlete);
+        self.save_athletes();
+    }
+
+    pub fn set_tournament(&mut self, name: String, place: String, date: NaiveDate) {
+        self.registering.name = name;
+        self.registering.place = place;
+        self.registering.date = date;
+    }
+
+    /// The athlete with `id`, the tables refer to athletes by their id, as their rows are sorted
+    /// and the athletes might have changed since the table was shown.
+    pub(super) fn athlete_mut(&mut self, id: Uuid) -> Option<&mut Athlete> {
+        self.athletes.iter_mut().find(|athlete| athlete.get_id() == id)
+    }
+
+    /// Adds the athlete at `index` to the current registration, returning it for setting its categories.
+    pub fn add_to_registering(&mut self, index: usize) -> Option<&mut RegisteringAthlete> {
+        let athlete = self.athletes.get(index)?;
+        self.registering.athletes.push(RegisteringAthlete::for_tournament(athlete, self.registering.date, &self.config.age_category_hints));
+        self.registering.athletes.last_mut()
+    }
+
+    /// Loads the registration of the history-entry at `index` for correcting and writing it again.
+    pub fn reopen_history_entry(&mut self, index: usize) -> Option<&[RegisteringAthlete]> {
+        let entry = self.history.get(index)?;
+        self.registering.athletes = entry.to_registering_athletes();
+        self.registering.invitation = get_invitation_file(&self.config.history_file, entry);
+        self.set_tournament(entry.get_name().to_owned(), entry.get_place().to_owned(), entry.get_date());
+        self.mode = Mode::Registering;
+        Some(&self.registering.athletes)
+    }
+
+    /// Writes the tournament-files of the current registration and waits for the writing to finish,
+    /// files of an earlier registration are kept by numbering the new ones.
+    pub fn register_blocking(&mut self) {
+        register(self, ExistingFiles::Rename);
+        finish_writing(self);
+    }
+
+    /// Opens a draft or tournament-file for registering, e.g. when started by double-clicking it.
+    pub fn open_file(&mut self, path: &Path) {
+        import_file(self, path);
+        self.mode = Mode::Registering;
+    }
+
+    /// Scrolls the table to where it was left in the last session, only once, so it can be scrolled afterwards.
+    pub(super) fn restore_scroll_offset<'a>(&mut self, id: &str, table: TableBuilder<'a>) -> TableBuilder<'a> {
+        match self.restored_scroll_offsets.remove(id) {
+            Some(offset) => table.vertical_scroll_offset(offset),
+            None => table
+        }
+    }
+
+    pub(super) fn remember_scroll_offset(&mut self, id: &str, output: &ScrollAreaOutput<()>) {
+        self.scroll_offsets.insert(id.to_owned(), output.state.offset.y);
+    }
+
+    /// Waits for a registration started otherwise, e.g. by the local API, to be written.
+    pub fn finish_writing_blocking(&mut self) {
+        finish_writing(self);
     }
 
     fn show_adding(&mut self, ui: &mut Ui) {
@@ -162,19 +886,19 @@ impl EMelderApp {
             ui.text_edit_singleline(&mut self.adding.sur_name);
         });
         ui.horizontal(|ui| {
-            egui::ComboBox::from_label(translate!("add.belt", &self.translations))
-            .selected_text(translate!(&format!("add.belt.{}", self.adding.belt.serialise()), &self.translations))
-            .show_ui(ui, |ui| {
-                for belt in [Belt::Kyu9, Belt::Kyu8, Belt::Kyu7, Belt::Kyu6, Belt::Kyu5, Belt::Kyu4, Belt::Kyu3, Belt::Kyu2, Belt::Kyu1,
-                Belt::Dan1, Belt::Dan2, Belt::Dan3, Belt::Dan4, Belt::Dan5, Belt::Dan6, Belt::Dan7, Belt::Dan8, Belt::Dan9, Belt::Dan10] {
-                    ui.selectable_value(&mut self.adding.belt, belt,
-                        translate!(&format!("add.belt.{}", belt.serialise()), &self.translations));
-                }
-            });
+            if belt_select(ui, "add", &mut self.adding.belt, &self.translations).changed() {
+                self.adding.belt_chosen = true;
+            }
+            ui.label(translate!("add.belt", &self.translations));
         });
         ui.horizontal(|ui| {
             ui.label(translate!("add.year", &self.translations));
-            ui.add(egui::DragValue::new(&mut self.adding.year).range(LOWER_BOUND_BIRTH_YEAR..=UPPER_BOUND_BIRTH_YEAR));
+            if ui.add(egui::DragValue::new(&mut self.adding.year).range(LOWER_BOUND_BIRTH_YEAR..=UPPER_BOUND_BIRTH_YEAR)).changed()
+                && !self.adding.belt_chosen {
+                if let Some(belt) = self.config.belt_suggestion.suggest(self.adding.year) {
+                    self.adding.belt = belt;
+                }
+            }
         });
         ui.horizontal(|ui| {
             egui::ComboBox::from_label(translate!("add.gender", &self.translations))
@@ -186,21 +910,26 @@ impl EMelderApp {
                 }
             })
         });
+        ui.horizontal(|ui| {
+            ui.label(translate!("add.group", &self.translations));
+            ui.text_edit_singleline(&mut self.adding.group);
+        });
 
         if ui.button(translate!("add.commit", &self.translations)).clicked() {
-            self.athletes.push(Athlete::new(
+            let mut athlete = Athlete::new(
                 self.adding.given_name.clone(), self.adding.sur_name.clone(),
                 self.adding.year, self.adding.belt, WeightCategory::default(), self.adding.gender
-            ));
+            );
+            athlete.get_group_mut().clone_from(&self.adding.group);
             self.adding.clear(&self.config);
-            match write_athletes(&self.config.athletes_file, &self.athletes) {
-                Ok(()) => {},
-                Err(err) => {
-                    log::error!("failed to write athletes, due to {err}");
-                    crash();
-                }
-            }
+            self.add_athlete(athlete);
+        }
+
+        ui.separator();
+        if ui.add_enabled(self.paste_import.is_none(), egui::Button::new(translate!("add.paste", &self.translations))).clicked() {
+            self.paste_import = Some(PasteImport::default());
         }
+        show_paste_import(self, ui);
     }
 
     #[allow(clippy::too_many_lines)]
@@ -212,13 +941,26 @@ impl EMelderApp {
             return;
         }
 
+        if ui.button(translate!("edit_athlete.weight_categories", &self.translations)).clicked() {
+            self.new_weight_categories.clear();
+            self.weight_categories_invalid = false;
+            self.mode = Mode::WeightCategories;
+        }
+        ui.checkbox(&mut self.referees_only, translate!("edit_athlete.referees_only", &self.translations));
 
         let mut to_graduate = None;
         let mut gender_to_change = None;
+        let mut belt_to_change = None;
+        let mut group_changed = false;
+        let mut license_changed = false;
+        let mut documents_changed = false;
+        let today = Local::now().date_naive();
+        let row_height = self.row_height();
         let table = TableBuilder::new(ui)
-            .columns(Column::auto().at_least(100.0), 5).column(Column::auto().at_least(50.0));
+            .columns(Column::auto().at_least(100.0), 8).column(Column::auto().at_least(50.0));
+        let table = self.restore_scroll_offset("edit_athlete.table", table);
 
-        table.header(20.0, |mut header| {
+        let output = table.header(20.0, |mut header| {
             header.col(|ui| {
                 ui.strong(translate!("edit_athlete.given_name", &self.translations));
             });
@@ -234,10 +976,22 @@ impl EMelderApp {
             header.col(|ui| {
                 ui.strong(translate!("edit_athlete.belt", &self.translations));
             });
+            header.col(|ui| {
+                ui.strong(translate!("edit_athlete.group", &self.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("edit_athlete.referee_license", &self.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("edit_athlete.documents", &self.translations));
+            });
             header.col(|_ui| {});
         }).body(|mut body| {
-            for (index, athlete) in self.athletes.iter().enumerate() {
-                body.row(18.0, |mut row| {
+            for (index, athlete) in self.athletes.iter_mut().enumerate() {
+                if self.referees_only && athlete.get_referee_license().is_none() {
+                    continue;
+                }
+                body.row(row_height, |mut row| {
                     row.col(|ui| {
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
                         ui.label(athlete.get_given_name());
@@ -250,7 +1004,7 @@ impl EMelderApp {
                         ui.label(athlete.get_birth_year().to_string());
                     });
                     row.col(|ui| {
-                        egui::ComboBox::from_label(translate!("edit_athlete.table.gender", &self.translations))
+                        egui::ComboBox::new(athlete.get_id(), translate!("edit_athlete.table.gender", &self.translations))
                         .selected_text(translate!(&format!("register.table.gender_category.{}", athlete.get_gender().render()), &self.translations))
                         .show_ui(ui, |ui| {
                             let mut current_gender = athlete.get_gender();
@@ -259,45 +1013,97 @@ impl EMelderApp {
                                 translate!(&format!("register.table.gender_category.{}", gender.render()), &self.translations));
                             }
                             if athlete.get_gender() != current_gender {
-                                gender_to_change = Some((index, current_gender));
+                                gender_to_change = Some((athlete.get_id(), current_gender));
                             }
                         });
                     });
+                    row.col(|ui| {
+                        let mut belt = *athlete.get_belt();
+                        if belt_select(ui, index, &mut belt, &self.translations).changed() {
+                            belt_to_change = Some((athlete.get_id(), belt));
+                        }
+                    });
+                    row.col(|ui| {
+                        if ui.text_edit_singleline(athlete.get_group_mut()).lost_focus() {
+                            group_changed = true;
+                        }
+                    });
                     row.col(|ui| {
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
-                        ui.label(translate!(&format!("add.belt.{}", athlete.get_belt().serialise()), &self.translations));
+                        let id = athlete.get_id().to_string();
+                        let license = athlete.get_referee_license_mut();
+                        let Some(referee_license) = license else {
+                            if ui.button(translate!("edit_athlete.referee_license.add", &self.translations)).clicked() {
+                                // licenses are usually issued for one year
+                                *license = Some(RefereeLicense::new(String::new(),
+                                    today.checked_add_months(Months::new(12)).unwrap_or(today)));
+                                license_changed = true;
+                            }
+                            return;
+                        };
+                        let mut remove = false;
+                        ui.horizontal(|ui| {
+                            ui.label(translate!("edit_athlete.referee_license.level", &self.translations));
+                            if ui.add(egui::TextEdit::singleline(referee_license.get_level_mut()).desired_width(60.0)).lost_focus() {
+                                license_changed = true;
+                            }
+                            ui.label(translate!("edit_athlete.referee_license.expiry", &self.translations));
+                            if ui.add(egui_extras::DatePickerButton::new(referee_license.get_expiry_mut()).id_salt(&id)
+                                .format("%d.%m.%Y")).changed() {
+                                license_changed = true;
+                            }
+                            if !referee_license.is_valid_on(today) {
+                                error_label(ui, translate!("edit_athlete.referee_license.expired", &self.translations));
+                            }
+                            if ui.small_button("✖").on_hover_text(translate!("edit_athlete.referee_license.remove", &self.translations))
+                                .clicked() {
+                                remove = true;
+                            }
+                        });
+                        if remove {
+                            *license = None;
+                            license_changed = true;
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        let id = athlete.get_id().to_string();
+                        ui.horizontal(|ui| {
+                            for document in Document::ALL {
+                                if show_document_expiry(ui, &id, document, athlete.get_document_expiry_mut(document), today,
+                                    &self.translations) {
+                                    documents_changed = true;
+                                }
+                            }
+                        });
                     });
                     row.col(|ui| {
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
                         if ui.button(translate!("edit_athlete.graduate", &self.translations)).clicked() {
-                            to_graduate = Some(index);
+                            to_graduate = Some(athlete.get_id());
                         }
                     });
                 });
             }
         });
+        self.remember_scroll_offset("edit_athlete.table", &output);
 
-        if let Some(index) = to_graduate {
-            let belt = self.athletes[index].get_belt();
-            *self.athletes[index].get_belt_mut() = belt.inc();
-            #[allow(clippy::single_match_else)]
-            match write_athletes(&self.config.athletes_file, &self.athletes) {
-                Ok(()) => {},
-                Err(err) => {
-                    log::error!("failed to write athletes, due to {err}");
-                    crash();
-                }
-            }
+        if let Some(athlete) = to_graduate.and_then(|id| self.athlete_mut(id)) {
+            let belt = athlete.get_belt();
+            *athlete.get_belt_mut() = belt.inc();
+            self.save_athletes();
         }
-        if let Some((index, new_gender)) = gender_to_change {
-            *self.athletes[index].get_gender_mut() = new_gender;
-            match write_athletes(&self.config.athletes_file, &self.athletes) {
-                Ok(()) => {},
-                Err(err) => {
-                    log::error!("failed to write athhletes, due to {err}");
-                    crash();
-                }
-            }
+        if let Some((athlete, new_belt)) = belt_to_change.and_then(|(id, new_belt)| Some((self.athlete_mut(id)?, new_belt))) {
+            *athlete.get_belt_mut() = new_belt;
+            self.save_athletes();
+        }
+        if let Some((athlete, new_gender)) = gender_to_change
+            .and_then(|(id, new_gender)| Some((self.athlete_mut(id)?, new_gender))) {
+            *athlete.get_gender_mut() = new_gender;
+            self.save_athletes();
+        }
+        if group_changed || license_changed || documents_changed {
+            self.save_athletes();
         }
     }
 
@@ -337,26 +1143,38 @@ impl EMelderApp {
         ui.horizontal(|ui| {
             ui.label(translate!("edit.private", &self.translations));
             ui.text_edit_singleline(self.club.get_sender_mut().get_private_phone_mut());
+            show_phone_preview(ui, self.club.get_sender_mut().get_private_phone_mut(), &self.translations);
         });
 
         ui.horizontal(|ui| {
             ui.label(translate!("edit.public", &self.translations));
             ui.text_edit_singleline(self.club.get_sender_mut().get_public_phone_mut());
+            show_phone_preview(ui, self.club.get_sender_mut().get_public_phone_mut(), &self.translations);
         });
 
         ui.horizontal(|ui| {
             ui.label(translate!("edit.fax", &self.translations));
             ui.text_edit_singleline(self.club.get_sender_mut().get_fax_mut());
+            show_phone_preview(ui, self.club.get_sender_mut().get_fax_mut(), &self.translations);
         });
 
         ui.horizontal(|ui| {
             ui.label(translate!("edit.mobile", &self.translations));
             ui.text_edit_singleline(self.club.get_sender_mut().get_mobile_mut());
+            show_phone_preview(ui, self.club.get_sender_mut().get_mobile_mut(), &self.translations);
         });
 
         ui.horizontal(|ui| {
             ui.label(translate!("edit.mail", &self.translations));
             ui.text_edit_singleline(self.club.get_sender_mut().get_mail_mut());
+            let mail = self.club.get_sender_mut().get_mail_mut();
+            let valid = is_valid_email(mail);
+            if ui.add_enabled(valid, egui::Button::new(translate!("edit.mail.test", &self.translations))).clicked() {
+                let _ = open::that_detached(format!("mailto:{mail}"));
+            }
+            if !valid && !mail.is_empty() {
+                ui.label(translate!("edit.mail.invalid", &self.translations));
+            }
         });
 
         ui.horizontal(|ui| {
@@ -393,15 +1211,88 @@ impl EMelderApp {
             ui.text_edit_singleline(self.club.get_nation_mut());
         });
 
-        if ui.button(translate!("edit.save", &self.translations)).clicked() {
-            match write_club(&self.config.club_file, &self.club) {
-                Ok(()) => {},
-                Err(err) => {
-                    log::error!("failed to write club, due to {err}");
-                    crash();
-                }
+        ui.separator();
+        ui.strong(translate!("edit.departments", &self.translations));
+        let mut to_delete = None;
+        for (index, department) in self.club.get_departments_mut().iter_mut().enumerate() {
+            ui.push_id(("edit.departments", index), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(translate!("edit.departments.name", &self.translations));
+                    ui.text_edit_singleline(department.get_name_mut());
+                    ui.label(translate!("edit.club_number", &self.translations));
+                    ui.add(egui::DragValue::new(department.get_number_mut())
+                        .range(0..=9_999_999)
+                        .custom_formatter(|n, _| {
+                            format!("{n:07}")
+                        }));
+                    ui.label(translate!("edit.group", &self.translations));
+                    ui.text_edit_singleline(department.get_group_mut());
+                    if ui.button(translate!("edit.departments.delete", &self.translations)).clicked() {
+                        to_delete = Some(index);
+                    }
+                });
+            });
+        }
+        if let Some(index) = to_delete.filter(|index| *index < self.club.get_departments().len()) {
+            self.club.get_departments_mut().remove(index);
+            self.registering.department = None;
+        }
+        if ui.button(translate!("edit.departments.add", &self.translations)).clicked() {
+            self.club.get_departments_mut().push(Department::default());
+        }
+        ui.separator();
+        ui.strong(translate!("edit.directory", &self.translations));
+        ui.horizontal(|ui| {
+            ui.label(translate!("edit.directory.search", &self.translations));
+            ui.text_edit_singleline(&mut self.directory_search);
+        });
+        let matching: Vec<usize> = self.club.get_directory().iter().enumerate()
+            .filter(|(_, directory_club)| directory_club.matches(&self.directory_search)).map(|(index, _)| index).collect();
+        let mut to_delete = None;
+        for index in matching {
+            let Some(directory_club) = self.club.get_directory_mut().get_mut(index) else {
+                continue;
+            };
+            ui.push_id(("edit.directory", index), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(translate!("edit.directory.name", &self.translations));
+                    ui.text_edit_singleline(directory_club.get_name_mut());
+                    ui.label(translate!("edit.club_number", &self.translations));
+                    ui.add(egui::DragValue::new(directory_club.get_number_mut())
+                        .range(0..=9_999_999)
+                        .custom_formatter(|n, _| {
+                            format!("{n:07}")
+                        }));
+                    ui.label(translate!("edit.directory.town", &self.translations));
+                    ui.text_edit_singleline(directory_club.get_town_mut());
+                    if ui.button(translate!("edit.directory.delete", &self.translations)).clicked() {
+                        to_delete = Some(index);
+                    }
+                });
+            });
+        }
+        if let Some(index) = to_delete.filter(|index| *index < self.club.get_directory().len()) {
+            self.club.get_directory_mut().remove(index);
+        }
+        if ui.button(translate!("edit.directory.add", &self.translations)).clicked() {
+            self.club.get_directory_mut().push(DirectoryClub::default());
+            self.directory_search.clear();
+        }
+        ui.separator();
+
+        if ui.button(translate!("edit.save", &self.translations))
+            .on_hover_text(shortcut_hint(self, ui, "shortcut.save", &SAVE)).clicked() {
+            self.save_edited_club();
+        }
+    }
+
+    fn save_edited_club(&mut self) {
+        for phone in self.club.get_sender_mut().get_phones_mut() {
+            if let Some(normalized) = normalize_phone(phone) {
+                *phone = normalized;
             }
         }
+        self.save_club();
     }
 
     #[allow(clippy::too_many_lines)]
@@ -412,29 +1303,26 @@ impl EMelderApp {
         }
 
         let mut to_delete = None;
+        let mut sorted = false;
+        let mut shown: Vec<usize> = (0..self.athletes.len()).collect();
+        self.config.sorting.delete.sort(&self.athletes, &mut shown);
+        let row_height = self.row_height();
         let table = TableBuilder::new(ui).columns(Column::auto().at_least(100.0), 5)
             .column(Column::auto().at_least(50.0));
+        let table = self.restore_scroll_offset("delete.table", table);
 
-        table.header(20.0, |mut header| {
-            header.col(|ui| {
-                ui.strong(translate!("delete.given_name", &self.translations));
-            });
-            header.col(|ui| {
-                ui.strong(translate!("delete.sur_name", &self.translations));
-            });
-            header.col(|ui| {
-                ui.strong(translate!("delete.year", &self.translations));
-            });
-            header.col(|ui| {
-                ui.strong(translate!("delete.gender", &self.translations));
-            });
-            header.col(|ui| {
-                ui.strong(translate!("delete.belt", &self.translations));
-            });
+        let sorting = &mut self.config.sorting.delete;
+        let output = table.header(20.0, |mut header| {
+            for (key, column) in [("delete.given_name", SortColumn::GivenName), ("delete.sur_name", SortColumn::SurName),
+                ("delete.year", SortColumn::BirthYear), ("delete.gender", SortColumn::Gender), ("delete.belt", SortColumn::Belt)] {
+                header.col(|ui| {
+                    sorted |= sortable_header(ui, translate!(key, &self.translations), column, sorting);
+                });
+            }
             header.col(|_ui| {});
         }).body(|mut body| {
-            for (index, athlete) in self.athletes.iter().enumerate() {
-                body.row(18.0, |mut row| {
+            for athlete in shown.iter().map(|index| &self.athletes[*index]) {
+                body.row(row_height, |mut row| {
                     row.col(|ui| {
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
                         ui.label(athlete.get_given_name());
@@ -456,27 +1344,134 @@ impl EMelderApp {
                     row.col(|ui| {
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
                         if ui.button(translate!("delete.delete", &self.translations)).clicked() {
-                            to_delete = Some(index);
+                            to_delete = Some(athlete.get_id());
                         }
                     });
                 });
             }
         });
+        self.remember_scroll_offset("delete.table", &output);
 
-        if let Some(index) = to_delete {
+        if let Some(index) = to_delete.and_then(|id| self.athletes.iter().position(|athlete| athlete.get_id() == id)) {
             self.athletes.remove(index);
-            match write_athletes(&self.config.athletes_file, &self.athletes) {
-                Ok(()) => {},
-                Err(err) => {
-                    log::error!("failed to write athletes, due to {err}");
-                    crash();
-                }
-            }
+            self.save_athletes();
+        }
+        if sorted {
+            self.save_configs();
         }
     }
 
     #[allow(clippy::too_many_lines)]
+    fn show_age_categories_config(&mut self, ui: &mut Ui) {
+        ui.label(translate!("config.age_categories", &self.translations));
+        let mut to_delete = None;
+        for (index, age_category) in self.config.age_categories.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(age_category).desired_width(120.0));
+                if ui.button("🗑").clicked() {
+                    to_delete = Some(index);
+                }
+            });
+        }
+        if let Some(index) = to_delete {
+            self.config.age_categories.remove(index);
+        }
+        if ui.button(translate!("config.age_categories.add", &self.translations)).clicked() {
+            self.config.age_categories.push(String::new());
+        }
+
+        ui.label(translate!("config.age_category_hints", &self.translations));
+        let mut to_delete = None;
+        for (index, hint) in self.config.age_category_hints.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut hint.birth_year).range(LOWER_BOUND_BIRTH_YEAR..=UPPER_BOUND_BIRTH_YEAR));
+                ui.label("→");
+                ui.add(egui::TextEdit::singleline(&mut hint.age_category).desired_width(120.0));
+                if ui.button("🗑").clicked() {
+                    to_delete = Some(index);
+                }
+            });
+        }
+        if let Some(index) = to_delete {
+            self.config.age_category_hints.remove(index);
+        }
+        if ui.button(translate!("config.age_category_hints.add", &self.translations)).clicked() {
+            // e.g. the next younger year after the last one
+            let birth_year = self.config.age_category_hints.last().map_or(DEFAULT_BIRTH_YEAR, |hint| hint.birth_year + 1)
+                .min(UPPER_BOUND_BIRTH_YEAR);
+            self.config.age_category_hints.push(AgeCategoryHint { birth_year, age_category: String::new() });
+        }
+    }
+
+    fn show_fees_config(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(translate!("config.fees", &self.translations));
+            ui.add(fee_value(&mut self.config.fees.per_athlete));
+        });
+        let mut to_delete = None;
+        for (index, fee) in self.config.fees.per_age_category.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut fee.age_category).desired_width(120.0));
+                ui.label("→");
+                ui.add(fee_value(&mut fee.fee));
+                if ui.button("🗑").clicked() {
+                    to_delete = Some(index);
+                }
+            });
+        }
+        if let Some(index) = to_delete {
+            self.config.fees.per_age_category.remove(index);
+        }
+        if ui.button(translate!("config.fees.add", &self.translations)).clicked() {
+            let fee = self.config.fees.per_athlete;
+            self.config.fees.per_age_category.push(AgeCategoryFee { age_category: String::new(), fee });
+        }
+    }
+
+    fn show_belt_suggestion_config(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.config.belt_suggestion.enabled, translate!("config.belt_suggestion", &self.translations));
+        if !self.config.belt_suggestion.enabled {
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.label(translate!("config.belt_suggestion.first_exam_age", &self.translations));
+            ui.add(egui::DragValue::new(&mut self.config.belt_suggestion.first_exam_age).range(4..=14));
+        });
+        ui.horizontal(|ui| {
+            ui.label(translate!("config.belt_suggestion.years_per_exam", &self.translations));
+            ui.add(egui::DragValue::new(&mut self.config.belt_suggestion.years_per_exam).range(1..=3));
+        });
+    }
+
+    fn show_additional_exports_config(&mut self, ui: &mut Ui) {
+        ui.label(translate!("config.additional_exports", &self.translations));
+        ui.checkbox(&mut self.config.entry_list_pdf, translate!("register.entry_list_pdf", &self.translations));
+        ui.checkbox(&mut self.config.entry_list_csv, translate!("config.additional_exports.csv", &self.translations));
+        ui.horizontal(|ui| {
+            let mut calendar = self.config.calendar_file.is_some();
+            if ui.checkbox(&mut calendar, translate!("config.additional_exports.calendar", &self.translations))
+                .on_hover_text(translate!("config.additional_exports.calendar.hover", &self.translations)).changed() {
+                self.config.calendar_file = if calendar { pick_calendar_file() } else { None };
+            }
+            if let Some(calendar_file) = &self.config.calendar_file {
+                if ui.button(calendar_file.display().to_string()).clicked() {
+                    if let Some(path) = pick_calendar_file() {
+                        self.config.calendar_file = Some(path);
+                    }
+                }
+            }
+        });
+    }
+
     fn show_config(&mut self, ui: &mut Ui) {
+        if self.config.langs.is_empty() {
+            // the languages are only discovered when needed, to keep the startup fast
+            self.config.langs = get_languages().unwrap_or_else(|err| {
+                log::warn!("failed to get languages, due to {err}");
+                vec![self.config.lang.clone()]
+            });
+        }
+
         ui.horizontal(|ui| {
             egui::ComboBox::from_label(translate!("config.lang", &self.translations))
             .selected_text(*LANG_NAMES.get(self.config.lang.as_str()).unwrap_or(&self.config.lang.as_str()))
@@ -488,7 +1483,18 @@ impl EMelderApp {
             });
         });
         
-        ui.checkbox(&mut self.config.dark_mode, translate!("config.dark_mode", &self.translations));
+        let mut theme = self.config.theme();
+        egui::ComboBox::from_label(translate!("config.theme", &self.translations))
+        .selected_text(translate!(theme.translation_key(), &self.translations))
+        .show_ui(ui, |ui| {
+            for option in Theme::ALL {
+                ui.selectable_value(&mut theme, option, translate!(option.translation_key(), &self.translations));
+            }
+        });
+        if theme != self.config.theme() {
+            self.config.set_theme(theme);
+        }
+        ui.checkbox(&mut self.config.touch_mode, translate!("config.touch_mode", &self.translations));
 
         ui.horizontal(|ui| {
             ui.label(translate!("config.select_athletes_file", &self.translations));
@@ -502,6 +1508,13 @@ impl EMelderApp {
                         None => {}
                     }
             }
+            egui::ComboBox::from_id_salt("config.recent_athletes_files")
+            .selected_text(translate!("config.recent_files", &self.translations))
+            .show_ui(ui, |ui| {
+                for recent_file in &self.config.recent_athletes_files {
+                    ui.selectable_value(&mut self.config.athletes_file, recent_file.clone(), recent_file.display().to_string());
+                }
+            });
         });
 
         ui.horizontal(|ui| {
@@ -516,8 +1529,50 @@ impl EMelderApp {
                         None => {}
                     }
             }
+            egui::ComboBox::from_id_salt("config.recent_club_files")
+            .selected_text(translate!("config.recent_files", &self.translations))
+            .show_ui(ui, |ui| {
+                for recent_file in &self.config.recent_club_files {
+                    ui.selectable_value(&mut self.config.club_file, recent_file.clone(), recent_file.display().to_string());
+                }
+            });
         });
 
+        ui.horizontal(|ui| {
+            ui.label(translate!("config.select_history_file", &self.translations));
+            if ui.button(self.config.history_file.display().to_string()).clicked() {
+                #[allow(clippy::single_match)]
+                match rfd::FileDialog::new().set_can_create_directories(true)
+                    .set_title(translate!("config.history_file.file_picker", &self.translations)).save_file() {
+                        Some(history_file) => {
+                            self.config.history_file = history_file;
+                        }
+                        None => {}
+                    }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(translate!("config.file_name_template", &self.translations));
+            ui.text_edit_singleline(&mut self.config.file_name_template)
+                .on_hover_text(translate!("config.file_name_template.hover", &self.translations));
+            if ui.add_enabled(self.config.file_name_template != DEFAULT_FILE_NAME_TEMPLATE,
+                egui::Button::new(translate!("config.file_name_template.reset", &self.translations))).clicked() {
+                self.config.file_name_template = default_file_name_template();
+            }
+        });
+        egui::ComboBox::from_label(translate!("config.tournament_encoding", &self.translations))
+        .selected_text(self.config.tournament_encoding.name())
+        .show_ui(ui, |ui| {
+            for encoding in TournamentEncoding::ALL {
+                ui.selectable_value(&mut self.config.tournament_encoding, encoding, encoding.name());
+            }
+        }).response.on_hover_text(translate!("config.tournament_encoding.hover", &self.translations));
+        // files of different groups must not get the same name
+        if !self.config.file_name_template.contains("{age}") || !self.config.file_name_template.contains("{gender}") {
+            warning_label(ui, translate!("config.file_name_template.ambiguous", &self.translations));
+        }
+
         ui.horizontal(|ui| {
             ui.label(translate!("config.select_tournament_basedir", &self.translations));
             if ui.button(self.config.tournament_basedir.display().to_string()).clicked() {
@@ -534,6 +1589,26 @@ impl EMelderApp {
             }
         });
 
+        ui.horizontal(|ui| {
+            ui.label(translate!("config.shared_drafts_dir", &self.translations));
+            let shared_drafts_dir = self.config.shared_drafts_dir.as_ref().map_or_else(
+                || translate!("config.shared_drafts_dir.none", &self.translations), |directory| directory.display().to_string());
+            if ui.button(shared_drafts_dir).clicked() {
+                if let Some(directory) = rfd::FileDialog::new().set_can_create_directories(true)
+                    .set_title(translate!("config.shared_drafts_dir.file_picker", &self.translations)).pick_folder() {
+                    self.config.shared_drafts_dir = Some(directory);
+                }
+            }
+            if self.config.shared_drafts_dir.is_some() && ui.button("✖").clicked() {
+                self.config.shared_drafts_dir = None;
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(translate!("config.draft_user", &self.translations));
+            ui.text_edit_singleline(&mut self.config.draft_user);
+        });
+
         egui::ComboBox::from_label(translate!("config.default_gender_category", &self.translations))
         .selected_text(translate!(&format!("register.table.gender_category.{}", self.config.default_gender_category.render()),
         &self.translations))
@@ -544,25 +1619,208 @@ impl EMelderApp {
             }
         });
 
-        if ui.button(translate!("config.save", &self.translations)).clicked() {
-            match write_configs(&self.config) {
-                Ok(()) => {
-                    self.translations.clear();
-                    self.translations = match get_translations(&self.config.lang) {
-                        Ok(translations) => translations,
-                        Err(err) => {
-                            log::warn!("failed to obtain translations, due to {err}");
-                            HashMap::new()
-                        }
-                    }
-                },
-                Err(err) => {
-                    log::warn!("failed to write configs, due to {err}");
+        egui::ComboBox::from_label(translate!("config.open_folder", &self.translations))
+        .selected_text(translate!(self.config.open_folder.translation_key(), &self.translations))
+        .show_ui(ui, |ui| {
+            for open_folder in OpenFolder::ALL {
+                ui.selectable_value(&mut self.config.open_folder, open_folder,
+                    translate!(open_folder.translation_key(), &self.translations));
+            }
+        });
+
+        egui::ComboBox::from_label(translate!("config.gender_abbreviations", &self.translations))
+        .selected_text(self.config.gender_abbreviations.name())
+        .show_ui(ui, |ui| {
+            for gender_abbreviations in GenderAbbreviations::ALL {
+                ui.selectable_value(&mut self.config.gender_abbreviations, gender_abbreviations, gender_abbreviations.name());
+            }
+        });
+
+        ui.separator();
+        show_csv_export_config(self, ui);
+        ui.separator();
+        self.show_additional_exports_config(ui);
+        ui.separator();
+        show_email_config(self, ui);
+        ui.separator();
+        show_api_config(self, ui);
+        ui.separator();
+        show_schedule_config(self, ui);
+        ui.separator();
+        show_auto_lock_config(self, ui);
+        ui.separator();
+        // the associations and shortcuts are written to the registry of Windows or the data-directory of the desktop
+        if cfg!(any(windows, target_os = "linux")) {
+            show_desktop_integration_config(self, ui);
+            ui.separator();
+        }
+        self.show_belt_suggestion_config(ui);
+        ui.separator();
+        self.show_age_categories_config(ui);
+        ui.separator();
+        self.show_fees_config(ui);
+        ui.separator();
+        show_restore(self, ui);
+        ui.separator();
+
+        ui.label(translate!("config.nav_pages", &self.translations));
+        let mut to_move_up = None;
+        let nav_pages_len = self.config.nav_pages.len();
+        for (index, page) in self.config.nav_pages.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add_enabled(page.mode != Mode::Config, egui::Checkbox::new(&mut page.visible,
+                    translate!(page.mode.translation_key(), &self.translations)));
+                if ui.add_enabled(index > 0, egui::Button::new("⬆")).clicked() {
+                    to_move_up = Some(index);
+                }
+                if ui.add_enabled(index + 1 < nav_pages_len, egui::Button::new("⬇")).clicked() {
+                    to_move_up = Some(index + 1);
                 }
+            });
+        }
+        if let Some(index) = to_move_up {
+            self.config.nav_pages.swap(index - 1, index);
+        }
+
+        ui.checkbox(&mut self.config.role_selection, translate!("config.role_selection", &self.translations));
+
+        ui.horizontal(|ui| {
+            ui.label(translate!("config.admin_pin", &self.translations));
+            show_pin_edit(ui, &mut self.admin_pin_edit, &mut self.config.admin_pin, &self.translations);
+        });
+
+        if ui.button(translate!("config.save", &self.translations))
+            .on_hover_text(shortcut_hint(self, ui, "shortcut.save", &SAVE)).clicked() {
+            self.save_edited_configs(ui.ctx());
+        }
+    }
+
+    fn save_edited_configs(&mut self, ctx: &egui::Context) {
+        push_recent_file(&mut self.config.recent_athletes_files, &self.config.athletes_file);
+        push_recent_file(&mut self.config.recent_club_files, &self.config.club_file);
+        if self.config.lang != self.active_lang {
+            self.config.previous_lang = Some(self.active_lang.clone());
+        }
+        if self.save_configs() {
+            ctx.set_visuals(self.config.theme().visuals());
+            apply_touch_mode(ctx, self.config.touch_mode);
+            // the files might have been switched
+            self.athletes = load_athletes(&self.config.athletes_file);
+            self.club = load_club(&self.config.club_file);
+            self.active_lang = self.config.lang.clone();
+            self.load_translations();
+        }
+    }
+
+    fn load_translations(&mut self) {
+        self.translations = match get_translations(&self.config.lang) {
+            Ok(translations) => Arc::new(translations),
+            Err(err) => {
+                log::warn!("failed to obtain translations, due to {err}");
+                Arc::default()
             }
         }
     }
 
+    /// Switches to the language used before the current one, e.g. when handing the laptop to an assistant.
+    pub fn toggle_language(&mut self) {
+        let Some(previous_lang) = self.config.previous_lang.take() else {
+            return;
+        };
+        self.config.previous_lang = Some(std::mem::replace(&mut self.config.lang, previous_lang));
+        self.active_lang = self.config.lang.clone();
+        self.save_configs();
+        self.load_translations();
+    }
+
+    fn show_language_toggle(&mut self, ui: &mut Ui) {
+        let Some(previous_lang) = self.config.previous_lang.as_deref().filter(|lang| *lang != self.config.lang) else {
+            return;
+        };
+        ui.separator();
+        let name = *LANG_NAMES.get(previous_lang).unwrap_or(&previous_lang);
+        if ui.button(format!("🌐 {name}")).on_hover_text(translate!("config.lang_toggle", &self.translations)).clicked() {
+            self.toggle_language();
+        }
+    }
+
+    fn show_role_selection(&mut self, ui: &mut Ui) {
+        ui.heading(translate!("role.select", &self.translations));
+        ui.separator();
+
+        if ui.button(translate!("role.coach", &self.translations)).clicked() {
+            self.role = Some(Role::Coach);
+        }
+
+        ui.horizontal(|ui| {
+            if !self.config.admin_pin.is_empty() {
+                ui.label(translate!("role.pin", &self.translations));
+                ui.add(egui::TextEdit::singleline(&mut self.pin_input).password(true));
+            }
+            if ui.button(translate!("role.admin", &self.translations)).clicked() {
+                if self.config.admin_pin.verify(&self.pin_input) {
+                    self.role = Some(Role::Admin);
+                    self.pin_wrong = false;
+                }
+                else {
+                    self.pin_wrong = true;
+                }
+                self.pin_input.clear();
+            }
+        });
+
+        if self.pin_wrong {
+            ui.label(translate!("role.pin_wrong", &self.translations));
+        }
+    }
+
+    pub(super) fn row_height(&self) -> f32 {
+        if self.config.touch_mode { 40.0 } else { 18.0 }
+    }
+
+    pub(super) fn is_admin(&self) -> bool {
+        self.role == Some(Role::Admin)
+    }
+
+    /// The shortcuts work on every page, so they are handled before any page is shown.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if pressed(ctx, &REGISTER) && self.is_mode_allowed(Mode::Registering) && can_register(self) {
+            // the checks before writing the files ask on the registering-page
+            self.mode = Mode::Registering;
+            start_registering(self);
+        }
+        if pressed(ctx, &SAVE) {
+            match self.mode {
+                Mode::Registering => self.persist_draft(),
+                Mode::WeightCategories => save_weight_categories(self),
+                Mode::History => self.save_history(),
+                Mode::EditClub => self.save_edited_club(),
+                Mode::Config => self.save_edited_configs(ctx),
+                Mode::Exam | Mode::Adding | Mode::EditAthlete | Mode::Deleting | Mode::Merge | Mode::Roster | Mode::Seasons
+                    | Mode::Generated | Mode::About => { self.save_athletes(); }
+            }
+        }
+    }
+
+    fn is_mode_allowed(&self, mode: Mode) -> bool {
+        // a restored session must not bypass the restrictions of the kiosk mode or the role
+        match mode {
+            Mode::EditAthlete | Mode::WeightCategories | Mode::Merge => !self.read_only,
+            Mode::Deleting | Mode::EditClub | Mode::Config => !self.read_only && self.is_admin(),
+            Mode::Registering | Mode::Exam | Mode::Adding | Mode::Roster | Mode::Seasons | Mode::History | Mode::Generated | Mode::About => true
+        }
+    }
+
+    fn current_window_title(&self) -> String {
+        let title = translate!("application.title", &self.translations);
+        match self.mode {
+            Mode::Registering if !self.registering.name.is_empty() => {
+                format!("{title} – {} {}", self.registering.name, self.registering.date.format("%d.%m.%Y"))
+            }
+            mode => format!("{title} – {}", translate!(mode.translation_key(), &self.translations))
+        }
+    }
+
     fn show_about(&mut self, ui: &mut Ui) {
         ui.label(translate!("about.about", &self.translations));
         ui.separator();
@@ -586,11 +1844,16 @@ impl EMelderApp {
             }
         });
 
+        show_demo_button(self, ui);
+        if ui.button(translate!("tour.start", &self.translations)).clicked() {
+            self.start_tour();
+            self.mode = Mode::Registering;
+        }
+
         if ui.button(translate!("about.check_update", &self.translations)).clicked() {
-            let update_available = check_update_available(VERSION);
             self.popup_open = true;
-            if let Ok(update_available) = update_available {
-                match update_available {
+            match check_update_available(VERSION) {
+                Ok(update_available) => match update_available {
                     UpdateAvailability::UpdateAvailable => {
                         self.update_check_text = Some(translate!("about.update_available", &self.translations));
                     }
@@ -600,18 +1863,41 @@ impl EMelderApp {
                     UpdateAvailability::RunningUnstable => {
                         self.update_check_text = Some(translate!("about.running_unstable", &self.translations));
                     }
+                },
+                Err(err) => {
+                    log::warn!("failed to get new version information from network, due to {err}");
+                    self.update_check_text = Some(translate!(err.translation_key(), &self.translations));
                 }
             }
-            else {
-                log::warn!("failed to get new version information from network: {}", update_available.unwrap_err()); // cannot panic, as it was checked above for `Ok`
-                self.update_check_text = Some(translate!("about.no_network", &self.translations));
-            }
         }
     }
 }
 
 impl eframe::App for EMelderApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, SESSION_KEY, &Session {
+            mode: self.mode, register_search: self.registering.search.clone(),
+            only_without_start: self.registering.only_without_start, exam_search: self.exam.search.clone(),
+            directory_search: self.directory_search.clone(),
+            // tables not shown in this session keep the offsets of the last one
+            scroll_offsets: self.restored_scroll_offsets.iter().chain(&self.scroll_offsets)
+                .map(|(id, offset)| (id.clone(), *offset)).collect()
+        });
+        self.persist_draft();
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.collect_finished_tasks();
+        self.handle_api_calls();
+        self.handle_shutdown(ctx);
+        self.run_due_jobs(ctx);
+
+        let window_title = self.current_window_title();
+        if window_title != self.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(window_title.clone()));
+            self.window_title = window_title;
+        }
+
         if !self.popup_open && self.update_check_text.is_some() {
             self.update_check_text = None;
         }
@@ -623,46 +1909,72 @@ impl eframe::App for EMelderApp {
             });
         }
 
+        if self.update_auto_lock(ctx) {
+            show_lock(self, ctx);
+            return;
+        }
+
+        if self.role.is_some() && self.update_check_text.is_none() {
+            self.handle_shortcuts(ctx);
+        }
+
+        show_demo_banner(self, ctx);
         egui::CentralPanel::default().show(ctx, |ui| {
             if self.update_check_text.is_some() {
                 ui.disable();
             }
+            if self.role.is_none() {
+                self.show_role_selection(ui);
+                return;
+            }
             egui::menu::bar(ui, |ui| {
-                if ui.button(translate!("application.register", &self.translations)).clicked() {
-                    self.mode = Mode::Registering;
-                }
-
-                if ui.button(translate!("application.add", &self.translations)).clicked() {
-                    self.mode = Mode::Adding;
-                }
-
-                if ui.button(translate!("application.edit_athlete", &self.translations)).clicked() {
-                    self.mode = Mode::EditAthlete;
+                for page in &self.config.nav_pages {
+                    // the settings cannot be hidden, as they would be unreachable otherwise
+                    if !page.visible && page.mode != Mode::Config {
+                        continue;
+                    }
+                    if ui.add_enabled(self.is_mode_allowed(page.mode),
+                        egui::Button::new(translate!(page.mode.translation_key(), &self.translations))).clicked() {
+                        self.mode = page.mode;
+                    }
                 }
 
-                if ui.button(translate!("application.delete", &self.translations)).clicked() {
-                    self.mode = Mode::Deleting;
-                }
+                self.show_language_toggle(ui);
 
-                if ui.button(translate!("application.edit", &self.translations)).clicked() {
-                    self.mode = Mode::EditClub;
+                if self.config.role_selection {
+                    ui.separator();
+                    let role = if self.is_admin() { "role.admin" } else { "role.coach" };
+                    ui.label(format!("{} {}", translate!("role.active", &self.translations), translate!(role, &self.translations)));
+                    if ui.button(translate!("role.switch", &self.translations)).clicked() {
+                        self.role = None;
+                        self.mode = Mode::default();
+                    }
                 }
+            });
 
-                if ui.button(translate!("application.config", &self.translations)).clicked() {
-                    self.mode = Mode::Config;
-                }
+            if !self.is_mode_allowed(self.mode) {
+                self.mode = Mode::default();
+            }
 
-                if ui.button(translate!("application.about", &self.translations)).clicked() {
-                    self.mode = Mode::About;
-                }
-            });
+            show_writing(self, ui);
+            show_save_error(self, ui);
+            show_aged_out(self, ui.ctx());
+            show_desktop_integration_offer(self, ui.ctx());
+            show_toasts(self, ui.ctx());
 
             match self.mode {
                 Mode::Registering => show_registering(self, ui),
+                Mode::Exam => show_exam(self, ui),
                 Mode::Adding => self.show_adding(ui),
                 Mode::EditAthlete => self.show_edit_athlete(ui),
+                Mode::WeightCategories => show_weight_categories(self, ui),
                 Mode::EditClub => self.show_edit(ui),
                 Mode::Deleting => self.show_delete(ui),
+                Mode::Merge => show_merge(self, ui),
+                Mode::Roster => show_roster(self, ui),
+                Mode::Seasons => show_seasons(self, ui),
+                Mode::History => show_history(self, ui),
+                Mode::Generated => show_generated(self, ui),
                 Mode::Config => self.show_config(ui),
                 Mode::About => self.show_about(ui)
             }
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use egui::{Id, Key, Response, Ui};
+
+use crate::tournament_info::Belt;
+use crate::utils::translate;
+
+fn matches_belt(label: &str, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    if !query.is_empty() && query.chars().all(|c| c.is_ascii_digit()) {
+        // "1" is the 1st Kyu and Dan, but not the 10th Dan
+        let digits: String = label.chars().take_while(char::is_ascii_digit).collect();
+        return digits == query;
+    }
+    label.to_lowercase().contains(&query)
+}
+
+/// A dropdown of all belts, which are filtered by typing e.g. "3" or "braun", Enter picks the first one left.
+pub(super) fn belt_select(ui: &mut Ui, id_salt: impl std::hash::Hash, belt: &mut Belt, translations: &HashMap<String, String>)
+    -> Response {
+    let id = Id::new(("belt_select", id_salt));
+    let query_id = id.with("query");
+    let mut changed = false;
+    let mut response = egui::ComboBox::from_id_salt(id)
+    .selected_text(translate!(&format!("add.belt.{}", belt.serialise()), translations))
+    .show_ui(ui, |ui| {
+        let mut query: String = ui.data(|data| data.get_temp(query_id)).unwrap_or_default();
+        let query_response = ui.add(egui::TextEdit::singleline(&mut query)
+            .hint_text(translate!("add.belt.search", translations)).id(query_id));
+        query_response.request_focus();
+        let labels: Vec<(Belt, String)> = Belt::ALL.iter()
+            .map(|belt| (*belt, translate!(&format!("add.belt.{}", belt.serialise()), translations)))
+            .filter(|(_, label)| matches_belt(label, &query)).collect();
+        if ui.input(|input| input.key_pressed(Key::Enter)) {
+            if let Some((first, _)) = labels.first() {
+                changed |= *belt != *first;
+                *belt = *first;
+            }
+            ui.memory_mut(egui::Memory::close_popup);
+        }
+        for (option, label) in labels {
+            changed |= ui.selectable_value(belt, option, label).changed();
+        }
+        ui.data_mut(|data| data.insert_temp(query_id, query));
+    });
+    // the query starts empty the next time the dropdown is opened
+    if response.inner.is_none() {
+        ui.data_mut(|data| data.remove::<String>(query_id));
+    }
+    if changed {
+        response.response.mark_changed();
+    }
+    response.response
+}
@@ -0,0 +1,143 @@
+use egui::Ui;
+
+use crate::tournament_info::{CsvColumn, CsvEncoding, CsvExport, CsvField};
+use crate::utils::{translate, write_csv_export};
+use super::EMelderApp;
+use super::theme::error_label;
+
+fn field_translation_key(field: CsvField) -> &'static str {
+    match field {
+        CsvField::GivenName => "csv_export.field.given_name",
+        CsvField::SurName => "csv_export.field.sur_name",
+        CsvField::BirthYear => "csv_export.field.year",
+        CsvField::Belt => "csv_export.field.belt",
+        CsvField::WeightCategory => "csv_export.field.weight_category",
+        CsvField::AgeCategory => "csv_export.field.age_category",
+        CsvField::Gender => "csv_export.field.gender",
+        CsvField::GenderCategory => "csv_export.field.gender_category",
+        CsvField::ClubName => "csv_export.field.club_name",
+        CsvField::ClubNumber => "csv_export.field.club_number",
+        CsvField::TournamentName => "csv_export.field.tournament_name",
+        CsvField::TournamentDate => "csv_export.field.tournament_date"
+    }
+}
+
+/// Edits the CSV-layouts of the organizers, they are saved together with the other configs.
+#[allow(clippy::too_many_lines)]
+pub fn show_csv_export_config(app: &mut EMelderApp, ui: &mut Ui) {
+    ui.label(translate!("csv_export.config", &app.translations));
+    let mut export_to_delete = None;
+    for (export_index, export) in app.config.csv_exports.iter_mut().enumerate() {
+        let title = if export.name.is_empty() { translate!("csv_export.unnamed", &app.translations) } else { export.name.clone() };
+        egui::CollapsingHeader::new(title).id_salt(("csv_export.config", export_index)).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(translate!("csv_export.name", &app.translations));
+                ui.text_edit_singleline(&mut export.name);
+            });
+            ui.horizontal(|ui| {
+                ui.label(translate!("csv_export.delimiter", &app.translations));
+                let mut delimiter = export.delimiter.to_string();
+                if ui.add(egui::TextEdit::singleline(&mut delimiter).desired_width(20.0).char_limit(1)).changed() {
+                    // the delimiter cannot be empty, so the previous one is kept
+                    if let Some(delimiter) = delimiter.chars().next() {
+                        export.delimiter = delimiter;
+                    }
+                }
+                egui::ComboBox::from_id_salt(("csv_export.encoding", export_index))
+                .selected_text(export.encoding.name())
+                .show_ui(ui, |ui| {
+                    for encoding in CsvEncoding::ALL {
+                        ui.selectable_value(&mut export.encoding, encoding, encoding.name());
+                    }
+                });
+                ui.checkbox(&mut export.header, translate!("csv_export.header", &app.translations));
+            });
+
+            let mut column_to_delete = None;
+            let mut column_to_move_up = None;
+            let columns_len = export.columns.len();
+            for (index, column) in export.columns.iter_mut().enumerate() {
+                ui.push_id(("csv_export.column", export_index, index), |ui| {
+                    ui.horizontal(|ui| {
+                        let previous_field = column.field;
+                        egui::ComboBox::from_id_salt("field")
+                        .selected_text(translate!(field_translation_key(column.field), &app.translations))
+                        .show_ui(ui, |ui| {
+                            for field in CsvField::ALL {
+                                ui.selectable_value(&mut column.field, field, translate!(field_translation_key(field), &app.translations));
+                            }
+                        });
+                        if column.field != previous_field && column.header == previous_field.default_header() {
+                            column.header = column.field.default_header().to_owned();
+                        }
+                        ui.text_edit_singleline(&mut column.header);
+                        if ui.add_enabled(index > 0, egui::Button::new("⬆")).clicked() {
+                            column_to_move_up = Some(index);
+                        }
+                        if ui.add_enabled(index + 1 < columns_len, egui::Button::new("⬇")).clicked() {
+                            column_to_move_up = Some(index + 1);
+                        }
+                        if ui.button("✖").clicked() {
+                            column_to_delete = Some(index);
+                        }
+                    });
+                });
+            }
+            if let Some(index) = column_to_move_up {
+                export.columns.swap(index - 1, index);
+            }
+            if let Some(index) = column_to_delete.filter(|index| *index < export.columns.len()) {
+                export.columns.remove(index);
+            }
+            ui.horizontal(|ui| {
+                if ui.button(translate!("csv_export.add_column", &app.translations)).clicked() {
+                    export.columns.push(CsvColumn { field: CsvField::GivenName, header: CsvField::GivenName.default_header().to_owned() });
+                }
+                if ui.button(translate!("csv_export.delete", &app.translations)).clicked() {
+                    export_to_delete = Some(export_index);
+                }
+            });
+        });
+    }
+    if let Some(index) = export_to_delete.filter(|index| *index < app.config.csv_exports.len()) {
+        app.config.csv_exports.remove(index);
+        app.registering.csv_export = 0;
+    }
+    if ui.button(translate!("csv_export.add", &app.translations)).clicked() {
+        app.config.csv_exports.push(CsvExport::default());
+    }
+}
+
+/// Exports the current registration in the CSV-layout of the selected organizer.
+pub fn show_csv_export(app: &mut EMelderApp, ui: &mut Ui) {
+    if app.config.csv_exports.is_empty() {
+        return;
+    }
+    let selected = app.registering.csv_export.min(app.config.csv_exports.len() - 1);
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_label(translate!("csv_export.organizer", &app.translations))
+        .selected_text(&app.config.csv_exports[selected].name)
+        .show_ui(ui, |ui| {
+            for (index, export) in app.config.csv_exports.iter().enumerate() {
+                ui.selectable_value(&mut app.registering.csv_export, index, &export.name);
+            }
+        });
+        if ui.add_enabled(!app.registering.athletes.is_empty(), egui::Button::new(translate!("csv_export.export", &app.translations)))
+            .clicked() {
+            let export = &app.config.csv_exports[selected];
+            let file_name = format!("{} {}.csv", app.registering.name, export.name).trim().to_owned();
+            if let Some(path) = rfd::FileDialog::new().set_can_create_directories(true).add_filter("CSV", &["csv"])
+                .set_directory(&app.config.tournament_basedir).set_file_name(file_name).save_file() {
+                let contents = export.render(&app.registering.athletes, &app.club.for_department(app.registering.department),
+                    &app.registering.name, app.registering.date);
+                app.registering.csv_export_error = write_csv_export(&path, export, &contents).err().map(|err| {
+                    log::warn!("failed to export registration as CSV, due to {err}");
+                    format!("{}: {err}", path.display())
+                });
+            }
+        }
+    });
+    if let Some(csv_export_error) = &app.registering.csv_export_error {
+        error_label(ui, format!("{} {csv_export_error}", translate!("csv_export.error", &app.translations)));
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::PathBuf;
+
+use chrono::{Datelike, Local};
+use egui::Ui;
+
+use crate::error::Error;
+use crate::tournament_info::{Athlete, Belt, Club, GeneratedFile, GenderCategory, HistoryEntry, WeightCategory};
+use crate::utils::{translate, write_athletes, write_club};
+use super::EMelderApp;
+use super::app::{Config, Registering};
+use super::theme::warning_label;
+
+/// The real data, kept while the demo-data is shown.
+#[derive(Debug)]
+pub(super) struct Demo {
+    dir: PathBuf,
+    config: Config,
+    athletes: Vec<Athlete>,
+    club: Club,
+    history: Vec<HistoryEntry>,
+    generated: Vec<GeneratedFile>,
+    registering: Registering
+}
+
+fn demo_athletes() -> Vec<Athlete> {
+    // the birth years follow the current season, so the athletes fit into the usual age-categories
+    let year = u16::try_from(Local::now().year()).unwrap_or(2025);
+    [("Erika", "Mustermann", 11, Belt::Kyu7, "-36", GenderCategory::Female),
+        ("Max", "Mustermann", 13, Belt::Kyu5, "-50", GenderCategory::Male),
+        ("Anna", "Musterfrau", 12, Belt::Kyu6, "-40", GenderCategory::Female),
+        ("Leon", "Beispiel", 10, Belt::Kyu8, "-31", GenderCategory::Male),
+        ("Mia", "Beispiel", 14, Belt::Kyu4, "-48", GenderCategory::Female),
+        ("Paul", "Probe", 16, Belt::Kyu3, "-66", GenderCategory::Male),
+        ("Sophie", "Probe", 17, Belt::Kyu2, "-57", GenderCategory::Female),
+        ("Jonas", "Test", 19, Belt::Kyu1, "-81", GenderCategory::Male)]
+    .into_iter().map(|(given_name, sur_name, age, belt, weight_category, gender)| {
+        Athlete::new(given_name.to_owned(), sur_name.to_owned(), year - age, belt,
+            WeightCategory::from_str(weight_category).unwrap_or_default(), gender)
+    }).collect()
+}
+
+fn demo_club() -> Club {
+    let mut club = Club::default();
+    *club.get_name_mut() = String::from("Judo-Club Musterstadt e. V.");
+    *club.get_number_mut() = 1234;
+    let sender = club.get_sender_mut();
+    *sender.get_given_name_mut() = String::from("Max");
+    *sender.get_sur_name_mut() = String::from("Mustermann");
+    *sender.get_address_mut() = String::from("Musterstraße 1");
+    *sender.get_postal_code_mut() = 12345;
+    *sender.get_town_mut() = String::from("Musterstadt");
+    *sender.get_mail_mut() = String::from("max@judo-musterstadt.de");
+    club
+}
+
+impl EMelderApp {
+    /// Replaces athletes, club and history by demo-data in a temporary directory, the real files are not touched.
+    pub fn load_demo(&mut self) -> Result<(), Error> {
+        if self.demo.is_some() {
+            return Ok(());
+        }
+        if self.registering.writing.is_some() {
+            return Err(Error::Validation(String::from("a registration is being written")));
+        }
+        let dir = std::env::temp_dir().join(format!("e-melder-demo-{}", std::process::id()));
+        let mut config = self.config.clone();
+        config.athletes_file = dir.join("athletes.json");
+        config.club_file = dir.join("club.json");
+        config.history_file = dir.join("history.json");
+        config.generated_file = dir.join("generated.json");
+        config.tournament_basedir = dir.join("tournaments");
+        // the demo-athletes must not end up in a backup or on the website
+        config.schedule.enabled = false;
+        std::fs::create_dir_all(&config.tournament_basedir)
+            .map_err(Error::io(format!("failed to create {}", config.tournament_basedir.display())))?;
+        let athletes = demo_athletes();
+        let club = demo_club();
+        write_athletes(&config.athletes_file, &athletes)
+            .map_err(Error::io(format!("failed to write {}", config.athletes_file.display())))?;
+        write_club(&config.club_file, &club).map_err(Error::io(format!("failed to write {}", config.club_file.display())))?;
+
+        self.demo = Some(Box::new(Demo {
+            dir,
+            config: std::mem::replace(&mut self.config, config),
+            athletes: std::mem::replace(&mut self.athletes, athletes),
+            club: std::mem::replace(&mut self.club, club),
+            history: std::mem::take(&mut self.history),
+            generated: std::mem::take(&mut self.generated),
+            registering: std::mem::take(&mut self.registering)
+        }));
+        self.generated_verification.clear();
+        Ok(())
+    }
+
+    /// Returns to the real data and removes the files of the demo.
+    pub fn exit_demo(&mut self) {
+        if self.registering.writing.is_some() {
+            return;
+        }
+        let Some(demo) = self.demo.take() else {
+            return;
+        };
+        self.config = demo.config;
+        self.athletes = demo.athletes;
+        self.club = demo.club;
+        self.history = demo.history;
+        self.generated = demo.generated;
+        self.registering = demo.registering;
+        self.generated_verification.clear();
+        if let Err(err) = std::fs::remove_dir_all(&demo.dir) {
+            log::warn!("failed to remove demo-directory, due to {err}");
+        }
+    }
+
+    pub fn is_demo(&self) -> bool {
+        self.demo.is_some()
+    }
+}
+
+pub fn show_demo_button(app: &mut EMelderApp, ui: &mut Ui) {
+    if app.demo.is_some() {
+        return;
+    }
+    if ui.add_enabled(app.registering.writing.is_none(), egui::Button::new(translate!("demo.load", &app.translations)))
+        .on_hover_text(translate!("demo.load.hover", &app.translations)).clicked() {
+        if let Err(err) = app.load_demo() {
+            log::warn!("failed to load demo-data, due to {err}");
+            app.notify_error(format!("{} {err}", translate!("demo.error", &app.translations)));
+        }
+    }
+}
+
+pub fn show_demo_banner(app: &mut EMelderApp, ctx: &egui::Context) {
+    if app.demo.is_none() {
+        return;
+    }
+    egui::TopBottomPanel::top("demo").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            warning_label(ui, translate!("demo.banner", &app.translations));
+            if ui.add_enabled(app.registering.writing.is_none(), egui::Button::new(translate!("demo.exit", &app.translations)))
+                .clicked() {
+                app.exit_demo();
+            }
+        });
+    });
+}
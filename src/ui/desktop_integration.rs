@@ -0,0 +1,100 @@
+use egui::Ui;
+
+use crate::desktop_integration::{create_shortcuts, get_data_dir, install_xdg_integration, is_desktop_entry_installed,
+    register_file_associations, remove_shortcuts, uninstall_xdg_integration, unregister_file_associations};
+use crate::error::Error;
+use crate::utils::translate;
+use super::EMelderApp;
+use super::theme::error_label;
+
+type Action = fn() -> Result<(), Error>;
+
+/// Registers the file-associations and shortcuts, which an installer would create otherwise.
+pub fn show_desktop_integration_config(app: &mut EMelderApp, ui: &mut Ui) {
+    let mut action: Option<(&'static str, Action)> = None;
+    if cfg!(windows) {
+        ui.label(translate!("config.desktop_integration", &app.translations));
+        ui.horizontal(|ui| {
+            if ui.button(translate!("config.desktop_integration.associate", &app.translations))
+                .on_hover_text(translate!("config.desktop_integration.associate.hover", &app.translations)).clicked() {
+                action = Some(("config.desktop_integration.associated", register_file_associations));
+            }
+            if ui.button(translate!("config.desktop_integration.unassociate", &app.translations)).clicked() {
+                action = Some(("config.desktop_integration.unassociated", unregister_file_associations));
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button(translate!("config.desktop_integration.shortcuts", &app.translations)).clicked() {
+                action = Some(("config.desktop_integration.shortcuts_created", create_shortcuts));
+            }
+            if ui.button(translate!("config.desktop_integration.remove_shortcuts", &app.translations)).clicked() {
+                action = Some(("config.desktop_integration.shortcuts_removed", remove_shortcuts));
+            }
+        });
+    }
+    else {
+        ui.label(translate!("config.desktop_integration.xdg", &app.translations));
+        ui.horizontal(|ui| {
+            if ui.button(translate!("config.desktop_integration.install", &app.translations))
+                .on_hover_text(translate!("config.desktop_integration.associate.hover", &app.translations)).clicked() {
+                action = Some(("config.desktop_integration.installed", install_xdg_integration));
+            }
+            if ui.button(translate!("config.desktop_integration.uninstall", &app.translations)).clicked() {
+                action = Some(("config.desktop_integration.uninstalled", uninstall_xdg_integration));
+            }
+        });
+    }
+    if let Some((succeeded, action)) = action {
+        run_action(app, succeeded, action);
+    }
+    match &app.desktop_integration {
+        Some(Ok(succeeded)) => {
+            ui.label(translate!(*succeeded, &app.translations));
+        }
+        Some(Err(err)) => {
+            error_label(ui, format!("{} {err}", translate!("config.desktop_integration.failed", &app.translations)));
+        }
+        None => {}
+    }
+}
+
+fn run_action(app: &mut EMelderApp, succeeded: &'static str, action: Action) {
+    app.desktop_integration = Some(action().map(|()| succeeded).map_err(|err| {
+        log::warn!("failed to change the desktop-integration, due to {err}");
+        err.to_string()
+    }));
+}
+
+/// Offers the desktop-entry once, as there is no installer for Linux adding it.
+pub fn show_desktop_integration_offer(app: &mut EMelderApp, ctx: &egui::Context) {
+    if !cfg!(target_os = "linux") || app.config.desktop_integration_offered || app.is_touring() {
+        return;
+    }
+    if get_data_dir().is_ok_and(|data_dir| is_desktop_entry_installed(&data_dir)) {
+        app.config.desktop_integration_offered = true;
+        return;
+    }
+    let mut install = None;
+    egui::Window::new(translate!("application.title", &app.translations)).collapsible(false).resizable(false).show(ctx, |ui| {
+        ui.label(translate!("desktop_integration.offer", &app.translations));
+        ui.horizontal(|ui| {
+            if ui.button(translate!("config.desktop_integration.install", &app.translations)).clicked() {
+                install = Some(true);
+            }
+            if ui.button(translate!("desktop_integration.not_now", &app.translations)).clicked() {
+                install = Some(false);
+            }
+        });
+        ui.label(translate!("desktop_integration.later", &app.translations));
+    });
+    if let Some(install) = install {
+        if install {
+            run_action(app, "config.desktop_integration.installed", install_xdg_integration);
+            if let Some(Err(err)) = &app.desktop_integration {
+                app.notify_error(format!("{} {err}", translate!("config.desktop_integration.failed", &app.translations)));
+            }
+        }
+        app.config.desktop_integration_offered = true;
+        app.save_configs();
+    }
+}
@@ -0,0 +1,279 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use egui::Ui;
+
+use crate::tournament_info::{merge_drafts, new_draft_athletes, Competition, Draft};
+use crate::utils::{get_draft_file, get_shared_draft_file, read_draft, read_shared_drafts, read_tournament, translate, write_draft, DRAFT_EXTENSION};
+use super::EMelderApp;
+use super::theme::error_label;
+
+/// Exports the current registration to a file, which can be imported on another machine.
+pub fn show_draft(app: &mut EMelderApp, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        if ui.add_enabled(!app.registering.athletes.is_empty(), egui::Button::new(translate!("draft.export", &app.translations)))
+            .clicked() {
+            let file_name = format!("{}.{DRAFT_EXTENSION}", app.registering.name).trim().to_owned();
+            if let Some(path) = rfd::FileDialog::new().set_can_create_directories(true)
+                .add_filter(translate!("draft.filter", &app.translations), &[DRAFT_EXTENSION]).set_file_name(file_name).save_file() {
+                let draft = current_draft(app);
+                app.registering.draft_error = write_draft(&path, &draft).err().map(|err| {
+                    log::warn!("failed to export draft, due to {err}");
+                    format!("{}: {err}", path.display())
+                });
+                app.registering.athletes = draft.athletes;
+            }
+        }
+        if ui.add_enabled(app.registering.writing.is_none(), egui::Button::new(translate!("draft.import", &app.translations)))
+            .clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter(translate!("draft.filter", &app.translations), &[DRAFT_EXTENSION]).pick_file() {
+                import_draft(app, &path);
+            }
+        }
+        if ui.add_enabled(app.registering.writing.is_none(), egui::Button::new(translate!("draft.import_dm4", &app.translations)))
+            .on_hover_text(translate!("draft.import_dm4.hover", &app.translations)).clicked() {
+            if let Some(paths) = rfd::FileDialog::new().add_filter("DM4", &["dm4"]).set_directory(&app.config.tournament_basedir)
+                .pick_files() {
+                import_tournaments(app, &paths);
+            }
+        }
+        if ui.add_enabled(app.registering.writing.is_none() && !is_empty(app),
+            egui::Button::new(translate!("draft.discard", &app.translations))).clicked() {
+            app.registering.confirm_discard = true;
+        }
+    });
+    show_confirm_discard(app, ui);
+    if let Some(draft_error) = &app.registering.draft_error {
+        error_label(ui, format!("{} {draft_error}", translate!("draft.error", &app.translations)));
+    }
+    show_shared_drafts(app, ui);
+}
+
+/// Imports a draft or tournament-file by its extension.
+pub(super) fn import_file(app: &mut EMelderApp, path: &Path) {
+    if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case(DRAFT_EXTENSION)) {
+        import_draft(app, path);
+    }
+    else {
+        import_tournaments(app, &[path.to_path_buf()]);
+    }
+}
+
+impl EMelderApp {
+    /// Continues the registration, which was in progress when the app was closed.
+    pub(super) fn restore_draft(&mut self) {
+        let draft_file = match get_draft_file() {
+            Ok(draft_file) => draft_file,
+            Err(err) => {
+                log::warn!("failed to get draft-file, due to {err}");
+                return;
+            }
+        };
+        match read_draft(draft_file) {
+            Ok(draft) => {
+                self.set_tournament(draft.name, draft.place, draft.date);
+                self.registering.athletes = draft.athletes;
+                self.registering.competition = draft.competition;
+                self.registering.coach = draft.coach;
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => log::warn!("failed to restore draft, due to {err}")
+        }
+    }
+
+    /// Keeps the registration in progress for the next start, an empty one removes the draft.
+    pub(super) fn persist_draft(&mut self) {
+        // the registration of the demo is not continued after a restart
+        if self.demo.is_some() {
+            return;
+        }
+        let draft_file = match get_draft_file() {
+            Ok(draft_file) => draft_file,
+            Err(err) => {
+                log::warn!("failed to get draft-file, due to {err}");
+                return;
+            }
+        };
+        if is_empty(self) {
+            remove_draft_file(&draft_file);
+            return;
+        }
+        let draft = current_draft(self);
+        if let Err(err) = write_draft(&draft_file, &draft) {
+            log::warn!("failed to persist draft, due to {err}");
+        }
+        self.registering.athletes = draft.athletes;
+    }
+
+    pub(super) fn discard_draft(&mut self) {
+        self.registering.name.clear();
+        self.registering.place.clear();
+        self.registering.date = Local::now().date_naive();
+        self.registering.athletes.clear();
+        self.registering.entry_selection.clear();
+        self.registering.department = None;
+        self.registering.competition = Competition::Individual;
+        self.registering.coach = self.config.coach.clone();
+        self.registering.invitation = None;
+        self.registering.invitation_reading = None;
+        self.registering.invitation_error = None;
+        self.registering.draft_error = None;
+        match get_draft_file() {
+            Ok(draft_file) => remove_draft_file(&draft_file),
+            Err(err) => log::warn!("failed to get draft-file, due to {err}")
+        }
+    }
+}
+
+fn show_confirm_discard(app: &mut EMelderApp, ui: &Ui) {
+    if !app.registering.confirm_discard {
+        return;
+    }
+    let mut discard = None;
+    egui::Window::new(translate!("draft.discard", &app.translations)).collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+        ui.label(translate!("draft.discard.confirm", &app.translations));
+        ui.horizontal(|ui| {
+            if ui.button(translate!("draft.discard.yes", &app.translations)).clicked() {
+                discard = Some(true);
+            }
+            if ui.button(translate!("draft.discard.no", &app.translations)).clicked() {
+                discard = Some(false);
+            }
+        });
+    });
+    if let Some(discard) = discard {
+        app.registering.confirm_discard = false;
+        if discard {
+            app.discard_draft();
+        }
+    }
+}
+
+fn is_empty(app: &EMelderApp) -> bool {
+    app.registering.name.is_empty() && app.registering.place.is_empty() && app.registering.athletes.is_empty()
+}
+
+fn remove_draft_file(draft_file: &Path) {
+    if let Err(err) = std::fs::remove_file(draft_file) {
+        if err.kind() != io::ErrorKind::NotFound {
+            log::warn!("failed to remove draft, due to {err}");
+        }
+    }
+}
+
+fn import_draft(app: &mut EMelderApp, path: &Path) {
+    match read_draft(path) {
+        Ok(draft) => {
+            app.registering.name = draft.name;
+            app.registering.place = draft.place;
+            app.registering.date = draft.date;
+            app.registering.athletes = draft.athletes;
+            app.registering.competition = draft.competition;
+            app.registering.coach = draft.coach;
+            // the invitation is a file on the other machine
+            app.registering.invitation = None;
+            app.registering.invitation_reading = None;
+            app.registering.draft_error = None;
+        }
+        Err(err) => {
+            log::warn!("failed to import draft, due to {err}");
+            app.registering.draft_error = Some(format!("{}: {err}", path.display()));
+        }
+    }
+}
+
+/// Continues a registration started elsewhere, each file holds the athletes of one category.
+fn import_tournaments(app: &mut EMelderApp, paths: &[PathBuf]) {
+    let mut athletes = Vec::new();
+    let mut competition = Competition::Individual;
+    let mut errors = Vec::new();
+    for path in paths {
+        match read_tournament(path) {
+            Ok(tournament) => {
+                if athletes.is_empty() {
+                    app.set_tournament(tournament.get_name().to_owned(), tournament.get_place().to_owned(), tournament.get_date());
+                }
+                if !tournament.get_teams().is_empty() {
+                    competition = Competition::Team;
+                }
+                athletes.extend(tournament.to_registering_athletes(&app.athletes));
+            }
+            Err(err) => {
+                log::warn!("failed to import tournament-file, due to {err}");
+                errors.push(err.to_string());
+            }
+        }
+    }
+    if !athletes.is_empty() {
+        app.registering.athletes = athletes;
+        app.registering.competition = competition;
+        app.registering.invitation = None;
+        app.registering.invitation_reading = None;
+    }
+    app.registering.draft_error = if errors.is_empty() { None } else { Some(errors.join("\n")) };
+}
+
+fn current_draft(app: &mut EMelderApp) -> Draft {
+    // the athletes are taken, so they have to be put back after using the draft
+    Draft {
+        name: app.registering.name.clone(), place: app.registering.place.clone(), date: app.registering.date,
+        athletes: std::mem::take(&mut app.registering.athletes), competition: app.registering.competition,
+        coach: app.registering.coach.clone()
+    }
+}
+
+/// Shares the draft with the other coaches in the shared folder and merges their selections for the same tournament.
+fn show_shared_drafts(app: &mut EMelderApp, ui: &mut Ui) {
+    let Some(shared_dir) = app.config.shared_drafts_dir.clone() else {
+        return;
+    };
+    let own_file = get_shared_draft_file(&shared_dir, &app.registering.name, app.registering.date, &app.config.draft_user);
+    ui.horizontal(|ui| {
+        if ui.add_enabled(!app.registering.name.is_empty() && !app.registering.athletes.is_empty(),
+            egui::Button::new(translate!("draft.shared.save", &app.translations))).clicked() {
+            let draft = current_draft(app);
+            app.registering.draft_error = write_draft(&own_file, &draft).err().map(|err| {
+                log::warn!("failed to write shared draft, due to {err}");
+                format!("{}: {err}", own_file.display())
+            });
+            app.registering.athletes = draft.athletes;
+        }
+        if ui.button(translate!("draft.shared.refresh", &app.translations)).clicked() {
+            match read_shared_drafts(&shared_dir, &app.registering.name, app.registering.date) {
+                Ok(drafts) => {
+                    app.registering.shared_drafts = drafts.into_iter().filter(|(path, _)| *path != own_file).collect();
+                    app.registering.draft_error = None;
+                }
+                Err(err) => {
+                    log::warn!("failed to read shared drafts, due to {err}");
+                    app.registering.shared_drafts.clear();
+                    app.registering.draft_error = Some(format!("{}: {err}", shared_dir.display()));
+                }
+            }
+        }
+    });
+
+    let mut to_merge = None;
+    for (index, (path, draft)) in app.registering.shared_drafts.iter().enumerate() {
+        let new_athletes = new_draft_athletes(&app.registering.athletes, &draft.athletes);
+        let file_name = path.file_name().map(|file_name| file_name.to_string_lossy().into_owned()).unwrap_or_default();
+        egui::CollapsingHeader::new(format!("{file_name} ({} {})", new_athletes.len(),
+            translate!("draft.shared.new_athletes", &app.translations)))
+        .id_salt(("draft.shared", index)).show(ui, |ui| {
+            for athlete in &new_athletes {
+                ui.label(format!("{} {} ({}), {} {}", athlete.get_given_name(), athlete.get_sur_name(), athlete.get_birth_year(),
+                    athlete.get_age_category(), athlete.get_weight_category()));
+            }
+            if ui.add_enabled(!new_athletes.is_empty(), egui::Button::new(translate!("draft.shared.merge", &app.translations)))
+                .clicked() {
+                to_merge = Some(index);
+            }
+        });
+    }
+    if let Some(index) = to_merge.filter(|index| *index < app.registering.shared_drafts.len()) {
+        let (_, draft) = app.registering.shared_drafts.remove(index);
+        merge_drafts(&mut app.registering.athletes, draft.athletes);
+    }
+}
@@ -0,0 +1,105 @@
+use egui::{Key, Modifiers, Ui};
+use uuid::Uuid;
+
+use crate::utils::translate;
+use super::EMelderApp;
+use super::registering::{add_to_registering, show_search};
+
+const PANE_HEIGHT: f32 = 300.0;
+
+/// All athletes on the left and the registered ones on the right, the selected ones are moved between both
+/// by the buttons in the middle or the keyboard, which suits building large registrations.
+pub(super) fn show_dual_pane(app: &mut EMelderApp, ui: &mut Ui) {
+    let shown = show_search(app, ui);
+    // athletes hidden by the search are not moved unseen
+    app.registering.pool_selection.retain(|id| shown.iter().any(|index| app.athletes[*index].get_id() == *id));
+    let len = app.registering.athletes.len();
+    app.registering.entry_selection.retain(|index| *index < len);
+
+    // the keys are left to the search, while it has the focus
+    let (mut add, mut remove) = if ui.ctx().wants_keyboard_input() {
+        (false, false)
+    }
+    else {
+        ui.input_mut(|input| (
+            input.consume_key(Modifiers::COMMAND, Key::ArrowRight) || input.consume_key(Modifiers::NONE, Key::Enter),
+            input.consume_key(Modifiers::COMMAND, Key::ArrowLeft) || input.consume_key(Modifiers::NONE, Key::Delete)
+        ))
+    };
+
+    ui.horizontal_top(|ui| {
+        let pane_width = (ui.available_width() - 80.0) / 2.0;
+        ui.vertical(|ui| {
+            ui.set_width(pane_width);
+            ui.strong(translate!("register.dual_pane.athletes", &app.translations));
+            egui::ScrollArea::vertical().id_salt("register.dual_pane.athletes").max_height(PANE_HEIGHT).show(ui, |ui| {
+                for index in shown.iter().copied() {
+                    let athlete = &app.athletes[index];
+                    let registered = app.registering.athletes.iter().any(|registering| athlete.is_same_athlete(registering));
+                    let text = format!("{}{} {} ({})", if registered { "✔ " } else { "" }, athlete.get_given_name(),
+                        athlete.get_sur_name(), athlete.get_birth_year());
+                    let id = athlete.get_id();
+                    let response = ui.selectable_label(app.registering.pool_selection.contains(&id), text);
+                    if response.double_clicked() {
+                        app.registering.pool_selection = [id].into();
+                        add = true;
+                    }
+                    else if response.clicked() && !app.registering.pool_selection.remove(&id) {
+                        app.registering.pool_selection.insert(id);
+                    }
+                }
+            });
+        });
+
+        ui.vertical(|ui| {
+            ui.add_space(PANE_HEIGHT / 3.0);
+            if ui.add_enabled(!app.registering.pool_selection.is_empty(),
+                egui::Button::new(format!("→ ({})", app.registering.pool_selection.len())))
+                .on_hover_text(translate!("register.dual_pane.add", &app.translations)).clicked() {
+                add = true;
+            }
+            if ui.add_enabled(!app.registering.entry_selection.is_empty(),
+                egui::Button::new(format!("← ({})", app.registering.entry_selection.len())))
+                .on_hover_text(translate!("register.dual_pane.remove", &app.translations)).clicked() {
+                remove = true;
+            }
+        });
+
+        ui.vertical(|ui| {
+            ui.set_width(pane_width);
+            ui.strong(format!("{} ({})", translate!("register.dual_pane.registered", &app.translations), len));
+            egui::ScrollArea::vertical().id_salt("register.dual_pane.registered").max_height(PANE_HEIGHT).show(ui, |ui| {
+                for (index, athlete) in app.registering.athletes.iter().enumerate() {
+                    let text = format!("{} {} – {} {}", athlete.get_given_name(), athlete.get_sur_name(), athlete.get_age_category(),
+                        athlete.get_weight_category());
+                    let response = ui.selectable_label(app.registering.entry_selection.contains(&index), text);
+                    if response.double_clicked() {
+                        app.registering.entry_selection = [index].into();
+                        remove = true;
+                    }
+                    else if response.clicked() && !app.registering.entry_selection.remove(&index) {
+                        app.registering.entry_selection.insert(index);
+                    }
+                }
+            });
+        });
+    });
+
+    if add {
+        let pool_selection = std::mem::take(&mut app.registering.pool_selection);
+        // added in the order of the table
+        let ids: Vec<Uuid> = shown.iter().map(|index| app.athletes[*index].get_id()).filter(|id| pool_selection.contains(id)).collect();
+        for id in ids {
+            add_to_registering(app, id);
+        }
+    }
+    if remove {
+        // removed from the back, so the indices of the remaining ones stay valid
+        for index in std::mem::take(&mut app.registering.entry_selection).into_iter().rev() {
+            app.registering.athletes.remove(index);
+        }
+    }
+    if shown.is_empty() {
+        ui.label(translate!("register.search.empty", &app.translations));
+    }
+}
@@ -0,0 +1,172 @@
+use std::path::PathBuf;
+use std::thread::JoinHandle;
+
+use egui::Ui;
+
+use crate::email::{EmailSettings, RegistrationEmail, SmtpSecurity, DEFAULT_BODY_TEMPLATE, DEFAULT_SUBJECT_TEMPLATE};
+use crate::error::Error;
+use crate::utils::translate;
+use super::EMelderApp;
+use super::theme::{error_label, warning_label};
+
+/// The mail of the last registration, offered after writing its files.
+#[derive(Debug)]
+pub(super) struct EmailTask {
+    email: RegistrationEmail,
+    sending: Option<JoinHandle<Result<(), Error>>>,
+    error: Option<String>,
+    sent: bool
+}
+
+/// Offers mailing the written files, if an SMTP-server is configured.
+pub(super) fn prepare_registration_email(app: &mut EMelderApp, paths: &[PathBuf]) {
+    if !app.config.email.is_configured() {
+        return;
+    }
+    let mut attachments = Vec::new();
+    for path in paths {
+        attachments.push(path.clone());
+        // the additional formats, which were written next to the tournament-file
+        for (enabled, extension) in [(app.config.entry_list_pdf, "pdf"), (app.config.entry_list_csv, "csv")] {
+            let additional = path.with_extension(extension);
+            if enabled && additional.exists() {
+                attachments.push(additional);
+            }
+        }
+    }
+    let email = RegistrationEmail::new(&app.config.email, &app.registering.name, app.registering.date, &app.registering.place,
+        app.club.get_name(), attachments);
+    app.registering.email = Some(EmailTask { email, sending: None, error: None, sent: false });
+}
+
+impl EmailTask {
+    pub(super) fn is_sending(&self) -> bool {
+        self.sending.as_ref().is_some_and(|sending| !sending.is_finished())
+    }
+}
+
+pub(super) fn finish_sending(app: &mut EMelderApp) {
+    let Some(task) = &mut app.registering.email else {
+        return;
+    };
+    if !task.sending.as_ref().is_some_and(JoinHandle::is_finished) {
+        return;
+    }
+    let result = task.sending.take().map_or(Ok(()), |sending| {
+        sending.join().unwrap_or_else(|_| Err(Error::Network(String::from("panic while sending"))))
+    });
+    match result {
+        Ok(()) => task.sent = true,
+        Err(err) => {
+            log::warn!("failed to send registration by email, due to {err}");
+            task.error = Some(err.to_string());
+        }
+    }
+}
+
+pub(super) fn show_registration_email(app: &mut EMelderApp, ui: &Ui) {
+    finish_sending(app);
+    let Some(task) = &mut app.registering.email else {
+        return;
+    };
+
+    let mut close = false;
+    let mut send = false;
+    egui::Window::new(translate!("email.title", &app.translations)).collapsible(false).show(ui.ctx(), |ui| {
+        if task.sent {
+            ui.label(translate!("email.sent", &app.translations));
+            close = ui.button(translate!("email.close", &app.translations)).clicked();
+            return;
+        }
+        let editable = task.sending.is_none();
+        egui::Grid::new("email").num_columns(2).show(ui, |ui| {
+            ui.label(translate!("email.recipient", &app.translations));
+            ui.add_enabled(editable, egui::TextEdit::singleline(&mut task.email.recipient));
+            ui.end_row();
+            ui.label(translate!("email.subject", &app.translations));
+            ui.add_enabled(editable, egui::TextEdit::singleline(&mut task.email.subject));
+            ui.end_row();
+        });
+        ui.add_enabled(editable, egui::TextEdit::multiline(&mut task.email.body).desired_rows(6));
+        ui.label(translate!("email.attachments", &app.translations));
+        for attachment in &task.email.attachments {
+            ui.label(attachment.file_name().map(|file_name| file_name.to_string_lossy().into_owned()).unwrap_or_default());
+        }
+        if let Some(error) = &task.error {
+            error_label(ui, format!("{} {error}", translate!("email.failed", &app.translations)));
+        }
+        ui.horizontal(|ui| {
+            if task.sending.is_some() {
+                ui.spinner();
+                ui.label(translate!("email.sending", &app.translations));
+                // polled without user input
+                ui.ctx().request_repaint_after(std::time::Duration::from_millis(200));
+                return;
+            }
+            send = ui.add_enabled(!task.email.recipient.trim().is_empty(), egui::Button::new(translate!("email.send", &app.translations)))
+                .clicked();
+            close = ui.button(translate!("email.cancel", &app.translations)).clicked();
+        });
+    });
+
+    if send {
+        let email = task.email.clone();
+        let settings = app.config.email.clone();
+        task.error = None;
+        task.sending = Some(std::thread::spawn(move || email.send(&settings)));
+    }
+    if close {
+        app.registering.email = None;
+    }
+}
+
+pub fn show_email_config(app: &mut EMelderApp, ui: &mut Ui) {
+    let settings = &mut app.config.email;
+    ui.checkbox(&mut settings.enabled, translate!("config.email", &app.translations))
+        .on_hover_text(translate!("config.email.hover", &app.translations));
+    if !settings.enabled {
+        return;
+    }
+    egui::Grid::new("email_config").num_columns(2).show(ui, |ui| {
+        ui.label(translate!("config.email.host", &app.translations));
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut settings.host);
+            ui.add(egui::DragValue::new(&mut settings.port));
+            egui::ComboBox::from_id_salt("smtp_security").selected_text(settings.security.name()).show_ui(ui, |ui| {
+                for security in SmtpSecurity::ALL {
+                    ui.selectable_value(&mut settings.security, security, security.name());
+                }
+            });
+        });
+        ui.end_row();
+        ui.label(translate!("config.email.user", &app.translations));
+        ui.text_edit_singleline(&mut settings.user);
+        ui.end_row();
+        ui.label(translate!("config.email.password", &app.translations));
+        ui.add(egui::TextEdit::singleline(&mut settings.password).password(true));
+        ui.end_row();
+        ui.label(translate!("config.email.from", &app.translations));
+        ui.text_edit_singleline(&mut settings.from);
+        ui.end_row();
+        ui.label(translate!("config.email.recipient", &app.translations));
+        ui.text_edit_singleline(&mut settings.recipient);
+        ui.end_row();
+        ui.label(translate!("config.email.subject", &app.translations));
+        ui.text_edit_singleline(&mut settings.subject_template);
+        ui.end_row();
+        ui.label(translate!("config.email.body", &app.translations));
+        ui.text_edit_multiline(&mut settings.body_template);
+        ui.end_row();
+    });
+    if !settings.password.is_empty() {
+        warning_label(ui, translate!("config.email.password.plaintext", &app.translations));
+    }
+    ui.label(translate!("config.email.placeholders", &app.translations));
+    if ui.button(translate!("config.email.reset", &app.translations)).clicked() {
+        settings.subject_template = DEFAULT_SUBJECT_TEMPLATE.to_owned();
+        settings.body_template = DEFAULT_BODY_TEMPLATE.to_owned();
+    }
+    if !EmailSettings::is_configured(settings) {
+        error_label(ui, translate!("config.email.incomplete", &app.translations));
+    }
+}
@@ -0,0 +1,237 @@
+use egui::{TextWrapMode, Ui};
+use egui_extras::{Column, TableBuilder};
+
+use crate::export::csv::write_exam_csv;
+use crate::export::pdf::write_exam_list;
+use crate::tournament_info::{Exam, ExamCandidate, SortColumn};
+use crate::utils::translate;
+use super::EMelderApp;
+use super::registering::matches_query;
+use super::sorting::sortable_header;
+use super::theme::error_label;
+
+/// A Kyu-examination being prepared, kept while switching pages, but not between starts.
+#[derive(Debug, Default)]
+pub(super) struct ExamPage {
+    exam: Exam,
+    pub(super) search: String,
+    error: Option<String>
+}
+
+#[derive(Clone, Copy)]
+enum ExamExport {
+    Pdf,
+    Csv
+}
+
+/// Registers athletes to a Kyu-examination instead of a tournament, with a printable list for the examiners.
+pub(super) fn show_exam(app: &mut EMelderApp, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        ui.label(translate!("exam.date", &app.translations));
+        ui.add(egui_extras::DatePickerButton::new(&mut app.exam.exam.date).id_salt("exam_date").format("%d.%m.%Y"));
+    });
+    ui.horizontal(|ui| {
+        ui.label(translate!("exam.place", &app.translations));
+        ui.text_edit_singleline(&mut app.exam.exam.place);
+    });
+
+    let mut export = None;
+    ui.horizontal(|ui| {
+        let enabled = !app.exam.exam.candidates.is_empty();
+        if ui.add_enabled(enabled, egui::Button::new(translate!("exam.export.pdf", &app.translations))).clicked() {
+            export = Some(ExamExport::Pdf);
+        }
+        if ui.add_enabled(enabled, egui::Button::new(translate!("exam.export.csv", &app.translations))).clicked() {
+            export = Some(ExamExport::Csv);
+        }
+    });
+    if let Some(export) = export {
+        export_exam(app, export);
+    }
+    if let Some(error) = &app.exam.error {
+        error_label(ui, error);
+    }
+
+    ui.separator();
+    show_table_athletes(app, ui);
+    ui.separator();
+
+    if app.exam.exam.candidates.is_empty() {
+        ui.label(translate!("exam.candidates.empty", &app.translations));
+    }
+    else {
+        show_table_candidates(app, ui);
+    }
+}
+
+fn export_exam(app: &mut EMelderApp, export: ExamExport) {
+    let extension = match export {
+        ExamExport::Pdf => "pdf",
+        ExamExport::Csv => "csv"
+    };
+    let file_name = format!("{} {}.{extension}", translate!("exam.list.title", &app.translations),
+        app.exam.exam.date.format("%Y-%m-%d"));
+    let Some(path) = rfd::FileDialog::new().set_can_create_directories(true).set_directory(&app.config.tournament_basedir)
+        .add_filter(extension.to_uppercase(), &[extension]).set_file_name(file_name).save_file() else {
+        return;
+    };
+    let club = app.club.clone();
+    let result = match export {
+        ExamExport::Pdf => write_exam_list(&path, &app.exam.exam, &club, &app.translations),
+        ExamExport::Csv => write_exam_csv(&path, &app.exam.exam, &club)
+    };
+    match result {
+        Ok(()) => {
+            app.exam.error = None;
+            if matches!(export, ExamExport::Pdf) {
+                let _ = open::that_detached(path);
+            }
+        }
+        Err(err) => {
+            log::warn!("failed to export exam, due to {err}");
+            app.exam.error = Some(format!("{}: {err}", path.display()));
+        }
+    }
+}
+
+fn show_table_athletes(app: &mut EMelderApp, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        ui.label(translate!("exam.search", &app.translations));
+        ui.text_edit_singleline(&mut app.exam.search);
+    });
+
+    // athletes with the 1. Kyu or a Dan-grade take no Kyu-examination
+    let mut shown: Vec<usize> = app.athletes.iter().enumerate().filter(|(_, athlete)| {
+        athlete.get_belt().next_kyu().is_some()
+            && !app.exam.exam.candidates.iter().any(|candidate| candidate.is_same_as(athlete))
+            && matches_query(&format!("{} {}", athlete.get_given_name(), athlete.get_sur_name()), &app.exam.search)
+    }).map(|(index, _)| index).collect();
+    app.config.sorting.exam.sort(&app.athletes, &mut shown);
+
+    let mut to_add = None;
+    let mut sorted = false;
+    ui.push_id("exam.table.athletes", |ui| {
+        let row_height = app.row_height();
+        let table = TableBuilder::new(ui).columns(Column::auto().at_least(100.0), 4)
+            .column(Column::auto().at_least(50.0)).max_scroll_height(150.0);
+        let table = app.restore_scroll_offset("exam.table.athletes", table);
+
+        let sorting = &mut app.config.sorting.exam;
+        let output = table.header(20.0, |mut header| {
+            for (key, column) in [("register.table.given_name", SortColumn::GivenName), ("register.table.sur_name", SortColumn::SurName),
+                ("register.table.belt", SortColumn::Belt), ("register.table.year", SortColumn::BirthYear)] {
+                header.col(|ui| {
+                    sorted |= sortable_header(ui, translate!(key, &app.translations), column, sorting);
+                });
+            }
+        }).body(|mut body| {
+            for index in shown.iter().copied() {
+                let athlete = &app.athletes[index];
+                body.row(row_height, |mut row| {
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(athlete.get_given_name());
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(athlete.get_sur_name());
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(translate!(&format!("add.belt.{}", athlete.get_belt().serialise()), &app.translations));
+                    });
+                    row.col(|ui| {
+                        ui.label(athlete.get_birth_year().to_string());
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        if ui.button(translate!("register.table.add", &app.translations)).clicked() {
+                            to_add = Some(index);
+                        }
+                    });
+                });
+            }
+        });
+        app.remember_scroll_offset("exam.table.athletes", &output);
+    });
+
+    if let Some(candidate) = to_add.and_then(|index| app.athletes.get(index)).and_then(ExamCandidate::from_athlete) {
+        app.exam.exam.candidates.push(candidate);
+    }
+    if sorted {
+        app.save_configs();
+    }
+    if shown.is_empty() {
+        ui.label(translate!("register.search.empty", &app.translations));
+    }
+}
+
+fn show_table_candidates(app: &mut EMelderApp, ui: &mut Ui) {
+    let mut to_remove = None;
+    ui.push_id("exam.table.candidates", |ui| {
+        let row_height = app.row_height();
+        let table = TableBuilder::new(ui).columns(Column::auto().at_least(100.0), 5)
+            .column(Column::auto().at_least(50.0));
+        let table = app.restore_scroll_offset("exam.table.candidates", table);
+
+        let output = table.header(20.0, |mut header| {
+            header.col(|ui| {
+                ui.strong(translate!("register.table.given_name", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("register.table.sur_name", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("register.table.year", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("register.table.belt", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("exam.list.target", &app.translations));
+            });
+        }).body(|mut body| {
+            for (index, candidate) in app.exam.exam.candidates.iter_mut().enumerate() {
+                body.row(row_height, |mut row| {
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(candidate.get_given_name());
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(candidate.get_sur_name());
+                    });
+                    row.col(|ui| {
+                        ui.label(candidate.get_birth_year().to_string());
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(translate!(&format!("add.belt.{}", candidate.get_belt().serialise()), &app.translations));
+                    });
+                    row.col(|ui| {
+                        let targets = candidate.possible_targets();
+                        egui::ComboBox::from_id_salt(("exam_target", index))
+                        .selected_text(translate!(&format!("add.belt.{}", candidate.get_target().serialise()), &app.translations))
+                        .show_ui(ui, |ui| {
+                            for target in targets {
+                                ui.selectable_value(candidate.get_target_mut(), target,
+                                    translate!(&format!("add.belt.{}", target.serialise()), &app.translations));
+                            }
+                        });
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        if ui.button(translate!("register.table.delete", &app.translations)).clicked() {
+                            to_remove = Some(index);
+                        }
+                    });
+                });
+            }
+        });
+        app.remember_scroll_offset("exam.table.candidates", &output);
+    });
+
+    if let Some(index) = to_remove.filter(|index| *index < app.exam.exam.candidates.len()) {
+        app.exam.exam.candidates.remove(index);
+    }
+}
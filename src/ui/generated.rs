@@ -0,0 +1,128 @@
+use std::io;
+use std::path::Path;
+
+use egui::{TextWrapMode, Ui};
+use egui_extras::{Column, TableBuilder};
+
+use crate::utils::{hash_file, translate, write_tournament};
+use super::EMelderApp;
+
+#[derive(Clone, Copy, Debug)]
+pub(super) enum Verification {
+    Unchanged,
+    Modified,
+    Missing
+}
+
+impl Verification {
+    fn translation_key(self) -> &'static str {
+        match self {
+            Verification::Unchanged => "generated.unchanged",
+            Verification::Modified => "generated.modified",
+            Verification::Missing => "generated.missing"
+        }
+    }
+}
+
+fn verify(path: &Path, sha256: &str) -> Verification {
+    match hash_file(path) {
+        Ok(hash) if hash == sha256 => Verification::Unchanged,
+        Ok(_) => Verification::Modified,
+        Err(err) => {
+            if err.kind() != io::ErrorKind::NotFound {
+                log::warn!("failed to hash generated file, due to {err}");
+            }
+            Verification::Missing
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn show_generated(app: &mut EMelderApp, ui: &mut Ui) {
+    if app.generated.is_empty() {
+        ui.label(translate!("generated.empty", &app.translations));
+        return;
+    }
+
+    let mut to_verify = None;
+    let mut to_regenerate = None;
+    let row_height = app.row_height();
+    ui.push_id("generated.table", |ui| {
+        let table = TableBuilder::new(ui).columns(Column::auto().at_least(100.0), 3)
+            .columns(Column::auto().at_least(50.0), 3);
+
+        table.header(20.0, |mut header| {
+            header.col(|ui| {
+                ui.strong(translate!("generated.file", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("generated.timestamp", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("generated.status", &app.translations));
+            });
+            header.col(|_ui| {});
+            header.col(|_ui| {});
+            header.col(|_ui| {});
+        }).body(|mut body| {
+            // the most recent files first
+            for (index, generated) in app.generated.iter().enumerate().rev() {
+                body.row(row_height, |mut row| {
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(generated.get_path().file_name().unwrap_or_default().to_string_lossy())
+                            .on_hover_text(generated.get_path().display().to_string());
+                    });
+                    row.col(|ui| {
+                        ui.label(generated.get_timestamp().format("%d.%m.%Y %H:%M").to_string());
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        if let Some(verification) = app.generated_verification.get(&index) {
+                            ui.label(translate!(verification.translation_key(), &app.translations));
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        if ui.button(translate!("generated.open", &app.translations)).clicked() {
+                            let _ = open::that_detached(generated.get_path());
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        if ui.button(translate!("generated.verify", &app.translations)).clicked() {
+                            to_verify = Some(index);
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        if ui.add_enabled(!app.read_only, egui::Button::new(translate!("generated.regenerate", &app.translations)))
+                            .clicked() {
+                            to_regenerate = Some(index);
+                        }
+                    });
+                });
+            }
+        });
+    });
+
+    if let Some(index) = to_verify {
+        if let Some(generated) = app.generated.get(index) {
+            app.generated_verification.insert(index, verify(generated.get_path(), generated.get_sha256()));
+        }
+    }
+
+    if let Some(index) = to_regenerate {
+        if let Some(generated) = app.generated.get(index) {
+            // the stored registration renders to the same contents, so the recorded hash stays valid
+            match write_tournament(generated.get_path(), generated.get_tournament()) {
+                Ok(()) => {
+                    app.generated_verification.insert(index, verify(generated.get_path(), generated.get_sha256()));
+                }
+                Err(err) => {
+                    log::warn!("failed to generate file again, due to {err}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+
+use egui::{TextWrapMode, Ui};
+use egui_extras::{Column, TableBuilder};
+
+use crate::error::Error;
+use crate::tournament_info::{CsvExport, GeneratedFile, HistoryEntry, HistoryFile, Tournament};
+use crate::utils::{attach_file, copy_invitation, find_tournament_files, get_attachments_dir, get_invitation_file, list_attachments, read_tournament, translate,
+    write_csv_export, write_history};
+use super::EMelderApp;
+use super::theme::error_label;
+
+/// The notes and attachments of the history-entry shown in a window.
+#[derive(Debug)]
+pub(super) struct HistoryDetails {
+    index: usize,
+    // listed again after attaching or removing a file only
+    attachments: Vec<PathBuf>,
+    error: Option<String>
+}
+
+/// Registrations found in the tournament-basedir, which were not written by this app, e.g. by the official application.
+#[derive(Debug, Default)]
+pub(super) struct ForeignRegistrations {
+    entries: Vec<HistoryEntry>,
+    errors: Vec<String>
+}
+
+impl EMelderApp {
+    /// Looks for tournament-files unknown to the history and the generated files, returning the number of registrations found.
+    pub fn scan_foreign_registrations(&mut self) -> Result<usize, Error> {
+        let paths = find_tournament_files(&self.config.tournament_basedir)?;
+        let known: Vec<&Path> = self.history.iter().flat_map(|entry| entry.get_files().iter().map(HistoryFile::get_path))
+            .chain(self.generated.iter().map(GeneratedFile::get_path)).collect();
+        // the files of one registration share name, date and place of the tournament
+        #[allow(clippy::type_complexity)]
+        let mut grouped: BTreeMap<(NaiveDate, String, String), (Vec<Tournament>, Vec<PathBuf>)> = BTreeMap::new();
+        let mut errors = Vec::new();
+        for path in paths.into_iter().filter(|path| !known.contains(&path.as_path())) {
+            match read_tournament(&path) {
+                Ok(tournament) => {
+                    let key = (tournament.get_date(), tournament.get_name().to_owned(), tournament.get_place().to_owned());
+                    let (tournaments, paths) = grouped.entry(key).or_default();
+                    tournaments.push(tournament);
+                    paths.push(path);
+                }
+                Err(err) => {
+                    log::warn!("failed to read foreign tournament-file, due to {err}");
+                    errors.push(err.to_string());
+                }
+            }
+        }
+        let entries: Vec<HistoryEntry> = grouped.into_iter().map(|((date, name, place), (tournaments, paths))| {
+            let mut entry = HistoryEntry::from_tournaments(name, date, place, &tournaments);
+            entry.set_files(&tournaments, &paths);
+            entry
+        }).collect();
+        let count = entries.len();
+        self.foreign_registrations = Some(ForeignRegistrations { entries, errors });
+        Ok(count)
+    }
+
+    /// Adds the registrations found by `scan_foreign_registrations` to the history.
+    pub fn import_foreign_registrations(&mut self) {
+        let Some(foreign_registrations) = self.foreign_registrations.take() else {
+            return;
+        };
+        if foreign_registrations.entries.is_empty() {
+            return;
+        }
+        self.history.extend(foreign_registrations.entries);
+        self.save_history();
+    }
+
+    /// Copies `source` into the folder of the history-entry at `index`, returning the path of the copy.
+    pub fn attach_to_history_entry(&mut self, index: usize, source: &Path) -> Result<PathBuf, Error> {
+        let entry = self.history.get(index).ok_or_else(|| Error::Validation(format!("no history-entry {index}")))?;
+        attach_file(&get_attachments_dir(&self.config.history_file, entry), source)
+    }
+
+    /// Copies the invitation into the folder of the history-entry at `index` and attaches it to the entry.
+    pub fn set_history_invitation(&mut self, index: usize, invitation: &Path) -> Result<(), Error> {
+        let history_file = &self.config.history_file;
+        let entry = self.history.get_mut(index).ok_or_else(|| Error::Validation(format!("no history-entry {index}")))?;
+        let copy = copy_invitation(&get_attachments_dir(history_file, entry), invitation)?;
+        entry.set_invitation(Some(copy));
+        self.save_history();
+        Ok(())
+    }
+
+    pub fn get_history_invitation(&self, index: usize) -> Option<PathBuf> {
+        self.history.get(index).and_then(|entry| get_invitation_file(&self.config.history_file, entry))
+    }
+
+    pub fn get_history_attachments(&self, index: usize) -> Vec<PathBuf> {
+        self.history.get(index)
+            .map(|entry| list_attachments(&get_attachments_dir(&self.config.history_file, entry))).unwrap_or_default()
+    }
+
+    pub(super) fn save_history(&self) {
+        if let Err(err) = write_history(&self.config.history_file, &self.history) {
+            log::warn!("failed to write history, due to {err}");
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn show_history(app: &mut EMelderApp, ui: &mut Ui) {
+    if app.history.is_empty() {
+        ui.label(translate!("history.empty", &app.translations));
+        // clubs migrating from the official application start with its files only
+        show_foreign_registrations(app, ui);
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button(translate!("history.export_csv", &app.translations)).clicked() {
+            if let Some(path) = rfd::FileDialog::new().set_can_create_directories(true).add_filter("CSV", &["csv"])
+                .set_file_name("e-melder-history.csv").save_file() {
+                let export = CsvExport::default();
+                app.history_export_error = write_csv_export(&path, &export, &export.render_history(&app.history)).err().map(|err| {
+                    log::warn!("failed to export history as CSV, due to {err}");
+                    format!("{}: {err}", path.display())
+                });
+            }
+        }
+        if let Some(history_export_error) = &app.history_export_error {
+            error_label(ui, format!("{} {history_export_error}", translate!("csv_export.error", &app.translations)));
+        }
+    });
+    show_foreign_registrations(app, ui);
+
+    let mut to_attach = None;
+    let mut to_reopen = None;
+    let mut to_show = None;
+    let row_height = app.row_height();
+    ui.push_id("history.table", |ui| {
+        let table = TableBuilder::new(ui).column(Column::auto().at_least(80.0)).columns(Column::auto().at_least(100.0), 2)
+            .columns(Column::auto().at_least(50.0), 2).column(Column::auto().at_least(200.0)).column(Column::auto());
+        let table = app.restore_scroll_offset("history.table", table);
+
+        let output = table.header(20.0, |mut header| {
+            header.col(|ui| {
+                ui.strong(translate!("history.date", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("history.name", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("history.place", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("history.athletes", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("history.invitation", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("history.files", &app.translations));
+            });
+            header.col(|_ui| {});
+        }).body(|mut body| {
+            // the most recent registrations first
+            for (index, entry) in app.history.iter().enumerate().rev() {
+                #[allow(clippy::cast_precision_loss)]
+                body.row(row_height * entry.get_files().len().max(1) as f32, |mut row| {
+                    row.col(|ui| {
+                        ui.label(entry.get_date().format("%d.%m.%Y").to_string());
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(entry.get_name());
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(entry.get_place());
+                    });
+                    row.col(|ui| {
+                        ui.label(entry.get_athletes().len().to_string());
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        if let Some(invitation) = get_invitation_file(&app.config.history_file, entry) {
+                            if ui.button(translate!("history.invitation.open", &app.translations))
+                                .on_hover_text(invitation.display().to_string()).clicked() {
+                                let _ = open::that_detached(invitation);
+                            }
+                        }
+                        else if ui.add_enabled(!app.read_only,
+                            egui::Button::new(translate!("history.invitation.attach", &app.translations))).clicked() {
+                            to_attach = Some(index);
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.vertical(|ui| {
+                            for file in entry.get_files() {
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("{} ({})", file.get_age_category(), file.get_gender_category().render()));
+                                    ui.monospace(file.get_path().display().to_string());
+                                });
+                            }
+                        });
+                    });
+                    row.col(|ui| {
+                        ui.horizontal(|ui| {
+                            if ui.add_enabled(app.registering.writing.is_none(),
+                                egui::Button::new(translate!("history.reopen", &app.translations)))
+                                .on_hover_text(translate!("history.reopen.hover", &app.translations)).clicked() {
+                                to_reopen = Some(index);
+                            }
+                            let details = if entry.get_notes().is_empty() { "history.details" } else { "history.details.notes" };
+                            if ui.button(translate!(details, &app.translations)).clicked() {
+                                to_show = Some(index);
+                            }
+                        });
+                    });
+                });
+            }
+        });
+        app.remember_scroll_offset("history.table", &output);
+    });
+
+    if let Some(index) = to_reopen {
+        app.reopen_history_entry(index);
+    }
+    if let Some(index) = to_show {
+        app.history_details = Some(HistoryDetails { index, attachments: app.get_history_attachments(index), error: None });
+    }
+    show_history_details(app, ui);
+
+    if let Some(index) = to_attach {
+        if let Some(invitation) = rfd::FileDialog::new().add_filter("PDF", &["pdf"]).pick_file() {
+            if let Err(err) = app.set_history_invitation(index, &invitation) {
+                log::warn!("failed to attach invitation, due to {err}");
+            }
+        }
+    }
+}
+
+fn show_foreign_registrations(app: &mut EMelderApp, ui: &mut Ui) {
+    if ui.add_enabled(!app.read_only, egui::Button::new(translate!("history.foreign.scan", &app.translations)))
+        .on_hover_text(translate!("history.foreign.scan.hover", &app.translations)).clicked() {
+        if let Err(err) = app.scan_foreign_registrations() {
+            log::warn!("failed to scan for foreign registrations, due to {err}");
+            app.foreign_registrations = Some(ForeignRegistrations { entries: Vec::new(),
+                errors: vec![translate!(err.translation_key(), &app.translations)] });
+        }
+    }
+    let Some(foreign_registrations) = &app.foreign_registrations else {
+        return;
+    };
+    if foreign_registrations.entries.is_empty() {
+        ui.label(translate!("history.foreign.none", &app.translations));
+    }
+    else {
+        ui.label(translate!("history.foreign.found", &app.translations));
+        for entry in &foreign_registrations.entries {
+            ui.label(format!("{} {} ({}): {} {}", entry.get_date().format("%d.%m.%Y"), entry.get_name(), entry.get_place(),
+                entry.get_athletes().len(), translate!("history.athletes", &app.translations)));
+        }
+    }
+    for error in &foreign_registrations.errors {
+        error_label(ui, error);
+    }
+    let mut import = None;
+    ui.horizontal(|ui| {
+        if !foreign_registrations.entries.is_empty()
+            && ui.button(translate!("history.foreign.import", &app.translations)).clicked() {
+            import = Some(true);
+        }
+        if ui.button(translate!("history.foreign.close", &app.translations)).clicked() {
+            import = Some(false);
+        }
+    });
+    match import {
+        Some(true) => app.import_foreign_registrations(),
+        Some(false) => app.foreign_registrations = None,
+        None => {}
+    }
+    ui.separator();
+}
+
+fn show_history_details(app: &mut EMelderApp, ui: &Ui) {
+    let Some(details) = &mut app.history_details else {
+        return;
+    };
+    let Some(entry) = app.history.get_mut(details.index) else {
+        app.history_details = None;
+        return;
+    };
+    let mut open = true;
+    let mut notes_changed = false;
+    let mut attach = false;
+    let mut to_remove = None;
+    egui::Window::new(format!("{} – {}", entry.get_date().format("%d.%m.%Y"), entry.get_name())).open(&mut open)
+    .collapsible(false).show(ui.ctx(), |ui| {
+        ui.label(translate!("history.details", &app.translations));
+        notes_changed = ui.add_enabled(!app.read_only, egui::TextEdit::multiline(entry.get_notes_mut()).desired_rows(4))
+            .lost_focus();
+
+        ui.separator();
+        ui.label(translate!("history.details.attachments", &app.translations));
+        if details.attachments.is_empty() {
+            ui.label(translate!("history.details.attachments.empty", &app.translations));
+        }
+        for (index, attachment) in details.attachments.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let file_name = attachment.file_name().unwrap_or_default().to_string_lossy();
+                if ui.link(file_name).clicked() {
+                    let _ = open::that_detached(attachment);
+                }
+                if ui.add_enabled(!app.read_only, egui::Button::new("🗑")).clicked() {
+                    to_remove = Some(index);
+                }
+            });
+        }
+        if let Some(error) = &details.error {
+            error_label(ui, error);
+        }
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!app.read_only, egui::Button::new(translate!("history.details.attach", &app.translations))).clicked() {
+                attach = true;
+            }
+            let attachments_dir = get_attachments_dir(&app.config.history_file, entry);
+            if ui.add_enabled(attachments_dir.is_dir(), egui::Button::new(translate!("history.details.open_folder", &app.translations)))
+                .clicked() {
+                let _ = open::that_detached(attachments_dir);
+            }
+        });
+    });
+
+    let index = details.index;
+    let mut error = None;
+    if let Some(attachment) = to_remove.and_then(|to_remove| details.attachments.get(to_remove)) {
+        if let Err(err) = std::fs::remove_file(attachment) {
+            log::warn!("failed to remove attachment, due to {err}");
+            error = Some(err.to_string());
+        }
+    }
+    if attach {
+        for source in rfd::FileDialog::new().pick_files().unwrap_or_default() {
+            if let Err(err) = app.attach_to_history_entry(index, &source) {
+                log::warn!("failed to attach file, due to {err}");
+                error = Some(err.to_string());
+            }
+        }
+    }
+    if to_remove.is_some() || attach {
+        let attachments = app.get_history_attachments(index);
+        app.history_details = Some(HistoryDetails { index, attachments, error });
+    }
+    // the notes are saved when leaving the field or closing the window
+    if notes_changed || !open {
+        app.save_history();
+    }
+    if !open {
+        app.history_details = None;
+    }
+}
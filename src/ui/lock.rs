@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use egui::Ui;
+
+use crate::utils::{translate, Pin};
+use super::EMelderApp;
+use super::theme::warning_label;
+
+impl EMelderApp {
+    /// Records input as activity and locks the app after the configured time without any, returning whether it is locked.
+    pub(super) fn update_auto_lock(&mut self, ctx: &egui::Context) -> bool {
+        if !self.config.auto_lock.enabled {
+            self.locked = false;
+            return false;
+        }
+        let now = Instant::now();
+        if !self.locked && ctx.input(|input| !input.events.is_empty()) {
+            self.last_activity = now;
+        }
+        if self.lock_if_inactive(now) {
+            return true;
+        }
+        // locked without user input, when the app is left alone
+        ctx.request_repaint_after(self.lock_timeout().saturating_sub(now.duration_since(self.last_activity)));
+        false
+    }
+
+    fn lock_timeout(&self) -> Duration {
+        Duration::from_secs(u64::from(self.config.auto_lock.minutes.max(1)) * 60)
+    }
+
+    /// Locks the app, if nothing happened since the configured time before `now`.
+    pub fn lock_if_inactive(&mut self, now: Instant) -> bool {
+        if self.config.auto_lock.enabled && now.duration_since(self.last_activity) >= self.lock_timeout() {
+            self.locked = true;
+        }
+        self.locked
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Unlocks the app with the configured PIN, which is not asked for, if none is set.
+    pub fn unlock(&mut self, pin: &str) -> bool {
+        if self.config.auto_lock.pin.verify(pin) {
+            self.locked = false;
+            self.last_activity = Instant::now();
+        }
+        !self.locked
+    }
+}
+
+pub fn show_lock(app: &mut EMelderApp, ctx: &egui::Context) {
+    // nothing of the athletes is shown behind the prompt
+    egui::CentralPanel::default().show(ctx, |ui| {
+        ui.vertical_centered(|ui| {
+            ui.add_space(ui.available_height() / 3.0);
+            ui.heading(translate!("lock.title", &app.translations));
+            ui.label(translate!("lock.description", &app.translations));
+            let mut unlock = false;
+            if !app.config.auto_lock.pin.is_empty() {
+                let response = ui.add(egui::TextEdit::singleline(&mut app.unlock_input).password(true)
+                    .hint_text(translate!("lock.pin", &app.translations)));
+                response.request_focus();
+                unlock = response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+            }
+            if ui.button(translate!("lock.unlock", &app.translations)).clicked() || unlock {
+                let pin = std::mem::take(&mut app.unlock_input);
+                app.unlock_wrong = !app.unlock(&pin);
+            }
+            if app.unlock_wrong {
+                ui.label(translate!("role.pin_wrong", &app.translations));
+            }
+        });
+    });
+}
+
+pub fn show_auto_lock_config(app: &mut EMelderApp, ui: &mut Ui) {
+    ui.checkbox(&mut app.config.auto_lock.enabled, translate!("config.auto_lock", &app.translations));
+    if !app.config.auto_lock.enabled {
+        return;
+    }
+    ui.horizontal(|ui| {
+        ui.label(translate!("config.auto_lock.minutes", &app.translations));
+        ui.add(egui::DragValue::new(&mut app.config.auto_lock.minutes).range(1..=240));
+    });
+    ui.horizontal(|ui| {
+        ui.label(translate!("config.auto_lock.pin", &app.translations));
+        show_pin_edit(ui, &mut app.lock_pin_edit, &mut app.config.auto_lock.pin, &app.translations);
+    });
+    if app.config.auto_lock.pin.is_empty() {
+        warning_label(ui, translate!("config.auto_lock.no_pin", &app.translations));
+    }
+}
+
+/// Edits a PIN, which cannot be shown, as only its hash is kept, so a new one is entered to replace it.
+pub(super) fn show_pin_edit(ui: &mut Ui, input: &mut String, pin: &mut Pin, translations: &HashMap<String, String>) {
+    let mut edit = egui::TextEdit::singleline(input).password(true);
+    if !pin.is_empty() {
+        edit = edit.hint_text(translate!("config.pin.set", translations));
+    }
+    ui.add(edit);
+    // the PIN is only replaced on purpose, so clearing the field does not remove it
+    if ui.add_enabled(!input.is_empty(), egui::Button::new(translate!("config.pin.apply", translations))).clicked() {
+        *pin = Pin::new(input);
+        input.clear();
+    }
+    if !pin.is_empty() && ui.button(translate!("config.pin.remove", translations)).clicked() {
+        *pin = Pin::default();
+        input.clear();
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use egui::{TextWrapMode, Ui};
+use egui_extras::{Column, TableBuilder};
+
+use crate::tournament_info::{diff_athletes, merge_athletes, Athlete, AthleteDiff, AthleteField,
+    Document};
+use crate::utils::{read_athletes, translate};
+use super::EMelderApp;
+
+#[derive(Debug, Default)]
+pub(super) struct Merge {
+    file: Option<PathBuf>,
+    theirs: Vec<Athlete>,
+    // keyed by the positions in the other file, as its athletes might have no ids and the diff is computed anew each frame
+    excluded: HashSet<usize>
+}
+
+impl EMelderApp {
+    /// Reads the athletes of another file to be merged, e.g. of the laptop of another coach, the file is not written.
+    pub fn load_merge_file(&mut self, file: PathBuf) -> io::Result<()> {
+        self.merge = Merge::default();
+        let theirs = read_athletes(&file)?;
+        self.merge = Merge { file: Some(file), theirs, excluded: HashSet::new() };
+        Ok(())
+    }
+
+    /// The differences of the other file, each with the position of its athlete there.
+    pub fn merge_diffs(&self) -> Vec<(usize, AthleteDiff)> {
+        self.merge.theirs.iter().enumerate().flat_map(|(position, athlete)| {
+            diff_athletes(&self.athletes, std::slice::from_ref(athlete)).into_iter().map(move |diff| (position, diff))
+        }).collect()
+    }
+
+    /// Merges the differences, which were not deselected, into the own athletes.
+    pub fn commit_merge(&mut self) {
+        let excluded = &self.merge.excluded;
+        let diffs: Vec<AthleteDiff> = self.merge_diffs().into_iter().filter(|(position, _)| !excluded.contains(position))
+            .map(|(_, diff)| diff).collect();
+        merge_athletes(&mut self.athletes, diffs);
+        self.save_athletes();
+    }
+}
+
+fn field_translation_key(field: AthleteField) -> &'static str {
+    match field {
+        AthleteField::GivenName => "merge.field.given_name",
+        AthleteField::SurName => "merge.field.sur_name",
+        AthleteField::BirthYear => "merge.field.year",
+        AthleteField::Belt => "merge.field.belt",
+        AthleteField::WeightCategory => "merge.field.weight_category",
+        AthleteField::Gender => "merge.field.gender",
+        AthleteField::Group => "merge.field.group",
+        AthleteField::RefereeLicense => "merge.field.referee_license",
+        AthleteField::ConsentExpiry => "merge.field.consent_expiry",
+        AthleteField::MedicalCertificateExpiry => "merge.field.medical_certificate_expiry"
+    }
+}
+
+fn format_expiry(expiry: Option<NaiveDate>) -> String {
+    expiry.map(|expiry| expiry.format("%d.%m.%Y").to_string()).unwrap_or_default()
+}
+
+fn field_value(athlete: &Athlete, field: AthleteField, translations: &HashMap<String, String>) -> String {
+    match field {
+        AthleteField::GivenName => athlete.get_given_name().to_owned(),
+        AthleteField::SurName => athlete.get_sur_name().to_owned(),
+        AthleteField::BirthYear => athlete.get_birth_year().to_string(),
+        AthleteField::Belt => translate!(&format!("add.belt.{}", athlete.get_belt().serialise()), translations),
+        AthleteField::WeightCategory => athlete.get_weight_category().to_string(),
+        AthleteField::Gender => translate!(&format!("register.table.gender_category.{}", athlete.get_gender().render()), translations),
+        AthleteField::Group => athlete.get_group().to_owned(),
+        AthleteField::RefereeLicense => athlete.get_referee_license().map(|license| {
+            format!("{} ({})", license.get_level(), license.get_expiry().format("%d.%m.%Y"))
+        }).unwrap_or_default(),
+        AthleteField::ConsentExpiry => format_expiry(athlete.get_document_expiry(Document::ParentalConsent)),
+        AthleteField::MedicalCertificateExpiry => format_expiry(athlete.get_document_expiry(Document::MedicalCertificate))
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn show_merge(app: &mut EMelderApp, ui: &mut Ui) {
+    ui.label(translate!("merge.description", &app.translations));
+    ui.horizontal(|ui| {
+        if ui.button(translate!("merge.load", &app.translations)).clicked() {
+            if let Some(file) = rfd::FileDialog::new().add_filter("JSON", &["json"])
+                .set_title(translate!("merge.load", &app.translations)).pick_file() {
+                if let Err(err) = app.load_merge_file(file) {
+                    log::warn!("failed to read athletes to merge, due to {err}");
+                }
+            }
+        }
+        if let Some(file) = &app.merge.file {
+            ui.monospace(file.display().to_string());
+        }
+    });
+
+    if app.merge.file.is_none() {
+        return;
+    }
+
+    let diffs = app.merge_diffs();
+    if diffs.is_empty() {
+        ui.label(translate!("merge.no_differences", &app.translations));
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button(translate!("merge.select_all", &app.translations)).clicked() {
+            app.merge.excluded.clear();
+        }
+        if ui.button(translate!("merge.select_none", &app.translations)).clicked() {
+            app.merge.excluded = diffs.iter().map(|(position, _)| *position).collect();
+        }
+    });
+
+    let row_height = app.row_height();
+    ui.push_id("merge.table", |ui| {
+        let table = TableBuilder::new(ui).column(Column::auto().at_least(30.0)).columns(Column::auto().at_least(100.0), 3)
+            .max_scroll_height(400.0);
+
+        table.header(20.0, |mut header| {
+            header.col(|_ui| {});
+            header.col(|ui| {
+                ui.strong(translate!("merge.kind", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("merge.athlete", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("merge.changes", &app.translations));
+            });
+        }).body(|mut body| {
+            for (position, diff) in &diffs {
+                let lines = match diff {
+                    AthleteDiff::Added(_) => 1,
+                    AthleteDiff::Changed { fields, .. } => fields.len()
+                };
+                #[allow(clippy::cast_precision_loss)]
+                body.row(row_height * lines as f32, |mut row| {
+                    row.col(|ui| {
+                        let mut selected = !app.merge.excluded.contains(position);
+                        if ui.checkbox(&mut selected, "").changed() {
+                            if selected {
+                                app.merge.excluded.remove(position);
+                            }
+                            else {
+                                app.merge.excluded.insert(*position);
+                            }
+                        }
+                    });
+                    row.col(|ui| {
+                        let kind = match diff {
+                            AthleteDiff::Added(_) => "merge.added",
+                            AthleteDiff::Changed { .. } => "merge.changed"
+                        };
+                        ui.label(translate!(kind, &app.translations));
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        let athlete = match diff {
+                            AthleteDiff::Added(athlete) => athlete,
+                            AthleteDiff::Changed { index, .. } => &app.athletes[*index]
+                        };
+                        ui.label(format!("{} {} ({})", athlete.get_given_name(), athlete.get_sur_name(), athlete.get_birth_year()));
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        if let AthleteDiff::Changed { index, theirs, fields } = diff {
+                            ui.vertical(|ui| {
+                                for field in fields {
+                                    ui.label(format!("{} {} → {}", translate!(field_translation_key(*field), &app.translations),
+                                        field_value(&app.athletes[*index], *field, &app.translations),
+                                        field_value(theirs, *field, &app.translations)));
+                                }
+                            });
+                        }
+                    });
+                });
+            }
+        });
+    });
+
+    let selected = diffs.iter().filter(|(position, _)| !app.merge.excluded.contains(position)).count();
+    if ui.add_enabled(!app.read_only && selected > 0,
+        egui::Button::new(format!("{} ({selected})", translate!("merge.commit", &app.translations)))).clicked() {
+        app.commit_merge();
+    }
+}
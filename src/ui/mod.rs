@@ -1,4 +1,33 @@
+mod aged_out;
+mod api;
 pub mod app;
+mod belt_select;
+mod csv_export;
+mod demo;
+mod desktop_integration;
+mod draft;
+mod dual_pane;
+mod email;
+mod exam;
+mod generated;
+mod history;
+mod lock;
+mod merge;
+mod notifications;
+mod paste_import;
+mod plugins;
+mod progress;
 mod registering;
+mod restore;
+mod roster;
+mod save_error;
+mod schedule;
+mod seasons;
+mod shortcuts;
+mod shutdown;
+mod sorting;
+mod theme;
+mod tour;
+mod weight_categories;
 
 pub use app::EMelderApp;
\ No newline at end of file
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::utils::translate;
+use super::EMelderApp;
+use super::theme::error_label;
+
+// identical messages within this time after the last one are coalesced, the toast is shown as long
+const TOAST_DURATION: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+pub(super) struct Toast {
+    message: String,
+    count: usize,
+    last: Instant
+}
+
+impl EMelderApp {
+    /// Shows an error as toast and desktop-notification, repeated errors only increase the count of the toast.
+    /// Returns, whether the error was new.
+    pub fn notify_error(&mut self, message: String) -> bool {
+        let now = Instant::now();
+        if let Some(toast) = self.toasts.iter_mut()
+            .find(|toast| toast.message == message && now.duration_since(toast.last) < TOAST_DURATION) {
+            toast.count += 1;
+            toast.last = now;
+            return false;
+        }
+        if self.notifications {
+            show_desktop_notification(Arc::clone(&self.translations), message.clone());
+        }
+        self.toasts.push(Toast { message, count: 1, last: now });
+        true
+    }
+}
+
+fn show_desktop_notification(translations: Arc<HashMap<String, String>>, body: String) {
+    std::thread::spawn(move || {
+        #[cfg(all(target_family="unix", not(target_os="macos")))]
+        let _ = notify_rust::Notification::new()
+        .summary(&translate!("application.title", &translations))
+        .body(&body)
+        .sound_name("dialog-error")
+        .show().map(|handle| handle.wait_for_action(|_| {}));
+        #[cfg(not(all(target_family="unix", not(target_os="macos"))))]
+        let _ = notify_rust::Notification::new()
+        .summary(&translate!("application.title", &translations))
+        .body(&body)
+        .show();
+    });
+}
+
+pub fn show_toasts(app: &mut EMelderApp, ctx: &egui::Context) {
+    let now = Instant::now();
+    app.toasts.retain(|toast| now.duration_since(toast.last) < TOAST_DURATION);
+    if app.toasts.is_empty() {
+        return;
+    }
+    egui::Area::new(egui::Id::new("toasts")).anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -10.0)).show(ctx, |ui| {
+        for toast in &app.toasts {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                if toast.count > 1 {
+                    error_label(ui, format!("{} (×{})", toast.message, toast.count));
+                }
+                else {
+                    error_label(ui, &toast.message);
+                }
+            });
+        }
+    });
+    // the toasts disappear without user input
+    ctx.request_repaint_after(Duration::from_secs(1));
+}
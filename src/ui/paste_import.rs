@@ -0,0 +1,169 @@
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use egui::{TextWrapMode, Ui};
+use egui_extras::{Column, TableBuilder};
+
+use crate::tournament_info::{parse_pasted_athletes, Athlete};
+use crate::utils::{translate, Progress};
+use super::EMelderApp;
+
+/// The pasted text and its preview, the text is parsed in the background whenever it changes.
+#[derive(Debug, Default)]
+pub(super) struct PasteImport {
+    text: String,
+    parsed_text: String,
+    parsed: Vec<Option<Athlete>>,
+    parsing: Option<ParseTask>
+}
+
+#[derive(Debug)]
+struct ParseTask {
+    text: String,
+    progress: Arc<Progress>,
+    handle: JoinHandle<Vec<Option<Athlete>>>
+}
+
+impl PasteImport {
+    pub(super) fn is_parsing(&self) -> bool {
+        self.parsing.as_ref().is_some_and(|task| !task.handle.is_finished())
+    }
+
+    pub(super) fn finish_parsing(&mut self) {
+        if !self.parsing.as_ref().is_some_and(|task| task.handle.is_finished()) {
+            return;
+        }
+        let Some(task) = self.parsing.take() else {
+            return;
+        };
+        let parsed = task.handle.join().unwrap_or_default();
+        // a parse of an outdated text is dropped, the current text is parsed anew
+        if task.text == self.text {
+            // a cancelled parse is incomplete, so nothing is imported until the text is changed
+            self.parsed = if task.progress.is_cancelled() { Vec::new() } else { parsed };
+            self.parsed_text = task.text;
+        }
+    }
+
+    fn update_parsing(&mut self) {
+        self.finish_parsing();
+        match &self.parsing {
+            Some(task) if task.text != self.text => task.progress.cancel(),
+            Some(_) => {}
+            None if self.text != self.parsed_text => {
+                let text = self.text.clone();
+                let progress = Arc::new(Progress::default());
+                let handle = {
+                    let text = text.clone();
+                    let progress = Arc::clone(&progress);
+                    std::thread::spawn(move || parse_pasted_athletes(&text, &progress))
+                };
+                self.parsing = Some(ParseTask { text, progress, handle });
+            }
+            None => {}
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn show_paste_import(app: &mut EMelderApp, ui: &Ui) {
+    let row_height = app.row_height();
+    let Some(paste_import) = &mut app.paste_import else {
+        return;
+    };
+    paste_import.update_parsing();
+
+    let mut close = false;
+    let mut import = false;
+    egui::Window::new(translate!("add.paste.title", &app.translations))
+    .collapsible(false).show(ui.ctx(), |ui| {
+        ui.label(translate!("add.paste.text", &app.translations));
+        ui.add(egui::TextEdit::multiline(&mut paste_import.text).desired_rows(6).code_editor());
+
+        let parsed = &paste_import.parsed;
+        let valid = parsed.iter().flatten().count();
+        ui.separator();
+        if let Some(task) = &paste_import.parsing {
+            ui.horizontal(|ui| {
+                ui.add(egui::ProgressBar::new(task.progress.fraction()).show_percentage().animate(true));
+                if ui.add_enabled(!task.progress.is_cancelled(), egui::Button::new(translate!("progress.cancel", &app.translations))).clicked() {
+                    task.progress.cancel();
+                }
+            });
+            // keep the progress updating without user input
+            ui.ctx().request_repaint();
+        }
+        ui.push_id("add.paste.table", |ui| {
+            let table = TableBuilder::new(ui).columns(Column::auto().at_least(100.0), 5).max_scroll_height(200.0);
+
+            table.header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong(translate!("add.given_name", &app.translations));
+                });
+                header.col(|ui| {
+                    ui.strong(translate!("add.sur_name", &app.translations));
+                });
+                header.col(|ui| {
+                    ui.strong(translate!("add.year", &app.translations));
+                });
+                header.col(|ui| {
+                    ui.strong(translate!("add.belt", &app.translations));
+                });
+                header.col(|ui| {
+                    ui.strong(translate!("add.gender", &app.translations));
+                });
+            }).body(|mut body| {
+                for (index, athlete) in parsed.iter().enumerate() {
+                    body.row(row_height, |mut row| {
+                        let Some(athlete) = athlete else {
+                            row.col(|ui| {
+                                ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                                ui.label(format!("{} {}", translate!("add.paste.invalid", &app.translations), index + 1));
+                            });
+                            return;
+                        };
+                        row.col(|ui| {
+                            ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                            ui.label(athlete.get_given_name());
+                        });
+                        row.col(|ui| {
+                            ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                            ui.label(athlete.get_sur_name());
+                        });
+                        row.col(|ui| {
+                            ui.label(athlete.get_birth_year().to_string());
+                        });
+                        row.col(|ui| {
+                            ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                            ui.label(translate!(&format!("add.belt.{}", athlete.get_belt().serialise()), &app.translations));
+                        });
+                        row.col(|ui| {
+                            ui.label(translate!(&format!("register.table.gender_category.{}", athlete.get_gender().render()), &app.translations));
+                        });
+                    });
+                }
+            });
+        });
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(valid > 0 && paste_import.parsing.is_none(), egui::Button::new(
+                format!("{} ({valid})", translate!("add.paste.import", &app.translations)))).clicked() {
+                import = true;
+            }
+            if ui.button(translate!("add.paste.cancel", &app.translations)).clicked() {
+                close = true;
+            }
+        });
+    });
+
+    if import {
+        let athletes = std::mem::take(&mut paste_import.parsed);
+        // invalid lines are left out, they are shown in the preview
+        app.athletes.extend(athletes.into_iter().flatten());
+        app.save_athletes();
+        close = true;
+    }
+    if close {
+        app.paste_import = None;
+    }
+}
@@ -0,0 +1,96 @@
+use std::thread::JoinHandle;
+
+use egui::Ui;
+
+use crate::error::Error;
+use crate::exporter::{discover_plugins, Exporter, Registration, PLUGIN_TIMEOUT};
+use crate::utils::{get_plugins_dir, translate};
+use super::EMelderApp;
+use super::theme::error_label;
+
+/// An exporter running in the background, so a slow plugin does not freeze the app.
+#[derive(Debug)]
+pub(super) struct PluginTask {
+    name: String,
+    handle: JoinHandle<Result<(), Error>>
+}
+
+impl PluginTask {
+    pub(super) fn is_running(&self) -> bool {
+        !self.handle.is_finished()
+    }
+}
+
+pub(super) fn finish_plugin(app: &mut EMelderApp) {
+    if !app.registering.plugin_task.as_ref().is_some_and(|task| task.handle.is_finished()) {
+        return;
+    }
+    let Some(task) = app.registering.plugin_task.take() else {
+        return;
+    };
+    let result = task.handle.join().unwrap_or_else(|_| Err(Error::Validation(format!("{} panicked", task.name))));
+    app.registering.plugin_error = result.err().map(|err| {
+        log::warn!("failed to run exporter {}, due to {err}", task.name);
+        err.to_string()
+    });
+}
+
+/// Runs the exporters installed by the club in the plugins-directory on the current registration.
+pub fn show_plugins(app: &mut EMelderApp, ui: &mut Ui) {
+    finish_plugin(app);
+    let plugins = app.registering.plugins.get_or_insert_with(|| {
+        get_plugins_dir().and_then(|plugins_dir| discover_plugins(&plugins_dir)).unwrap_or_else(|err| {
+            log::warn!("failed to discover plugins, due to {err}");
+            app.registering.plugin_error = Some(translate!(err.translation_key(), &app.translations));
+            Vec::new()
+        })
+    });
+    if plugins.is_empty() {
+        // the plugins-directory could not be read
+        if let Some(plugin_error) = &app.registering.plugin_error {
+            error_label(ui, format!("{} {plugin_error}", translate!("plugins.error", &app.translations)));
+        }
+        return;
+    }
+    let selected = app.registering.plugin.min(plugins.len() - 1);
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_label(translate!("plugins.exporter", &app.translations))
+        .selected_text(plugins[selected].name())
+        .show_ui(ui, |ui| {
+            for (index, plugin) in plugins.iter().enumerate() {
+                ui.selectable_value(&mut app.registering.plugin, index, plugin.name());
+            }
+        });
+        let running = app.registering.plugin_task.is_some();
+        if ui.add_enabled(!running && !app.registering.athletes.is_empty(),
+            egui::Button::new(translate!("plugins.run", &app.translations))).clicked() {
+            let club = app.club.for_department(app.registering.department);
+            let registration = Registration {
+                name: &app.registering.name, place: &app.registering.place, date: app.registering.date, club: &club,
+                athletes: &app.registering.athletes
+            };
+            app.registering.plugin_error = None;
+            match registration.to_json() {
+                Ok(input) => {
+                    let plugin = plugins[selected].clone();
+                    let output_dir = app.config.tournament_basedir.clone();
+                    let handle = std::thread::spawn(move || plugin.run(input, &output_dir, PLUGIN_TIMEOUT));
+                    app.registering.plugin_task = Some(PluginTask { name: plugins[selected].name(), handle });
+                }
+                Err(err) => {
+                    log::warn!("failed to run exporter {}, due to {err}", plugins[selected].name());
+                    app.registering.plugin_error = Some(err.to_string());
+                }
+            }
+        }
+        if running {
+            ui.spinner();
+            ui.label(translate!("plugins.running", &app.translations));
+            // keep checking for the plugin to finish without user input
+            ui.ctx().request_repaint_after(std::time::Duration::from_millis(100));
+        }
+    });
+    if let Some(plugin_error) = &app.registering.plugin_error {
+        error_label(ui, format!("{} {plugin_error}", translate!("plugins.error", &app.translations)));
+    }
+}
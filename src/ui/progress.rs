@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use egui::Ui;
+
+use crate::utils::{translate, ItemState, Progress};
+use super::theme::error_label;
+
+pub fn show_progress(ctx: &egui::Context, title: &str, progress: &Progress, translations: &HashMap<String, String>) {
+    egui::Window::new(title).collapsible(false).resizable(false).show(ctx, |ui| {
+        ui.add(egui::ProgressBar::new(progress.fraction()).show_percentage().animate(true));
+        show_progress_items(ui, &progress.items());
+        if ui.add_enabled(!progress.is_cancelled(), egui::Button::new(translate!("progress.cancel", translations))).clicked() {
+            progress.cancel();
+        }
+    });
+    // keep the progress updating without user input
+    ctx.request_repaint();
+}
+
+pub fn show_progress_items(ui: &mut Ui, items: &[(String, ItemState)]) {
+    if items.is_empty() {
+        return;
+    }
+    egui::ScrollArea::vertical().max_height(200.0).id_salt("items").show(ui, |ui| {
+        for (item, state) in items {
+            match state {
+                ItemState::Pending => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(item);
+                    });
+                }
+                ItemState::Done => {
+                    ui.label(format!("✔ {item}"));
+                }
+                ItemState::Failed(message) => {
+                    error_label(ui, format!("{item}: {message}"));
+                }
+            }
+        }
+    });
+}
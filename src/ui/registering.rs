@@ -1,113 +1,276 @@
-use egui::{TextWrapMode, Ui};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use chrono::{Datelike, Local};
+use egui::{Key, Modifiers, PopupCloseBehavior, Response, TextWrapMode, Ui};
 use egui_extras::{Column, TableBuilder};
+use uuid::Uuid;
 
-use crate::tournament_info::{registering_athletes_to_tournaments, RegisteringAthlete};
-use crate::utils::{LEGAL_GENDER_CATEGORIES, translate, write_tournaments};
+use crate::error::Error;
+use crate::export::csv::write_entry_list_csv;
+use crate::export::ical::write_calendar_event;
+use crate::export::pdf::write_entry_list;
+use crate::tournament_info::{check_tournament_date, find_conflicts, find_duplicate_registrations, find_unofficial_weight_categories, group_registering_athletes, has_start_in_season, normalize_age_category, normalize_name, parse_invitation,
+    registering_athletes_to_tournaments, registering_teams_to_tournaments, render_fee, sanitize_team_name, suggest_clubs, Club, Competition, DirectoryClub, Document, GeneratedFile, HistoryEntry, RegisteringAthlete, SortColumn, Tournament, UnusualDate, WeightCategory};
+use crate::utils::{copy_invitation, existing_tournament_files, get_attachments_dir, get_tournament_file_name, hash_file, normalize_phone, read_invitation_text, LEGAL_GENDER_CATEGORIES, translate, write_generated_files,
+    write_history, write_tournaments, ExistingFiles, Progress};
 use super::EMelderApp;
+use super::app::{show_phone_preview, OpenFolder, RegisteringLayout};
+use super::csv_export::show_csv_export;
+use super::demo::show_demo_button;
+use super::draft::show_draft;
+use super::dual_pane::show_dual_pane;
+use super::email::{prepare_registration_email, show_registration_email};
+use super::plugins::show_plugins;
+use super::progress::{show_progress, show_progress_items};
+use super::shortcuts::{shortcut_hint, REGISTER};
+use super::sorting::sortable_header;
+use super::theme::{category_color, error_label, mark_invalid, tint_cell, warning_label};
+use super::tour::{show_tour, TourStep};
 
 enum Written {
     Successful,
-    Error,
+    Error(String),
     InvalidWeightCategory
 }
 
 #[allow(clippy::too_many_lines, clippy::module_name_repetitions)]
 pub fn show_registering(app: &mut EMelderApp, ui: &mut Ui) {
+    // both are part of the file-names, which would otherwise only consist of the category
     ui.horizontal(|ui| {
         ui.label(translate!("register.name", &app.translations));
-        ui.text_edit_singleline(&mut app.registering.name);
+        if app.registering.name.trim().is_empty() {
+            mark_invalid(ui);
+            ui.text_edit_singleline(&mut app.registering.name).on_hover_text(translate!("register.name.missing", &app.translations));
+        }
+        else {
+            ui.text_edit_singleline(&mut app.registering.name);
+        }
     });
 
     ui.horizontal(|ui| {
         ui.label(translate!("register.place", &app.translations));
-        ui.text_edit_singleline(&mut app.registering.place);
+        let response = if app.registering.place.trim().is_empty() {
+            mark_invalid(ui);
+            ui.text_edit_singleline(&mut app.registering.place).on_hover_text(translate!("register.place.missing", &app.translations))
+        }
+        else {
+            ui.text_edit_singleline(&mut app.registering.place)
+        };
+        show_place_suggestions(app, ui, &response);
     });
 
     ui.horizontal(|ui| {
         ui.label(translate!("register.date", &app.translations));
         ui.add(egui_extras::DatePickerButton::new(&mut app.registering.date).format("%d.%m.%Y"));
+        if let Some(unusual_date) = check_tournament_date(app.registering.date, Local::now().date_naive()) {
+            warning_label(ui, translate!(unusual_date_translation_key(unusual_date), &app.translations));
+        }
     });
 
-    if ui.button(translate!("register.register", &app.translations)).clicked() {
-        let tournaments = registering_athletes_to_tournaments(
-            &app.registering.athletes, &app.registering.name, app.registering.date,
-            &app.registering.place, &app.club);
-        
-        let written = if let Some(tournaments) = tournaments {
-            match write_tournaments(&tournaments, &app.config) {
-                Ok(()) => {
-                    Written::Successful
+    ui.horizontal(|ui| {
+        ui.label(translate!("register.coach", &app.translations));
+        ui.text_edit_singleline(app.registering.coach.get_name_mut());
+        ui.label(translate!("register.coach.phone", &app.translations));
+        ui.text_edit_singleline(app.registering.coach.get_phone_mut());
+        show_phone_preview(ui, app.registering.coach.get_phone(), &app.translations);
+    }).response.on_hover_text(translate!("register.coach.hover", &app.translations));
+
+    show_invitation(app, ui);
+
+    if !app.club.get_departments().is_empty() {
+        let selected_text = app.registering.department.and_then(|department| app.club.get_departments().get(department))
+            .map_or_else(|| translate!("register.department.none", &app.translations), |department| department.get_name().to_owned());
+        egui::ComboBox::from_label(translate!("register.department", &app.translations))
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut app.registering.department, None, translate!("register.department.none", &app.translations));
+            for (index, department) in app.club.get_departments().iter().enumerate() {
+                ui.selectable_value(&mut app.registering.department, Some(index), department.get_name());
+            }
+        });
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(translate!("register.competition", &app.translations));
+        ui.selectable_value(&mut app.registering.competition, Competition::Individual,
+            translate!("register.competition.individual", &app.translations));
+        ui.selectable_value(&mut app.registering.competition, Competition::Team,
+            translate!("register.competition.team", &app.translations))
+            .on_hover_text(translate!("register.competition.team.hover", &app.translations));
+    });
+
+    let response = ui.add_enabled(can_register(app), egui::Button::new(translate!("register.register", &app.translations)))
+        .on_hover_text(shortcut_hint(app, ui, "shortcut.register", &REGISTER));
+    app.tour.highlight(TourStep::Register, &response);
+    if missing_tournament(app) && !app.registering.athletes.is_empty() {
+        error_label(ui, translate!("register.tournament.missing", &app.translations));
+    }
+    if response.clicked() {
+        start_registering(app);
+    }
+
+    if ui.checkbox(&mut app.config.entry_list_pdf, translate!("register.entry_list_pdf", &app.translations))
+        .on_hover_text(translate!("register.entry_list_pdf.hover", &app.translations)).changed() {
+        app.save_configs();
+    }
+
+    show_csv_export(app, ui);
+    show_draft(app, ui);
+    show_plugins(app, ui);
+    show_unusual_date(app, ui);
+
+    if let Some(default_weight_categories) = &app.registering.default_weight_categories {
+        let mut register_anyway = None;
+        egui::Window::new(translate!("register.default_weight.title", &app.translations))
+        .collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+            ui.label(translate!("register.default_weight.text", &app.translations));
+            for athlete in default_weight_categories {
+                ui.label(athlete);
+            }
+            ui.horizontal(|ui| {
+                if ui.button(translate!("register.default_weight.register_anyway", &app.translations)).clicked() {
+                    register_anyway = Some(true);
                 }
-                Err(err) => {
-                    log::warn!("failed to write tournaments, due to {err}");
-                    Written::Error
+                if ui.button(translate!("register.default_weight.correct", &app.translations)).clicked() {
+                    register_anyway = Some(false);
                 }
+            });
+        });
+
+        if let Some(register_anyway) = register_anyway {
+            app.registering.default_weight_categories = None;
+            if register_anyway {
+                check_weight_categories(app);
             }
-        } else { Written::InvalidWeightCategory };
-
-        match written {
-            Written::Successful => {
-                let tournament_basedir = app.config.tournament_basedir.clone();
-                #[cfg(all(target_family="unix", not(target_os="macos")))]
-                let translations = app.translations.clone();
-                #[cfg(all(target_family="unix", not(target_os="macos")))]
-                std::thread::spawn(move || {
-                    let _ = notify_rust::Notification::new()
-                    .summary(&translate!("application.title", &translations))
-                    .body(&translate!("register.notification.ask", &translations))
-                    .sound_name("dialog-question")
-                    .action("yes", &translate!("register.notification.yes", &translations))
-                    .action("no", &translate!("register.notification.no", &translations))
-                    .show().map(|handle| {
-                        handle.wait_for_action(|action| {
-                            if action == "yes" {
-                                let _ = open::that_detached(tournament_basedir);
-                            }
-                        });
-                    });
-                });
+        }
+    }
 
-                #[cfg(any(not(target_family="unix"), target_os="macos"))]
-                let _ = open::that_detached(tournament_basedir);
-            }
-            Written::Error => {
-                let translations = app.translations.clone();
-                std::thread::spawn(move || {
-                    #[cfg(all(target_family="unix", not(target_os="macos")))]
-                    let _ = notify_rust::Notification::new()
-                    .summary(&translate!("application.title", &translations))
-                    .body(&translate!("register.notification.io_error", &translations))
-                    .sound_name("dialog-error")
-                    .show().map(|handle| handle.wait_for_action(|_| {}));
-                    #[cfg(not(all(target_family="unix", not(target_os="macos"))))]
-                    let _ = notify_rust::Notification::new()
-                    .summary(&translate!("application.title", &translations))
-                    .body(&translate!("register.notification.io_error", &translations))
-                    .show();
-                });
+    if let Some(unofficial_weight_categories) = &app.registering.unofficial_weight_categories {
+        let mut correct = None;
+        egui::Window::new(translate!("register.unofficial_weight.title", &app.translations))
+        .collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+            ui.label(translate!("register.unofficial_weight.text", &app.translations));
+            for (index, nearest) in unofficial_weight_categories {
+                let Some(athlete) = app.registering.athletes.get(*index) else {
+                    continue;
+                };
+                ui.label(format!("{} {} ({}, {}): {} → {nearest}", athlete.get_given_name(), athlete.get_sur_name(),
+                    athlete.get_age_category(),
+                    translate!(&format!("register.table.gender_category.{}", athlete.get_gender_category().render()), &app.translations),
+                    athlete.get_weight_category()));
             }
-            Written::InvalidWeightCategory => {
-                let translations = app.translations.clone();
-                std::thread::spawn(move || {
-                    #[cfg(all(target_family="unix", not(target_os="macos")))]
-                    let _ = notify_rust::Notification::new()
-                    .summary(&translate!("application.title", &translations))
-                    .body(&translate!("register.notification.invalid_weight_category", &translations))
-                    .sound_name("dialog-error")
-                    .show().map(|handle| handle.wait_for_action(|_| {}));
-                    #[cfg(not(all(target_family="unix", not(target_os="macos"))))]
-                    let _ = notify_rust::Notification::new()
-                    .summary(&translate!("application.title", &translations))
-                    .body(&translate!("register.notification.invalid_weight_category", &translations))
-                    .show();
-                });
+            ui.horizontal(|ui| {
+                if ui.button(translate!("register.unofficial_weight.use_nearest", &app.translations)).clicked() {
+                    correct = Some(UnofficialWeightAction::UseNearest);
+                }
+                if ui.button(translate!("register.unofficial_weight.register_anyway", &app.translations)).clicked() {
+                    correct = Some(UnofficialWeightAction::RegisterAnyway);
+                }
+                if ui.button(translate!("register.unofficial_weight.correct", &app.translations)).clicked() {
+                    correct = Some(UnofficialWeightAction::Correct);
+                }
+            });
+        });
+
+        if let Some(correct) = correct {
+            let unofficial_weight_categories = app.registering.unofficial_weight_categories.take().unwrap_or_default();
+            match correct {
+                UnofficialWeightAction::UseNearest => {
+                    for (index, nearest) in unofficial_weight_categories {
+                        if let Some(athlete) = app.registering.athletes.get_mut(index) {
+                            nearest.clone_into(athlete.get_weight_category_mut());
+                        }
+                    }
+                    check_conflicts(app);
+                }
+                UnofficialWeightAction::RegisterAnyway => check_conflicts(app),
+                UnofficialWeightAction::Correct => {}
+            }
+        }
+    }
+
+    if let Some(conflicts) = &app.registering.conflicts {
+        let mut register_anyway = None;
+        egui::Window::new(translate!("register.conflict.title", &app.translations))
+        .collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+            ui.label(translate!("register.conflict.text", &app.translations));
+            for conflict in conflicts {
+                ui.label(conflict);
+            }
+            ui.horizontal(|ui| {
+                if ui.button(translate!("register.conflict.register_anyway", &app.translations)).clicked() {
+                    register_anyway = Some(true);
+                }
+                if ui.button(translate!("register.conflict.cancel", &app.translations)).clicked() {
+                    register_anyway = Some(false);
+                }
+            });
+        });
+
+        if let Some(register_anyway) = register_anyway {
+            app.registering.conflicts = None;
+            if register_anyway {
+                check_existing_files(app);
+            }
+        }
+    }
+
+    if let Some(existing_files) = &app.registering.existing_files {
+        let mut existing = None;
+        egui::Window::new(translate!("register.existing.title", &app.translations))
+        .collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+            ui.label(translate!("register.existing.text", &app.translations));
+            for file_name in existing_files {
+                ui.monospace(file_name);
+            }
+            ui.horizontal(|ui| {
+                if ui.button(translate!("register.existing.keep", &app.translations)).clicked() {
+                    existing = Some(Some(ExistingFiles::Keep));
+                }
+                if ui.button(translate!("register.existing.overwrite", &app.translations)).clicked() {
+                    existing = Some(Some(ExistingFiles::Overwrite));
+                }
+                if ui.button(translate!("register.existing.rename", &app.translations)).clicked() {
+                    existing = Some(Some(ExistingFiles::Rename));
+                }
+                if ui.button(translate!("register.existing.cancel", &app.translations)).clicked() {
+                    existing = Some(None);
+                }
+            });
+        });
+
+        if let Some(existing) = existing {
+            app.registering.existing_files = None;
+            if let Some(existing) = existing {
+                register(app, existing);
             }
         }
     }
 
     ui.separator();
 
-    show_table_registering_adding(app, ui);
+    // new users can try the registering without entering their athletes first
+    if app.athletes.is_empty() {
+        show_demo_button(app, ui);
+    }
+    ui.horizontal(|ui| {
+        ui.label(translate!("register.layout", &app.translations));
+        let stacked = ui.selectable_value(&mut app.config.registering_layout, RegisteringLayout::Stacked,
+            translate!("register.layout.stacked", &app.translations));
+        let dual_pane = ui.selectable_value(&mut app.config.registering_layout, RegisteringLayout::DualPane,
+            translate!("register.layout.dual_pane", &app.translations))
+            .on_hover_text(translate!("register.layout.dual_pane.hover", &app.translations));
+        if stacked.changed() || dual_pane.changed() {
+            app.save_configs();
+        }
+    });
+    match app.config.registering_layout {
+        RegisteringLayout::Stacked => show_table_registering_adding(app, ui),
+        RegisteringLayout::DualPane => show_dual_pane(app, ui)
+    }
 
     ui.separator();
 
@@ -115,19 +278,810 @@ pub fn show_registering(app: &mut EMelderApp, ui: &mut Ui) {
         ui.label(translate!("register.table.empty", &app.translations));
     }
     else {
+        show_lapsed_documents(app, ui);
+        show_duplicate_registrations(app, ui);
+        if missing_team(app) {
+            warning_label(ui, translate!("register.team.missing", &app.translations));
+        }
         show_table_registering(app, ui);
+        show_files_summary(app, ui);
+        if app.config.fees.is_configured() {
+            ui.label(format!("{} {}", translate!("register.fees.total", &app.translations),
+                render_fee(app.config.fees.total(&app.registering.athletes))));
+        }
+        ui.separator();
+        show_groups(app, ui);
+        ui.separator();
+        show_preview(app, ui);
+    }
+    show_tour(app, ui.ctx());
+}
+
+fn lapsed_translation_key(document: Document) -> &'static str {
+    match document {
+        Document::ParentalConsent => "register.lapsed.consent",
+        Document::MedicalCertificate => "register.lapsed.medical_certificate"
+    }
+}
+
+fn show_lapsed_documents(app: &EMelderApp, ui: &mut Ui) {
+    // checked against the date of the tournament, as it might be changed after adding the athletes
+    for registering_athlete in &app.registering.athletes {
+        let Some(athlete) = app.athletes.iter().find(|athlete| athlete.is_same_athlete(registering_athlete)) else {
+            continue;
+        };
+        for document in athlete.lapsed_documents(app.registering.date) {
+            warning_label(ui, format!("{} {}: {}", athlete.get_given_name(), athlete.get_sur_name(),
+                translate!(lapsed_translation_key(document), &app.translations)));
+        }
+    }
+}
+
+fn show_duplicate_registrations(app: &EMelderApp, ui: &mut Ui) {
+    for index in find_duplicate_registrations(&app.registering.athletes) {
+        let athlete = &app.registering.athletes[index];
+        warning_label(ui, format!("{} {}: {}", athlete.get_given_name(), athlete.get_sur_name(),
+            translate!("register.duplicate", &app.translations)));
+    }
+}
+
+fn missing_tournament(app: &EMelderApp) -> bool {
+    app.registering.name.trim().is_empty() || app.registering.place.trim().is_empty()
+}
+
+fn missing_team(app: &EMelderApp) -> bool {
+    app.registering.competition == Competition::Team
+        && app.registering.athletes.iter().any(|athlete| sanitize_team_name(athlete.get_team()).is_empty())
+}
+
+/// Whether the register-button is enabled, its shortcut is only accepted then as well.
+pub(super) fn can_register(app: &EMelderApp) -> bool {
+    app.registering.writing.is_none() && find_duplicate_registrations(&app.registering.athletes).is_empty() && !missing_tournament(app)
+        && !missing_team(app)
+}
+
+/// Starts the checks of the date, the weight-categories and the conflicts, the files are written after them.
+pub(super) fn start_registering(app: &mut EMelderApp) {
+    app.advance_tour(TourStep::Register);
+    check_date(app);
+}
+
+fn show_invitation(app: &mut EMelderApp, ui: &mut Ui) {
+    finish_reading_invitation(app);
+    ui.horizontal(|ui| {
+        ui.label(translate!("register.invitation", &app.translations));
+        if let Some(invitation) = &app.registering.invitation {
+            if ui.link(invitation.file_name().unwrap_or_default().to_string_lossy()).on_hover_text(invitation.display().to_string())
+                .clicked() {
+                let _ = open::that_detached(invitation);
+            }
+            if app.registering.invitation_reading.is_some() {
+                ui.spinner();
+            }
+            if ui.small_button("✖").on_hover_text(translate!("register.invitation.remove", &app.translations)).clicked() {
+                app.registering.invitation = None;
+                app.registering.invitation_error = None;
+                // the text of a removed invitation is not applied anymore
+                app.registering.invitation_reading = None;
+            }
+        }
+        else if ui.button(translate!("register.invitation.attach", &app.translations)).clicked() {
+            if let Some(invitation) = rfd::FileDialog::new().add_filter("PDF", &["pdf"]).pick_file() {
+                attach_invitation(app, invitation);
+            }
+        }
+    });
+    if let Some(invitation_error) = &app.registering.invitation_error {
+        warning_label(ui, invitation_error);
+    }
+}
+
+fn attach_invitation(app: &mut EMelderApp, invitation: PathBuf) {
+    let path = invitation.clone();
+    app.registering.invitation_reading = Some(std::thread::spawn(move || read_invitation_text(path)));
+    app.registering.invitation_error = None;
+    app.registering.invitation = Some(invitation);
+}
+
+fn finish_reading_invitation(app: &mut EMelderApp) {
+    if !app.registering.invitation_reading.as_ref().is_some_and(JoinHandle::is_finished) {
+        return;
+    }
+    let Some(reading) = app.registering.invitation_reading.take() else {
+        return;
+    };
+    let result = reading.join().unwrap_or_else(|_| Err(Error::Validation(String::from("panic while reading the invitation"))));
+    // the invitation stays attached, even if its text cannot be read, e.g. for scanned documents
+    match result {
+        Ok(text) => {
+            let metadata = parse_invitation(&text);
+            if let Some(date) = metadata.date {
+                app.registering.date = date;
+            }
+            if let Some(place) = metadata.place {
+                app.registering.place = place;
+            }
+            app.registering.invitation_error = None;
+        }
+        Err(err) => {
+            log::warn!("failed to read invitation, due to {err}");
+            app.registering.invitation_error = Some(translate!("register.invitation.unreadable", &app.translations));
+        }
+    }
+}
+
+/// One entry per file to be written with its count of athletes, tinted as the rows of the table.
+fn show_files_summary(app: &EMelderApp, ui: &mut Ui) {
+    let groups = group_registering_athletes(&app.registering.athletes);
+    ui.horizontal_wrapped(|ui| {
+        ui.label(format!("{} ({}):", translate!("register.summary", &app.translations), groups.len()));
+        for (age_category, gender_category, indices) in &groups {
+            let color = category_color(age_category, gender_category.render());
+            ui.label(egui::RichText::new(format!("{age_category} {} ({})", gender_category.render(), indices.len())).background_color(color));
+        }
+    });
+}
+
+fn show_groups(app: &mut EMelderApp, ui: &mut Ui) {
+    ui.strong(translate!("register.groups", &app.translations));
+    ui.label(translate!("register.groups.text", &app.translations));
+
+    let mut moved = None;
+    for (age_category, gender_category, indices) in group_registering_athletes(&app.registering.athletes) {
+        // the file-name might be shortened or escaped, so it is shown as it will be written
+        let tournament = Tournament::new(app.registering.name.clone(), app.registering.date, app.registering.place.clone(),
+            age_category.clone(), gender_category, Club::default(), Vec::new());
+        let file_name = get_tournament_file_name(&app.config.tournament_basedir, &app.config.file_name_template, &tournament)
+            .unwrap_or_else(|_| translate!("register.groups.path_too_long", &app.translations));
+        let title = format!("{} ({}): {} – {file_name}", age_category,
+            translate!(&format!("register.table.gender_category.{}", gender_category.render()), &app.translations), indices.len());
+        egui::CollapsingHeader::new(title).id_salt(("register.groups", &age_category, gender_category.render()))
+        .default_open(true).show(ui, |ui| {
+            let (_, dropped) = ui.dnd_drop_zone::<usize, ()>(egui::Frame::default().inner_margin(4.0), |ui| {
+                // the drop-zone paints its own background, so the colour of the table is shown inside
+                egui::Frame::default().inner_margin(4.0).fill(category_color(&age_category, gender_category.render())).show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    for index in indices {
+                        let Some(athlete) = app.registering.athletes.get(index) else {
+                            continue;
+                        };
+                        ui.dnd_drag_source(egui::Id::new(("register.groups.athlete", index)), index, |ui| {
+                            ui.label(format!("{} {}", athlete.get_given_name(), athlete.get_sur_name()));
+                        });
+                    }
+                });
+            });
+            if let Some(index) = dropped {
+                moved = Some((*index, age_category.clone(), gender_category));
+            }
+        });
+    }
+
+    if let Some((index, age_category, gender_category)) = moved {
+        if let Some(athlete) = app.registering.athletes.get_mut(index) {
+            // the athlete keeps the group, if it may not start in the gender category of the target-group
+            if LEGAL_GENDER_CATEGORIES[athlete.get_gender()].contains(&gender_category) {
+                *athlete.get_age_category_mut() = age_category;
+                *athlete.get_gender_category_mut() = gender_category;
+            }
+        }
+    }
+}
+
+fn show_preview(app: &EMelderApp, ui: &mut Ui) {
+    // rendered only while opened, as the files are rendered anew every frame
+    egui::CollapsingHeader::new(translate!("register.preview", &app.translations)).id_salt("register_preview").show(ui, |ui| {
+        let Some(tournaments) = to_tournaments(app) else {
+            let reason = if missing_team(app) { "register.team.missing" } else { "register.preview.invalid_weight_category" };
+            warning_label(ui, translate!(reason, &app.translations));
+            return;
+        };
+        for tournament in tournaments {
+            let file_name = get_tournament_file_name(&app.config.tournament_basedir, &app.config.file_name_template, &tournament)
+                .unwrap_or_else(|_| translate!("register.groups.path_too_long", &app.translations));
+            egui::CollapsingHeader::new(file_name)
+            .id_salt(("register_preview", tournament.get_age_category(), tournament.get_gender_category().render()))
+            .show(ui, |ui| {
+                let text = tournament.render();
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    // read-only, but still selectable for copying
+                    ui.add(egui::TextEdit::multiline(&mut text.as_str()).code_editor().desired_width(f32::INFINITY));
+                });
+            });
+        }
+    });
+}
+
+const MAX_SUGGESTIONS: usize = 5;
+
+fn place_suggestions<'a>(history: &'a [HistoryEntry], place: &str) -> Vec<&'a str> {
+    // the most recently used places are the most likely ones
+    let lowercase_place = place.to_lowercase();
+    let mut ret: Vec<&str> = Vec::new();
+    for entry in history.iter().rev() {
+        let suggestion = entry.get_place();
+        if suggestion != place && suggestion.to_lowercase().starts_with(&lowercase_place) && !ret.contains(&suggestion) {
+            ret.push(suggestion);
+        }
+    }
+    ret.truncate(MAX_SUGGESTIONS);
+    ret
+}
+
+fn show_place_suggestions(app: &mut EMelderApp, ui: &Ui, response: &Response) {
+    let popup_id = ui.make_persistent_id("register.place.suggestions");
+    let suggestions: Vec<String> = place_suggestions(&app.history, &app.registering.place).into_iter()
+        .map(str::to_owned).collect();
+    show_suggestions(ui, popup_id, response, &mut app.registering.place, &suggestions, &mut app.registering.place_suggestion);
+}
+
+/// Teams are often named after the clubs starting together, so the clubs of the directory are suggested.
+fn team_suggestions(directory: &[DirectoryClub], team: &str) -> Vec<String> {
+    suggest_clubs(directory, team).into_iter().map(|club| club.get_name().to_owned())
+        .filter(|name| name != team).take(MAX_SUGGESTIONS).collect()
+}
+
+/// Offers the suggestions for the text of the field in a popup below it, chosen by clicking or the arrow-keys and Enter.
+fn show_suggestions(ui: &Ui, popup_id: egui::Id, response: &Response, text: &mut String, suggestions: &[String], suggestion: &mut usize) {
+    if text.is_empty() || suggestions.is_empty() || ui.input(|input| input.key_pressed(Key::Escape)) {
+        if ui.memory(|memory| memory.is_popup_open(popup_id)) {
+            ui.memory_mut(egui::Memory::close_popup);
+        }
+        return;
+    }
+
+    if response.changed() || response.gained_focus() {
+        *suggestion = 0;
+        ui.memory_mut(|memory| memory.open_popup(popup_id));
+    }
+    if !ui.memory(|memory| memory.is_popup_open(popup_id)) {
+        return;
+    }
+
+    let mut selected = (*suggestion).min(suggestions.len() - 1);
+    if response.has_focus() {
+        if ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::ArrowDown)) {
+            selected = (selected + 1).min(suggestions.len() - 1);
+        }
+        if ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::ArrowUp)) {
+            selected = selected.saturating_sub(1);
+        }
+    }
+    let mut accepted = None;
+    if response.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter)) {
+        accepted = Some(selected);
+    }
+
+    egui::popup_below_widget(ui, popup_id, response, PopupCloseBehavior::CloseOnClick, |ui| {
+        for (index, suggestion) in suggestions.iter().enumerate() {
+            if ui.selectable_label(index == selected, suggestion).clicked() {
+                accepted = Some(index);
+            }
+        }
+    });
+    *suggestion = selected;
+
+    if let Some(index) = accepted {
+        text.clone_from(&suggestions[index]);
+        ui.memory_mut(egui::Memory::close_popup);
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct WritingTask {
+    progress: Arc<Progress>,
+    #[allow(clippy::type_complexity)]
+    handle: JoinHandle<(Vec<Tournament>, Result<Vec<PathBuf>, Error>)>
+}
+
+impl WritingTask {
+    pub(super) fn is_running(&self) -> bool {
+        !self.handle.is_finished()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum UnofficialWeightAction {
+    UseNearest,
+    RegisterAnyway,
+    Correct
+}
+
+fn check_date(app: &mut EMelderApp) {
+    // a mis-clicked year would silently be written to all files
+    match check_tournament_date(app.registering.date, Local::now().date_naive()) {
+        Some(unusual_date) => app.registering.unusual_date = Some(unusual_date),
+        None => check_default_weight_categories(app)
+    }
+}
+
+fn unusual_date_translation_key(unusual_date: UnusualDate) -> &'static str {
+    match unusual_date {
+        UnusualDate::Past => "register.date.past",
+        UnusualDate::FarFuture => "register.date.far_future"
+    }
+}
+
+fn show_unusual_date(app: &mut EMelderApp, ui: &Ui) {
+    let Some(unusual_date) = app.registering.unusual_date else {
+        return;
+    };
+    let mut register_anyway = None;
+    egui::Window::new(translate!("register.date.title", &app.translations))
+    .collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+        ui.label(format!("{} {}", translate!(unusual_date_translation_key(unusual_date), &app.translations),
+            app.registering.date.format("%d.%m.%Y")));
+        ui.horizontal(|ui| {
+            if ui.button(translate!("register.date.register_anyway", &app.translations)).clicked() {
+                register_anyway = Some(true);
+            }
+            if ui.button(translate!("register.date.correct", &app.translations)).clicked() {
+                register_anyway = Some(false);
+            }
+        });
+    });
+    if let Some(register_anyway) = register_anyway {
+        app.registering.unusual_date = None;
+        if register_anyway {
+            check_default_weight_categories(app);
+        }
+    }
+}
+
+fn check_default_weight_categories(app: &mut EMelderApp) {
+    // team-competitions like Kata have no weight-categories to be checked
+    if app.registering.competition == Competition::Team {
+        check_conflicts(app);
+        return;
+    }
+    let default_weight_categories = find_default_weight_categories(app);
+    if default_weight_categories.is_empty() {
+        check_weight_categories(app);
+    }
+    else {
+        app.registering.default_weight_categories = Some(default_weight_categories);
+    }
+}
+
+fn find_default_weight_categories(app: &EMelderApp) -> Vec<String> {
+    app.registering.athletes.iter().filter(|athlete| athlete.has_default_weight_category())
+        .map(|athlete| format!("{} {}", athlete.get_given_name(), athlete.get_sur_name())).collect()
+}
+
+fn check_weight_categories(app: &mut EMelderApp) {
+    // checked before the files are written, as the organizers' imports reject unofficial classes
+    let unofficial_weight_categories = find_unofficial_weight_categories(&app.registering.athletes);
+    if unofficial_weight_categories.is_empty() {
+        check_conflicts(app);
+    }
+    else {
+        app.registering.unofficial_weight_categories = Some(unofficial_weight_categories);
+    }
+}
+
+fn check_conflicts(app: &mut EMelderApp) {
+    let conflicts = find_registering_conflicts(app);
+    if conflicts.is_empty() {
+        check_existing_files(app);
+    }
+    else {
+        app.registering.conflicts = Some(conflicts);
+    }
+}
+
+fn find_registering_conflicts(app: &EMelderApp) -> Vec<String> {
+    find_conflicts(&app.history, &app.registering.athletes, app.registering.date).iter().map(|(entry, athlete)| {
+        format!("{} {}: {} ({})", athlete.get_given_name(), athlete.get_sur_name(), entry.get_name(), entry.get_place())
+    }).collect()
+}
+
+/// The questions of `start_registering`, which would have to be answered before writing, for callers like the local API,
+/// who cannot be asked.
+pub(super) fn find_unconfirmed(app: &EMelderApp) -> Option<String> {
+    match check_tournament_date(app.registering.date, Local::now().date_naive()) {
+        Some(UnusualDate::Past) => return Some(String::from("the tournament is in the past")),
+        Some(UnusualDate::FarFuture) => return Some(String::from("the tournament is more than a year ahead")),
+        None => {}
+    }
+    // team-competitions like Kata have no weight-categories to be checked
+    if app.registering.competition == Competition::Individual {
+        let default_weight_categories = find_default_weight_categories(app);
+        if !default_weight_categories.is_empty() {
+            return Some(format!("default weight categories of {}", default_weight_categories.join(", ")));
+        }
+        let unofficial_weight_categories = find_unofficial_weight_categories(&app.registering.athletes);
+        if let Some((index, _)) = unofficial_weight_categories.first() {
+            let athlete = &app.registering.athletes[*index];
+            return Some(format!("unofficial weight category of {} {}", athlete.get_given_name(), athlete.get_sur_name()));
+        }
+    }
+    let conflicts = find_registering_conflicts(app);
+    if !conflicts.is_empty() {
+        return Some(format!("already registered on the same day: {}", conflicts.join(", ")));
+    }
+    None
+}
+
+fn to_tournaments(app: &EMelderApp) -> Option<Vec<Tournament>> {
+    let to_tournaments = match app.registering.competition {
+        Competition::Individual => registering_athletes_to_tournaments,
+        Competition::Team => registering_teams_to_tournaments
+    };
+    let mut tournaments = to_tournaments(
+        &app.registering.athletes, &app.registering.name, app.registering.date,
+        &app.registering.place, &app.club.for_department(app.registering.department))?;
+    let mut coach = app.registering.coach.clone();
+    if let Some(phone) = normalize_phone(coach.get_phone_mut()) {
+        *coach.get_phone_mut() = phone;
+    }
+    for tournament in &mut tournaments {
+        tournament.set_coach(coach.clone());
+        tournament.set_gender_abbreviations(app.config.gender_abbreviations);
+        tournament.set_encoding(app.config.tournament_encoding);
+    }
+    Some(tournaments)
+}
+
+fn check_existing_files(app: &mut EMelderApp) {
+    // the files of an earlier registration for the same tournament are not replaced without asking
+    let existing_files = to_tournaments(app)
+        .map(|tournaments| existing_tournament_files(&tournaments, &app.config.tournament_basedir, &app.config.file_name_template))
+        .unwrap_or_default();
+    if existing_files.is_empty() {
+        register(app, ExistingFiles::Overwrite);
+    }
+    else {
+        app.registering.existing_files = Some(existing_files);
+    }
+}
+
+pub(super) fn register(app: &mut EMelderApp, existing: ExistingFiles) {
+    let Some(mut tournaments) = to_tournaments(app) else {
+        notify_written(app, &Written::InvalidWeightCategory);
+        return;
+    };
+    if existing == ExistingFiles::Keep {
+        let existing_files = existing_tournament_files(&tournaments, &app.config.tournament_basedir, &app.config.file_name_template);
+        tournaments.retain(|tournament| {
+            get_tournament_file_name(&app.config.tournament_basedir, &app.config.file_name_template, tournament)
+                .is_ok_and(|file_name| !existing_files.contains(&file_name))
+        });
+        if tournaments.is_empty() {
+            return;
+        }
+    }
+
+    let progress = Arc::new(Progress::default());
+    let thread_progress = Arc::clone(&progress);
+    let config = app.config.clone();
+    let handle = std::thread::spawn(move || {
+        let result = write_tournaments(&tournaments, &config, existing, &thread_progress);
+        (tournaments, result)
+    });
+    app.registering.writing = Some(WritingTask { progress, handle });
+}
+
+pub(super) fn show_writing(app: &mut EMelderApp, ui: &Ui) {
+    show_write_error(app, ui);
+    show_write_results(app, ui);
+    show_fee_summary(app, ui);
+    show_registration_email(app, ui);
+    show_ask_open_folder(app, ui);
+    let Some(writing) = &app.registering.writing else {
+        return;
+    };
+    if !writing.handle.is_finished() {
+        show_progress(ui.ctx(), &translate!("register.progress", &app.translations), &writing.progress, &app.translations);
+        return;
+    }
+
+    finish_writing(app);
+}
+
+fn show_ask_open_folder(app: &mut EMelderApp, ui: &Ui) {
+    if !app.registering.ask_open_folder {
+        return;
+    }
+    let mut open = None;
+    egui::Window::new(translate!("application.title", &app.translations))
+    .collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+        ui.label(translate!("register.notification.ask", &app.translations));
+        ui.horizontal(|ui| {
+            if ui.button(translate!("register.notification.yes", &app.translations)).clicked() {
+                open = Some(true);
+            }
+            if ui.button(translate!("register.notification.no", &app.translations)).clicked() {
+                open = Some(false);
+            }
+        });
+    });
+
+    if let Some(open) = open {
+        app.registering.ask_open_folder = false;
+        if open {
+            let _ = open::that_detached(&app.config.tournament_basedir);
+        }
+    }
+}
+
+fn show_write_error(app: &mut EMelderApp, ui: &Ui) {
+    let Some(write_error) = &app.registering.write_error else {
+        return;
+    };
+    let mut retry = None;
+    egui::Window::new(translate!("register.write_error.title", &app.translations))
+    .collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+        ui.label(translate!("register.notification.io_error", &app.translations));
+        ui.label(write_error);
+        show_progress_items(ui, &app.registering.write_results);
+        ui.horizontal(|ui| {
+            if ui.add_enabled(app.registering.writing.is_none(),
+                egui::Button::new(translate!("register.write_error.retry", &app.translations))).clicked() {
+                retry = Some(true);
+            }
+            if ui.button(translate!("register.write_error.close", &app.translations)).clicked() {
+                retry = Some(false);
+            }
+        });
+    });
+
+    if let Some(retry) = retry {
+        app.registering.write_error = None;
+        app.registering.write_results.clear();
+        if retry {
+            check_existing_files(app);
+        }
+    }
+}
+
+// fewer files are reported by the notification only
+const MIN_WRITE_RESULTS: usize = 10;
+
+fn show_write_results(app: &mut EMelderApp, ui: &Ui) {
+    if app.registering.write_error.is_some() || app.registering.write_results.is_empty() {
+        return;
+    }
+    let mut close = false;
+    egui::Window::new(translate!("register.write_results.title", &app.translations))
+    .collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+        show_progress_items(ui, &app.registering.write_results);
+        if ui.button(translate!("register.write_results.close", &app.translations)).clicked() {
+            close = true;
+        }
+    });
+    if close {
+        app.registering.write_results.clear();
+    }
+}
+
+fn show_fee_summary(app: &mut EMelderApp, ui: &Ui) {
+    let Some(fee_summary) = &app.registering.fee_summary else {
+        return;
+    };
+    let mut close = false;
+    egui::Window::new(translate!("register.fees.title", &app.translations)).collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+        egui::Grid::new("fee_summary").num_columns(3).striped(true).show(ui, |ui| {
+            for (age_category, gender_category, count, fee) in fee_summary {
+                ui.label(format!("{age_category} ({})",
+                    translate!(&format!("register.table.gender_category.{}", gender_category.render()), &app.translations)));
+                ui.label(format!("{count} {}", translate!("register.fees.athletes", &app.translations)));
+                ui.label(render_fee(*fee));
+                ui.end_row();
+            }
+            ui.strong(translate!("register.fees.sum", &app.translations));
+            ui.strong(format!("{} {}", fee_summary.iter().map(|(_, _, count, _)| count).sum::<usize>(),
+                translate!("register.fees.athletes", &app.translations)));
+            ui.strong(render_fee(fee_summary.iter().map(|(_, _, _, fee)| fee).sum()));
+            ui.end_row();
+        });
+        if ui.button(translate!("register.write_results.close", &app.translations)).clicked() {
+            close = true;
+        }
+    });
+    if close {
+        app.registering.fee_summary = None;
+    }
+}
+
+pub(super) fn finish_writing(app: &mut EMelderApp) {
+    let Some(writing) = app.registering.writing.take() else {
+        return;
+    };
+    let written = match writing.handle.join() {
+        Ok((tournaments, Ok(paths))) => {
+            let mut entry = HistoryEntry::from_tournaments(app.registering.name.clone(), app.registering.date,
+                app.registering.place.clone(), &tournaments);
+            if let Some(invitation) = &app.registering.invitation {
+                // the invitation is kept with the registration, as the picked file might be moved or deleted
+                match copy_invitation(&get_attachments_dir(&app.config.history_file, &entry), invitation) {
+                    Ok(copy) => entry.set_invitation(Some(copy)),
+                    Err(err) => {
+                        log::warn!("failed to copy invitation, due to {err}");
+                        entry.set_invitation(Some(invitation.clone()));
+                    }
+                }
+            }
+            entry.set_files(&tournaments, &paths);
+            if tournaments.len() >= MIN_WRITE_RESULTS {
+                app.registering.write_results = writing.progress.items();
+            }
+            app.history.push(entry);
+            if app.config.fees.is_configured() {
+                app.registering.fee_summary = Some(app.config.fees.per_tournament(&tournaments));
+            }
+            if let Err(err) = write_history(&app.config.history_file, &app.history) {
+                log::warn!("failed to write history, due to {err}");
+            }
+            write_additional_exports(app, &tournaments, &paths);
+            remember_registrations(app);
+            if app.config.coach != app.registering.coach {
+                app.config.coach = app.registering.coach.clone();
+                app.save_configs();
+            }
+            prepare_registration_email(app, &paths);
+            record_generated_files(app, tournaments, paths);
+            Written::Successful
+        }
+        Ok((_, Err(Error::Cancelled))) => {
+            log::info!("writing tournaments was cancelled");
+            return;
+        }
+        Ok((_, Err(err))) => {
+            log::warn!("failed to write tournaments, due to {err}");
+            let reason = format!("{}\n{err}", translate!(err.translation_key(), &app.translations));
+            app.registering.write_results = writing.progress.items();
+            app.registering.write_error = Some(reason.clone());
+            Written::Error(reason)
+        }
+        Err(_) => {
+            log::warn!("failed to write tournaments, due to a panic while writing");
+            Written::Error(String::new())
+        }
+    };
+    notify_written(app, &written);
+}
+
+fn remember_registrations(app: &mut EMelderApp) {
+    // athletes registered in several age-categories are prefilled with the first one next time
+    let mut remembered = false;
+    for athlete in &mut app.athletes {
+        if let Some(registering_athlete) = app.registering.athletes.iter().find(|registering| athlete.is_same_athlete(registering)) {
+            athlete.remember_registration(registering_athlete, app.registering.date);
+            remembered = true;
+        }
+    }
+    if remembered {
+        app.save_athletes();
+    }
+}
+
+fn write_additional_exports(app: &mut EMelderApp, tournaments: &[Tournament], paths: &[PathBuf]) {
+    // the additional formats are written next to their tournament-files
+    for (tournament, path) in tournaments.iter().zip(paths) {
+        if app.config.entry_list_pdf {
+            if let Err(err) = write_entry_list(&path.with_extension("pdf"), tournament, &app.translations) {
+                log::warn!("failed to write entry list, due to {err}");
+                app.notify_error(format!("{}\n{err}", translate!("register.entry_list_pdf.failed", &app.translations)));
+            }
+        }
+        if app.config.entry_list_csv {
+            if let Err(err) = write_entry_list_csv(&path.with_extension("csv"), tournament) {
+                log::warn!("failed to write entry list as CSV, due to {err}");
+                app.notify_error(format!("{}\n{err}", translate!("register.entry_list_csv.failed", &app.translations)));
+            }
+        }
+    }
+    if let Some(calendar_file) = app.config.calendar_file.clone() {
+        if let Err(err) = write_calendar_event(&calendar_file, &app.registering.name, &app.registering.place, app.registering.date) {
+            log::warn!("failed to add the tournament to the calendar, due to {err}");
+            app.notify_error(format!("{}\n{err}", translate!("register.calendar.failed", &app.translations)));
+        }
+    }
+}
+
+const MAX_GENERATED_FILES: usize = 200;
+
+fn record_generated_files(app: &mut EMelderApp, tournaments: Vec<Tournament>, paths: Vec<PathBuf>) {
+    for (tournament, path) in tournaments.into_iter().zip(paths) {
+        match hash_file(&path) {
+            Ok(sha256) => app.generated.push(GeneratedFile::new(path, sha256, tournament)),
+            Err(err) => log::warn!("failed to hash generated file, due to {err}")
+        }
+    }
+    // the oldest files are forgotten
+    let excess = app.generated.len().saturating_sub(MAX_GENERATED_FILES);
+    app.generated.drain(..excess);
+    app.generated_verification.clear();
+    if let Err(err) = write_generated_files(&app.config.generated_file, &app.generated) {
+        log::warn!("failed to write generated files, due to {err}");
     }
 }
 
 #[allow(clippy::too_many_lines)]
+fn notify_written(app: &mut EMelderApp, written: &Written) {
+    match written {
+        Written::Successful => {
+            if !app.notifications {
+                return;
+            }
+            let tournament_basedir = app.config.tournament_basedir.clone();
+            match app.config.open_folder {
+                OpenFolder::Always => {
+                    let _ = open::that_detached(tournament_basedir);
+                    return;
+                }
+                OpenFolder::Never => return,
+                OpenFolder::Ask => {}
+            }
+            #[cfg(all(target_family="unix", not(target_os="macos")))]
+            let translations = Arc::clone(&app.translations);
+            #[cfg(all(target_family="unix", not(target_os="macos")))]
+            std::thread::spawn(move || {
+                let _ = notify_rust::Notification::new()
+                .summary(&translate!("application.title", &translations))
+                .body(&translate!("register.notification.ask", &translations))
+                .sound_name("dialog-question")
+                .action("yes", &translate!("register.notification.yes", &translations))
+                .action("no", &translate!("register.notification.no", &translations))
+                .show().map(|handle| {
+                    handle.wait_for_action(|action| {
+                        if action == "yes" {
+                            let _ = open::that_detached(tournament_basedir);
+                        }
+                    });
+                });
+            });
+
+            // the notifications of the other platforms do not support actions, so the app asks itself
+            #[cfg(any(not(target_family="unix"), target_os="macos"))]
+            {
+                app.registering.ask_open_folder = true;
+            }
+        }
+        Written::Error(reason) => {
+            // the reason names the failed file, none of the files have been written then
+            app.notify_error(format!("{}\n{reason}", translate!("register.notification.io_error", &app.translations)));
+        }
+        Written::InvalidWeightCategory => {
+            app.notify_error(translate!("register.notification.invalid_weight_category", &app.translations));
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+fn show_age_category(ui: &mut Ui, index: usize, age_category: &mut String, age_categories: &[String],
+    translations: &HashMap<String, String>) {
+    // typos would split one tournament into several files, so the presets are picked from a list
+    let custom = !age_categories.contains(age_category);
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt(("age_category", index))
+        .selected_text(if custom { translate!("register.table.age_category.custom", translations) } else { age_category.clone() })
+        .show_ui(ui, |ui| {
+            for preset in age_categories.iter().filter(|preset| !preset.is_empty()) {
+                ui.selectable_value(age_category, preset.clone(), preset);
+            }
+            if ui.selectable_label(custom, translate!("register.table.age_category.custom", translations)).clicked() && !custom {
+                age_category.clear();
+            }
+        });
+        if custom {
+            ui.add(egui::TextEdit::singleline(age_category).desired_width(80.0))
+                .on_hover_text(translate!("register.table.age_category.custom.hover", translations));
+        }
+    });
+}
+
 fn show_table_registering(app: &mut EMelderApp, ui: &mut Ui) {
     let mut to_delete = None;
+    let team = app.registering.competition == Competition::Team;
     ui.push_id("register.table.register", |ui| {
+        let row_height = app.row_height();
         let table = TableBuilder::new(ui)
             .columns(Column::auto().at_least(100.0), 7)
             .column(Column::auto().at_least(50.0));
+        let table = app.restore_scroll_offset("register.table.register", table);
 
-        table.header(20.0, |mut header| {
+        let output = table.header(20.0, |mut header| {
             header.col(|ui| {
                 ui.strong(translate!("register.table.given_name", &app.translations));
             });
@@ -147,28 +1101,35 @@ fn show_table_registering(app: &mut EMelderApp, ui: &mut Ui) {
                 ui.strong(translate!("register.table.age_category", &app.translations));
             });
             header.col(|ui| {
-                ui.strong(translate!("register.table.weight_category", &app.translations));
+                ui.strong(translate!(if team { "register.table.team" } else { "register.table.weight_category" }, &app.translations));
             });
             header.col(|_ui| {});
         }).body(|mut body| {
             for (index, athlete) in app.registering.athletes.iter_mut().enumerate() {
-                body.row(18.0, |mut row| {
+                // the colour of the file the athlete is written to, as in the groups below
+                let color = category_color(&normalize_age_category(athlete.get_age_category()), athlete.get_gender_category().render());
+                body.row(row_height, |mut row| {
                     row.col(|ui| {
+                        tint_cell(ui, color);
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
                         ui.label(athlete.get_given_name());
                     });
                     row.col(|ui| {
+                        tint_cell(ui, color);
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
                         ui.label(athlete.get_sur_name());
                     });
                     row.col(|ui| {
+                        tint_cell(ui, color);
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
                         ui.label(translate!(&format!("add.belt.{}", athlete.get_belt().serialise()), &app.translations));
                     });
                     row.col(|ui| {
+                        tint_cell(ui, color);
                         ui.label(athlete.get_birth_year().to_string());
                     });
                     row.col(|ui| {
+                        tint_cell(ui, color);
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
                         egui::ComboBox::from_id_salt(index)
                         .selected_text(translate!(&format!("register.table.gender_category.{}", athlete.get_gender_category().render()), &app.translations))
@@ -180,12 +1141,56 @@ fn show_table_registering(app: &mut EMelderApp, ui: &mut Ui) {
                         });
                     });
                     row.col(|ui| {
-                        ui.text_edit_singleline(athlete.get_age_category_mut());
+                        tint_cell(ui, color);
+                        show_age_category(ui, index, athlete.get_age_category_mut(), &app.config.age_categories, &app.translations);
                     });
                     row.col(|ui| {
-                        ui.text_edit_singleline(athlete.get_weight_category_mut());
+                        tint_cell(ui, color);
+                        if team {
+                            let response = ui.add(egui::TextEdit::singleline(athlete.get_team_mut()).desired_width(100.0)
+                                .hint_text(translate!("register.table.team.hint", &app.translations)));
+                            // the directory is only searched for the team being edited
+                            let popup_id = egui::Id::new(("register.team.suggestions", index));
+                            let suggestions = if response.has_focus() || ui.memory(|memory| memory.is_popup_open(popup_id)) {
+                                team_suggestions(app.club.get_directory(), athlete.get_team())
+                            }
+                            else {
+                                Vec::new()
+                            };
+                            show_suggestions(ui, popup_id, &response, athlete.get_team_mut(), &suggestions,
+                                &mut app.registering.team_suggestion);
+                            return;
+                        }
+                        ui.horizontal(|ui| {
+                            let lighter = WeightCategory::step(athlete.get_weight_category(), athlete.get_age_category(),
+                                *athlete.get_gender_category(), false);
+                            let heavier = WeightCategory::step(athlete.get_weight_category(), athlete.get_age_category(),
+                                *athlete.get_gender_category(), true);
+                            if ui.add_enabled(lighter.is_some(), egui::Button::new("−")).clicked() {
+                                *athlete.get_weight_category_mut() = lighter.unwrap_or_default().to_owned();
+                            }
+                            let nearest = WeightCategory::nearest_official(athlete.get_weight_category(), athlete.get_age_category(),
+                                *athlete.get_gender_category());
+                            if athlete.has_default_weight_category() {
+                                ui.add(egui::TextEdit::singleline(athlete.get_weight_category_mut()).desired_width(40.0)
+                                    .text_color(ui.visuals().warn_fg_color))
+                                    .on_hover_text(translate!("register.table.default_weight", &app.translations));
+                            }
+                            else if let Some(nearest) = nearest {
+                                ui.add(egui::TextEdit::singleline(athlete.get_weight_category_mut()).desired_width(40.0)
+                                    .text_color(ui.visuals().warn_fg_color))
+                                    .on_hover_text(format!("{} {nearest}", translate!("register.table.unofficial_weight", &app.translations)));
+                            }
+                            else {
+                                ui.add(egui::TextEdit::singleline(athlete.get_weight_category_mut()).desired_width(40.0));
+                            }
+                            if ui.add_enabled(heavier.is_some(), egui::Button::new("+")).clicked() {
+                                *athlete.get_weight_category_mut() = heavier.unwrap_or_default().to_owned();
+                            }
+                        });
                     });
                     row.col(|ui| {
+                        tint_cell(ui, color);
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
                         if ui.button(translate!("register.table.delete", &app.translations)).clicked() {
                             to_delete = Some(index);
@@ -194,49 +1199,53 @@ fn show_table_registering(app: &mut EMelderApp, ui: &mut Ui) {
                 });
             }
         });
+        app.remember_scroll_offset("register.table.register", &output);
     });
 
-    if let Some(index) = to_delete {
+    if let Some(index) = to_delete.filter(|index| *index < app.registering.athletes.len()) {
         app.registering.athletes.remove(index);
+        // the indices of the following entries changed
+        app.registering.entry_selection.clear();
     }
 }
 
 #[allow(clippy::too_many_lines)]
 fn show_table_registering_adding(app: &mut EMelderApp, ui: &mut Ui) {
-    ui.horizontal(|ui| {
-        ui.label(translate!("register.search", &app.translations));
-        ui.text_edit_singleline(&mut app.registering.search);
-    });
+    let shown = show_search(app, ui);
+    // athletes added already are not added twice
+    let to_add: Vec<Uuid> = shown.iter().map(|index| &app.athletes[*index])
+        .filter(|athlete| !app.registering.athletes.iter().any(|registering| athlete.is_same_athlete(registering)))
+        .map(|athlete| athlete.get_id()).collect();
+    if ui.add_enabled(!to_add.is_empty(), egui::Button::new(format!("{} ({})",
+        translate!("register.search.add_all", &app.translations), to_add.len()))).clicked() {
+        for id in to_add {
+            add_to_registering(app, id);
+        }
+    }
 
-    let mut athletes_shown = false;
+    let mut to_add = None;
+    let mut sorted = false;
     ui.push_id("register.table.add", |ui| {
+        let row_height = app.row_height();
         let table = TableBuilder::new(ui).columns(Column::auto().at_least(100.0), 5)
             .column(Column::auto().at_least(50.0)).max_scroll_height(100.0);
+        let table = app.restore_scroll_offset("register.table.add", table);
 
-        table.header(20.0, |mut header| {
-            header.col(|ui| {
-                ui.strong(translate!("register.table.given_name", &app.translations));
-            });
-            header.col(|ui| {
-                ui.strong(translate!("register.table.sur_name", &app.translations));
-            });
-            header.col(|ui| {
-                ui.strong(translate!("register.table.gender", &app.translations));
-            });
-            header.col(|ui| {
-                ui.strong(translate!("register.table.belt", &app.translations));
-            });
-            header.col(|ui| {
-                ui.strong(translate!("register.table.year", &app.translations));
-            });
+        let sorting = &mut app.config.sorting.registering;
+        let output = table.header(20.0, |mut header| {
+            for (key, column) in [("register.table.given_name", SortColumn::GivenName), ("register.table.sur_name", SortColumn::SurName),
+                ("register.table.gender", SortColumn::Gender), ("register.table.belt", SortColumn::Belt),
+                ("register.table.year", SortColumn::BirthYear)] {
+                header.col(|ui| {
+                    sorted |= sortable_header(ui, translate!(key, &app.translations), column, sorting);
+                });
+            }
         }).body(|mut body| {
-            for athlete in &app.athletes {
-                if !matches_query(&format!("{} {}", athlete.get_given_name(), athlete.get_sur_name()), &app.registering.search) {
-                    continue;
-                }
-                athletes_shown = true;
+            for index in shown.iter().copied() {
+                let athlete = &app.athletes[index];
+                let registered = app.registering.athletes.iter().any(|registering| athlete.is_same_athlete(registering));
 
-                body.row(18.0, |mut row| {
+                body.row(row_height, |mut row| {
                     row.col(|ui| {
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
                         ui.label(athlete.get_given_name());
@@ -258,22 +1267,78 @@ fn show_table_registering_adding(app: &mut EMelderApp, ui: &mut Ui) {
                     });
                     row.col(|ui| {
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
-                        if ui.button(translate!("register.table.add", &app.translations)).clicked() {
-                            app.registering.athletes.push(RegisteringAthlete::from_athlete(athlete));
+                        let response = if registered {
+                            ui.button(translate!("register.table.add_again", &app.translations))
+                                .on_hover_text(translate!("register.table.add_again.hover", &app.translations))
+                        }
+                        else {
+                            ui.button(translate!("register.table.add", &app.translations))
+                        };
+                        app.tour.highlight(TourStep::Add, &response);
+                        if response.clicked() {
+                            to_add = Some(athlete.get_id());
                         }
                     });
                 });
             }
         });
+        app.remember_scroll_offset("register.table.add", &output);
     });
 
-    if !athletes_shown {
+    if let Some(id) = to_add {
+        add_to_registering(app, id);
+    }
+    if sorted {
+        app.save_configs();
+    }
+    if shown.is_empty() {
         ui.label(translate!("register.search.empty", &app.translations));
     }
 }
 
-fn matches_query(base: &str, query: &str) -> bool {
+/// Shows the search for athletes and returns the indices of the athletes matching it in the order of the table.
+pub(super) fn show_search(app: &mut EMelderApp, ui: &mut Ui) -> Vec<usize> {
+    ui.horizontal(|ui| {
+        ui.label(translate!("register.search", &app.translations));
+        let response = ui.text_edit_singleline(&mut app.registering.search);
+        app.tour.highlight(TourStep::Search, &response);
+        ui.checkbox(&mut app.registering.only_without_start, translate!("register.search.only_without_start", &app.translations));
+    });
+
+    let season = Local::now().year();
+    let mut shown: Vec<usize> = app.athletes.iter().enumerate().filter(|(_, athlete)| {
+        matches_query(&format!("{} {}", athlete.get_given_name(), athlete.get_sur_name()), &app.registering.search)
+            && !(app.registering.only_without_start && has_start_in_season(&app.history, athlete, season))
+    }).map(|(index, _)| index).collect();
+    app.config.sorting.registering.sort(&app.athletes, &mut shown);
+    shown
+}
+
+/// Adds the athlete with `id` to the registration, athletes registered already are added in the next age-category.
+pub(super) fn add_to_registering(app: &mut EMelderApp, id: Uuid) {
+    let Some(athlete) = app.athletes.iter().find(|athlete| athlete.get_id() == id) else {
+        return;
+    };
+    // athletes can be registered in several age-categories, e.g. in U18 and for the men
+    let registered = app.registering.athletes.iter().rev().find(|registering| athlete.is_same_athlete(registering))
+        .map(|registering| registering.get_age_category().to_owned());
+    let mut registering = RegisteringAthlete::for_tournament(athlete, app.registering.date, &app.config.age_category_hints);
+    if let Some(registered) = &registered {
+        *registering.get_age_category_mut() = next_age_category(&app.config.age_categories, registered);
+    }
+    app.registering.athletes.push(registering);
+}
+
+/// The age-category after `current` in the configured ones, e.g. "Männer" after "U21", or none to be chosen by the user.
+fn next_age_category(age_categories: &[String], current: &str) -> String {
+    let current = normalize_age_category(current);
+    age_categories.iter().position(|age_category| normalize_age_category(age_category) == current)
+        .and_then(|position| age_categories.get(position + 1)).cloned().unwrap_or_default()
+}
+
+pub(super) fn matches_query(base: &str, query: &str) -> bool {
     // value for comparison was obtained by testing various values and choosing
     // the values with the results that felt best
-    base.contains(query) || textdistance::nstr::jaro(base, query) >= 0.65
+    let query = normalize_name(query);
+    base.contains(&query) || textdistance::nstr::jaro(base, &query) >= 0.65
 }
@@ -0,0 +1,206 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use chrono::Local;
+use egui::Ui;
+
+use crate::error::Error;
+use crate::tournament_info::{summarize_diff, Athlete, DiffSummary};
+use crate::utils::{backup_files, get_config_dir, get_config_file, read_athletes, restore_backup, translate, verify_backup, Progress};
+use super::EMelderApp;
+use super::app::{load_athletes, load_club, load_history};
+use super::progress::show_progress;
+use super::theme::error_label;
+
+const MANUAL_BACKUP_PREFIX: &str = "manual-";
+
+#[derive(Debug, Default)]
+pub(super) struct Restore {
+    backup_dir: Option<PathBuf>,
+    summary: DiffSummary,
+    // a backup failing the verification is not restored
+    error: Option<String>,
+    confirm: bool,
+    restored: bool,
+    backup: Option<BackupTask>,
+    backed_up: Option<Result<PathBuf, String>>
+}
+
+#[derive(Debug)]
+struct BackupTask {
+    backup_dir: PathBuf,
+    progress: Arc<Progress>,
+    handle: JoinHandle<Result<(), Error>>
+}
+
+fn backups_dir(app: &EMelderApp) -> Option<PathBuf> {
+    app.config.schedule.backup_dir.clone()
+        .or_else(|| get_config_dir().ok().map(|config_dir| config_dir.join("e-melder").join("backups")))
+}
+
+/// Backs up the config and its files in the background, a backup of large files takes a while.
+fn start_backup(app: &mut EMelderApp) {
+    let Some(backups_dir) = backups_dir(app) else {
+        app.restore.backed_up = Some(Err(translate!("error.io", &app.translations)));
+        return;
+    };
+    let config_file = match get_config_file() {
+        Ok(config_file) => config_file,
+        Err(err) => {
+            log::warn!("failed to get config-file, due to {err}");
+            app.restore.backed_up = Some(Err(err.to_string()));
+            return;
+        }
+    };
+    let backup_dir = backups_dir.join(format!("{MANUAL_BACKUP_PREFIX}{}", Local::now().format("%Y-%m-%d_%H-%M-%S")));
+    let progress = Arc::new(Progress::default());
+    let handle = {
+        let backup_dir = backup_dir.clone();
+        let progress = Arc::clone(&progress);
+        std::thread::spawn(move || backup_files(&backup_dir, &config_file, &progress))
+    };
+    app.restore.backed_up = None;
+    app.restore.backup = Some(BackupTask { backup_dir, progress, handle });
+}
+
+fn show_backup(app: &mut EMelderApp, ui: &Ui) {
+    finish_backup(app);
+    if let Some(backup) = &app.restore.backup {
+        show_progress(ui.ctx(), &translate!("config.backup.progress", &app.translations), &backup.progress, &app.translations);
+    }
+}
+
+pub(super) fn finish_backup(app: &mut EMelderApp) {
+    if !app.restore.backup.as_ref().is_some_and(|backup| backup.handle.is_finished()) {
+        return;
+    }
+    let Some(backup) = app.restore.backup.take() else {
+        return;
+    };
+    app.restore.backed_up = match backup.handle.join() {
+        Ok(Ok(())) => Some(Ok(backup.backup_dir)),
+        // the unfinished backup is removed already
+        Ok(Err(Error::Cancelled)) => None,
+        Ok(Err(err)) => {
+            log::warn!("failed to back up files, due to {err}");
+            Some(Err(err.to_string()))
+        }
+        Err(_) => Some(Err(translate!("error.io", &app.translations)))
+    };
+}
+
+impl Restore {
+    pub(super) fn is_backing_up(&self) -> bool {
+        self.backup.as_ref().is_some_and(|backup| !backup.handle.is_finished())
+    }
+
+    fn check(backup_dir: PathBuf, athletes: &[Athlete]) -> Self {
+        let result = verify_backup(&backup_dir).map_err(|err| err.to_string()).and_then(|checksums| {
+            if !checksums.contains_key("athletes.json") {
+                // the athletes are kept then
+                return Ok(DiffSummary::default());
+            }
+            let backup = read_athletes(backup_dir.join("athletes.json")).map_err(|err| err.to_string())?;
+            Ok(summarize_diff(athletes, &backup))
+        });
+        match result {
+            Ok(summary) => Self { backup_dir: Some(backup_dir), summary, ..Self::default() },
+            Err(err) => {
+                log::warn!("failed to verify backup, due to {err}");
+                Self { backup_dir: Some(backup_dir), error: Some(err), ..Self::default() }
+            }
+        }
+    }
+}
+
+pub fn show_restore(app: &mut EMelderApp, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        if ui.add_enabled(!app.read_only, egui::Button::new(translate!("config.restore", &app.translations))).clicked() {
+            let mut dialog = rfd::FileDialog::new().set_title(translate!("config.restore", &app.translations));
+            if let Some(backups_dir) = backups_dir(app).filter(|backups_dir| backups_dir.is_dir()) {
+                dialog = dialog.set_directory(backups_dir);
+            }
+            if let Some(backup_dir) = dialog.pick_folder() {
+                app.restore = Restore::check(backup_dir, &app.athletes);
+            }
+        }
+        if let Some(backup_dir) = &app.restore.backup_dir {
+            ui.monospace(backup_dir.display().to_string());
+        }
+    });
+    ui.horizontal(|ui| {
+        if ui.add_enabled(app.restore.backup.is_none(), egui::Button::new(translate!("config.backup", &app.translations))).clicked() {
+            start_backup(app);
+        }
+        match &app.restore.backed_up {
+            Some(Ok(backup_dir)) => {
+                ui.label(format!("{} {}", translate!("config.backup.done", &app.translations), backup_dir.display()));
+            }
+            Some(Err(err)) => {
+                error_label(ui, format!("{} {err}", translate!("config.backup.failed", &app.translations)));
+            }
+            None => {}
+        }
+    });
+    show_backup(app, ui);
+
+    if app.restore.restored {
+        ui.label(translate!("config.restore.done", &app.translations));
+    }
+    if app.restore.backup_dir.is_none() {
+        return;
+    }
+    if let Some(error) = &app.restore.error {
+        error_label(ui, format!("{} {error}", translate!("config.restore.invalid", &app.translations)));
+        return;
+    }
+
+    let summary = app.restore.summary;
+    ui.label(format!("{} {}, {} {}, {} {}", summary.added, translate!("config.restore.added", &app.translations),
+        summary.removed, translate!("config.restore.removed", &app.translations),
+        summary.changed, translate!("config.restore.changed", &app.translations)));
+    if ui.add_enabled(!app.read_only, egui::Button::new(translate!("config.restore.restore", &app.translations))).clicked() {
+        app.restore.confirm = true;
+    }
+
+    if !app.restore.confirm {
+        return;
+    }
+    let mut restore = None;
+    egui::Window::new(translate!("config.restore.confirm.title", &app.translations))
+    .collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+        ui.label(translate!("config.restore.confirm.text", &app.translations));
+        ui.horizontal(|ui| {
+            if ui.button(translate!("config.restore.confirm.restore", &app.translations)).clicked() {
+                restore = Some(true);
+            }
+            if ui.button(translate!("config.restore.confirm.cancel", &app.translations)).clicked() {
+                restore = Some(false);
+            }
+        });
+    });
+
+    match restore {
+        Some(true) => {
+            let Some(backup_dir) = app.restore.backup_dir.take() else {
+                return;
+            };
+            // verified again, as the backup might have changed since it was picked
+            match restore_backup(&backup_dir, &app.config) {
+                Ok(()) => {
+                    app.athletes = load_athletes(&app.config.athletes_file);
+                    app.club = load_club(&app.config.club_file);
+                    app.history = load_history(&app.config.history_file);
+                    app.restore = Restore { restored: true, ..Restore::default() };
+                }
+                Err(err) => {
+                    log::warn!("failed to restore backup, due to {err}");
+                    app.restore = Restore { backup_dir: Some(backup_dir), error: Some(err.to_string()), ..Restore::default() };
+                }
+            }
+        }
+        Some(false) => app.restore.confirm = false,
+        None => {}
+    }
+}
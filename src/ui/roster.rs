@@ -0,0 +1,176 @@
+use std::collections::{BTreeMap, HashMap};
+
+use egui::{TextWrapMode, Ui};
+use egui_extras::{Column, TableBuilder};
+
+use crate::tournament_info::{Athlete, SurNameDisplay};
+use crate::utils::{escape_html, translate, write_roster};
+use super::EMelderApp;
+
+pub fn show_roster(app: &mut EMelderApp, ui: &mut Ui) {
+    if app.athletes.is_empty() {
+        ui.label(translate!("roster.empty", &app.translations));
+        return;
+    }
+
+    if ui.button(translate!("roster.export", &app.translations)).clicked() {
+        if let Some(path) = rfd::FileDialog::new().set_can_create_directories(true)
+            .set_directory(&app.config.tournament_basedir).set_file_name("roster.html")
+            .set_title(translate!("roster.export.file_picker", &app.translations)).save_file() {
+            match write_roster(&path, &render_roster(&app.athletes, &app.translations)) {
+                Ok(()) => {
+                    let _ = open::that_detached(path);
+                }
+                Err(err) => {
+                    log::warn!("failed to write roster, due to {err}");
+                }
+            }
+        }
+    }
+
+    show_website_export(app, ui);
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for (group, athletes) in group_athletes(&app.athletes) {
+            ui.separator();
+            ui.heading(group_name(group, &app.translations));
+            ui.push_id(("roster.table", group), |ui| {
+                let row_height = app.row_height();
+                let table = TableBuilder::new(ui).columns(Column::auto().at_least(100.0), 4);
+
+                table.header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.strong(translate!("roster.given_name", &app.translations));
+                    });
+                    header.col(|ui| {
+                        ui.strong(translate!("roster.sur_name", &app.translations));
+                    });
+                    header.col(|ui| {
+                        ui.strong(translate!("roster.belt", &app.translations));
+                    });
+                    header.col(|ui| {
+                        ui.strong(translate!("roster.year", &app.translations));
+                    });
+                }).body(|mut body| {
+                    for athlete in athletes {
+                        body.row(row_height, |mut row| {
+                            row.col(|ui| {
+                                ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                                ui.label(athlete.get_given_name());
+                            });
+                            row.col(|ui| {
+                                ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                                ui.label(athlete.get_sur_name());
+                            });
+                            row.col(|ui| {
+                                ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                                ui.label(translate!(&format!("add.belt.{}", athlete.get_belt().serialise()), &app.translations));
+                            });
+                            row.col(|ui| {
+                                ui.label(athlete.get_birth_year().to_string());
+                            });
+                        });
+                    }
+                });
+            });
+        }
+    });
+}
+
+fn sur_name_translation_key(sur_name: SurNameDisplay) -> &'static str {
+    match sur_name {
+        SurNameDisplay::Full => "roster.website.sur_name.full",
+        SurNameDisplay::Initial => "roster.website.sur_name.initial",
+        SurNameDisplay::Hidden => "roster.website.sur_name.hidden"
+    }
+}
+
+/// Exports the roster for the website of the club, only with the details allowed by the privacy-settings.
+fn show_website_export(app: &mut EMelderApp, ui: &mut Ui) {
+    let mut export = None;
+    egui::CollapsingHeader::new(translate!("roster.website", &app.translations)).show(ui, |ui| {
+        let website_roster = &mut app.config.website_roster;
+        egui::ComboBox::from_label(translate!("roster.website.sur_name", &app.translations))
+        .selected_text(translate!(sur_name_translation_key(website_roster.sur_name), &app.translations))
+        .show_ui(ui, |ui| {
+            for sur_name in SurNameDisplay::ALL {
+                ui.selectable_value(&mut website_roster.sur_name, sur_name,
+                    translate!(sur_name_translation_key(sur_name), &app.translations));
+            }
+        });
+        ui.checkbox(&mut website_roster.belt, translate!("roster.website.belt", &app.translations));
+        ui.checkbox(&mut website_roster.birth_year, translate!("roster.website.year", &app.translations));
+        ui.checkbox(&mut website_roster.group, translate!("roster.website.group", &app.translations));
+        ui.horizontal(|ui| {
+            ui.label(translate!("roster.website.photo_url", &app.translations));
+            ui.text_edit_singleline(&mut website_roster.photo_url)
+                .on_hover_text(translate!("roster.website.photo_url.hint", &app.translations));
+        });
+        ui.horizontal(|ui| {
+            if ui.button(translate!("roster.website.export_html", &app.translations)).clicked() {
+                export = Some("html");
+            }
+            if ui.button(translate!("roster.website.export_json", &app.translations)).clicked() {
+                export = Some("json");
+            }
+        });
+    });
+
+    let Some(extension) = export else {
+        return;
+    };
+    // the privacy-settings are kept for the next export
+    app.save_configs();
+    if let Some(path) = rfd::FileDialog::new().set_can_create_directories(true).add_filter(extension.to_uppercase(), &[extension])
+        .set_file_name(format!("roster-website.{extension}")).save_file() {
+        let contents = if extension == "html" {
+            app.config.website_roster.render_html(&app.athletes)
+        }
+        else {
+            app.config.website_roster.render_json(&app.athletes)
+        };
+        if let Err(err) = write_roster(&path, &contents) {
+            log::warn!("failed to write roster for the website, due to {err}");
+        }
+    }
+}
+
+fn group_athletes(athletes: &[Athlete]) -> BTreeMap<&str, Vec<&Athlete>> {
+    let mut groups: BTreeMap<&str, Vec<&Athlete>> = BTreeMap::new();
+    for athlete in athletes {
+        groups.entry(athlete.get_group()).or_default().push(athlete);
+    }
+    for group in groups.values_mut() {
+        group.sort_by(|a, b| (a.get_sur_name(), a.get_given_name()).cmp(&(b.get_sur_name(), b.get_given_name())));
+    }
+    groups
+}
+
+fn group_name(group: &str, translations: &HashMap<String, String>) -> String {
+    if group.is_empty() {
+        translate!("roster.no_group", translations)
+    }
+    else {
+        group.to_owned()
+    }
+}
+
+fn render_roster(athletes: &[Athlete], translations: &HashMap<String, String>) -> String {
+    let mut ret = format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n",
+        escape_html(&translate!("roster.title", translations)));
+    for (group, athletes) in group_athletes(athletes) {
+        ret.push_str(&format!("<h2>{}</h2>\n<table>\n<tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>\n",
+            escape_html(&group_name(group, translations)), escape_html(&translate!("roster.given_name", translations)),
+            escape_html(&translate!("roster.sur_name", translations)), escape_html(&translate!("roster.belt", translations)),
+            escape_html(&translate!("roster.year", translations))));
+        for athlete in athletes {
+            ret.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(athlete.get_given_name()), escape_html(athlete.get_sur_name()),
+                escape_html(&translate!(&format!("add.belt.{}", athlete.get_belt().serialise()), translations)),
+                athlete.get_birth_year()));
+        }
+        ret.push_str("</table>\n");
+    }
+    ret.push_str("</body>\n</html>\n");
+    ret
+}
@@ -0,0 +1,147 @@
+use std::path::PathBuf;
+
+use egui::Ui;
+
+use crate::utils::{get_config_file, translate, write_athletes, write_club, write_configs};
+use super::EMelderApp;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum SaveTarget {
+    Athletes,
+    Club,
+    Config
+}
+
+impl SaveTarget {
+    fn translation_key(self) -> &'static str {
+        match self {
+            SaveTarget::Athletes => "save_error.athletes",
+            SaveTarget::Club => "save_error.club",
+            SaveTarget::Config => "save_error.config"
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct SaveError {
+    target: SaveTarget,
+    path: PathBuf,
+    message: String
+}
+
+impl EMelderApp {
+    /// Writes the athletes, a failure is shown to the user in a dialog.
+    pub(super) fn save_athletes(&mut self) -> bool {
+        let result = write_athletes(&self.config.athletes_file, &self.athletes).map_err(|err| err.to_string());
+        self.handle_save_result(SaveTarget::Athletes, self.config.athletes_file.clone(), result)
+    }
+
+    /// Writes the club, a failure is shown to the user in a dialog.
+    pub(super) fn save_club(&mut self) -> bool {
+        let result = write_club(&self.config.club_file, &self.club).map_err(|err| err.to_string());
+        self.handle_save_result(SaveTarget::Club, self.config.club_file.clone(), result)
+    }
+
+    /// Writes the configs, a failure is shown to the user in a dialog.
+    pub(super) fn save_configs(&mut self) -> bool {
+        // the config of the demo points to its temporary files
+        if self.demo.is_some() {
+            return true;
+        }
+        let result = write_configs(&self.config).map_err(|err| err.to_string());
+        self.handle_save_result(SaveTarget::Config, get_config_file().unwrap_or_default(), result)
+    }
+
+    fn save(&mut self, target: SaveTarget) -> bool {
+        match target {
+            SaveTarget::Athletes => self.save_athletes(),
+            SaveTarget::Club => self.save_club(),
+            SaveTarget::Config => self.save_configs()
+        }
+    }
+
+    /// Writes the athletes or the club to `path` and only uses it as their file afterwards,
+    /// so a failure keeps the old one.
+    fn save_elsewhere(&mut self, target: SaveTarget, path: PathBuf) {
+        let result = match target {
+            SaveTarget::Athletes => write_athletes(&path, &self.athletes),
+            SaveTarget::Club => write_club(&path, &self.club),
+            // the location of the config-file is fixed
+            SaveTarget::Config => return
+        }.map_err(|err| err.to_string());
+        if !self.handle_save_result(target, path.clone(), result) {
+            return;
+        }
+        match target {
+            SaveTarget::Athletes => self.config.athletes_file = path,
+            SaveTarget::Club => self.config.club_file = path,
+            SaveTarget::Config => {}
+        }
+        self.save_configs();
+    }
+
+    fn handle_save_result(&mut self, target: SaveTarget, path: PathBuf, result: Result<(), String>) -> bool {
+        match result {
+            Ok(()) => {
+                if self.save_error.as_ref().is_some_and(|save_error| save_error.target == target) {
+                    self.save_error = None;
+                }
+                true
+            }
+            Err(message) => {
+                log::warn!("failed to write {}, due to {message}", path.display());
+                self.save_error = Some(SaveError { target, path, message });
+                false
+            }
+        }
+    }
+}
+
+pub fn show_save_error(app: &mut EMelderApp, ui: &Ui) {
+    let Some(save_error) = &app.save_error else {
+        return;
+    };
+    let target = save_error.target;
+    let mut retry = false;
+    let mut save_elsewhere = None;
+    let mut close = false;
+    egui::Window::new(translate!("save_error.title", &app.translations))
+    .collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+        ui.label(translate!(target.translation_key(), &app.translations));
+        ui.horizontal(|ui| {
+            ui.label(translate!("save_error.path", &app.translations));
+            ui.monospace(save_error.path.display().to_string());
+        });
+        ui.label(&save_error.message);
+        ui.horizontal(|ui| {
+            if ui.button(translate!("save_error.retry", &app.translations)).clicked() {
+                retry = true;
+            }
+            // the location of the config-file is fixed
+            if ui.add_enabled(target != SaveTarget::Config,
+                egui::Button::new(translate!("save_error.save_elsewhere", &app.translations))).clicked() {
+                let mut dialog = rfd::FileDialog::new().set_can_create_directories(true).add_filter("JSON", &["json"]);
+                if let Some(file_name) = save_error.path.file_name() {
+                    dialog = dialog.set_file_name(file_name.to_string_lossy());
+                }
+                if let Some(parent) = save_error.path.parent() {
+                    dialog = dialog.set_directory(parent);
+                }
+                save_elsewhere = dialog.save_file();
+            }
+            if ui.button(translate!("save_error.close", &app.translations)).clicked() {
+                close = true;
+            }
+        });
+    });
+
+    if retry {
+        app.save(target);
+    }
+    if let Some(path) = save_elsewhere {
+        app.save_elsewhere(target, path);
+    }
+    if close {
+        app.save_error = None;
+    }
+}
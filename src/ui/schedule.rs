@@ -0,0 +1,126 @@
+use std::path::Path;
+
+use chrono::{Local, NaiveDateTime};
+use egui::Ui;
+
+use crate::error::Error;
+use crate::utils::{backup_files, get_config_dir, get_config_file, prune_backups, translate, write_roster, Progress};
+use super::EMelderApp;
+use super::theme::error_label;
+
+const SCHEDULED_BACKUP_PREFIX: &str = "scheduled-";
+// two weeks of nightly backups
+const SCHEDULED_BACKUPS_KEPT: usize = 14;
+
+impl EMelderApp {
+    pub(super) fn run_due_jobs(&mut self, ctx: &egui::Context) {
+        if !self.config.schedule.enabled {
+            return;
+        }
+        let now = Local::now().naive_local();
+        if self.config.schedule.is_due(now) {
+            self.run_scheduled_jobs(now);
+        }
+        // checked again without user input, when the app is left open over night
+        ctx.request_repaint_after(std::time::Duration::from_secs(60));
+    }
+
+    /// Runs the configured backup and exports and records the result in the config.
+    pub fn run_scheduled_jobs(&mut self, now: NaiveDateTime) {
+        let mut errors = Vec::new();
+        if self.config.schedule.backup {
+            if let Err(err) = self.scheduled_backup(now) {
+                log::warn!("failed to run scheduled backup, due to {err}");
+                errors.push(err.to_string());
+            }
+        }
+        if let Some(path) = self.config.schedule.website_roster_file.clone() {
+            if let Err(err) = self.scheduled_website_roster(&path) {
+                log::warn!("failed to run scheduled export of the roster, due to {err}");
+                errors.push(format!("{}: {err}", path.display()));
+            }
+        }
+        self.config.schedule.last_run = Some(now);
+        self.config.schedule.last_error = if errors.is_empty() { None } else { Some(errors.join("\n")) };
+        self.save_configs();
+    }
+
+    fn scheduled_backup(&self, now: NaiveDateTime) -> Result<(), Error> {
+        let backups_dir = match &self.config.schedule.backup_dir {
+            Some(backup_dir) => backup_dir.clone(),
+            None => get_config_dir()?.join("e-melder").join("backups")
+        };
+        let config_file = get_config_file()?;
+        backup_files(&backups_dir.join(format!("{SCHEDULED_BACKUP_PREFIX}{}", now.format("%Y-%m-%d_%H-%M-%S"))),
+            &config_file, &Progress::default())?;
+        prune_backups(&backups_dir, SCHEDULED_BACKUP_PREFIX, SCHEDULED_BACKUPS_KEPT)
+            .map_err(Error::io(format!("failed to remove old backups in {}", backups_dir.display())))
+    }
+
+    fn scheduled_website_roster(&self, path: &Path) -> std::io::Result<()> {
+        let contents = if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("json")) {
+            self.config.website_roster.render_json(&self.athletes)
+        }
+        else {
+            self.config.website_roster.render_html(&self.athletes)
+        };
+        write_roster(path, &contents)
+    }
+}
+
+pub fn show_schedule_config(app: &mut EMelderApp, ui: &mut Ui) {
+    ui.checkbox(&mut app.config.schedule.enabled, translate!("config.schedule", &app.translations));
+    if !app.config.schedule.enabled {
+        return;
+    }
+    ui.horizontal(|ui| {
+        ui.label(translate!("config.schedule.hour", &app.translations));
+        ui.add(egui::DragValue::new(&mut app.config.schedule.hour).range(0..=23).suffix(":00"));
+    });
+    ui.checkbox(&mut app.config.schedule.backup, translate!("config.schedule.backup", &app.translations));
+    if app.config.schedule.backup {
+        ui.horizontal(|ui| {
+            ui.label(translate!("config.schedule.backup_dir", &app.translations));
+            let backup_dir = app.config.schedule.backup_dir.as_ref().map_or_else(
+                || translate!("config.schedule.backup_dir.default", &app.translations),
+                |backup_dir| backup_dir.display().to_string());
+            if ui.button(backup_dir).clicked() {
+                if let Some(backup_dir) = rfd::FileDialog::new().set_can_create_directories(true).pick_folder() {
+                    app.config.schedule.backup_dir = Some(backup_dir);
+                }
+            }
+            if app.config.schedule.backup_dir.is_some() && ui.button("🗑").clicked() {
+                app.config.schedule.backup_dir = None;
+            }
+        });
+    }
+    ui.horizontal(|ui| {
+        ui.label(translate!("config.schedule.website_roster", &app.translations));
+        let website_roster_file = app.config.schedule.website_roster_file.as_ref().map_or_else(
+            || translate!("config.schedule.website_roster.none", &app.translations),
+            |website_roster_file| website_roster_file.display().to_string());
+        if ui.button(website_roster_file).clicked() {
+            if let Some(path) = rfd::FileDialog::new().set_can_create_directories(true).add_filter("HTML", &["html"])
+                .add_filter("JSON", &["json"]).set_file_name("roster-website.html").save_file() {
+                app.config.schedule.website_roster_file = Some(path);
+            }
+        }
+        if app.config.schedule.website_roster_file.is_some() && ui.button("🗑").clicked() {
+            app.config.schedule.website_roster_file = None;
+        }
+    });
+    match app.config.schedule.last_run {
+        Some(last_run) => {
+            let last_run = last_run.format("%d.%m.%Y %H:%M").to_string();
+            if let Some(last_error) = &app.config.schedule.last_error {
+                error_label(ui, format!("{} {last_run}: {last_error}", translate!("config.schedule.failed", &app.translations)));
+            }
+            else {
+                ui.label(format!("{} {last_run}", translate!("config.schedule.succeeded", &app.translations)));
+            }
+        }
+        None => {
+            ui.label(translate!("config.schedule.never", &app.translations));
+        }
+    }
+}
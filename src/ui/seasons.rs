@@ -0,0 +1,133 @@
+use egui::{TextWrapMode, Ui};
+use egui_extras::{Column, TableBuilder};
+
+use crate::error::Error;
+use crate::utils::{archive_season, list_seasons, translate};
+use super::EMelderApp;
+use super::theme::{error_label, warning_label};
+
+#[allow(clippy::too_many_lines)]
+pub fn show_seasons(app: &mut EMelderApp, ui: &mut Ui) {
+    ui.add_enabled_ui(!app.read_only && app.is_admin(), |ui| {
+        ui.horizontal(|ui| {
+            ui.label(translate!("seasons.name", &app.translations));
+            ui.text_edit_singleline(&mut app.seasons.name);
+        });
+        ui.checkbox(&mut app.seasons.carry_over, translate!("seasons.carry_over", &app.translations));
+
+        if ui.add_enabled(!app.seasons.name.is_empty(), egui::Button::new(translate!("seasons.archive", &app.translations)))
+            .clicked() {
+            archive(app, false);
+        }
+        if let Some(error) = app.seasons.error {
+            error_label(ui, translate!(error, &app.translations));
+        }
+        if app.seasons.confirm_overwrite {
+            warning_label(ui, translate!("seasons.exists", &app.translations));
+            ui.horizontal(|ui| {
+                if ui.button(translate!("seasons.exists.overwrite", &app.translations)).clicked() {
+                    archive(app, true);
+                }
+                if ui.button(translate!("seasons.exists.cancel", &app.translations)).clicked() {
+                    app.seasons.confirm_overwrite = false;
+                }
+            });
+        }
+    });
+
+    ui.separator();
+
+    let archived = app.seasons.archived.get_or_insert_with(|| {
+        list_seasons().unwrap_or_else(|err| {
+            log::warn!("failed to list seasons, due to {err}");
+            Vec::new()
+        })
+    });
+    if archived.is_empty() {
+        ui.label(translate!("seasons.empty", &app.translations));
+        return;
+    }
+
+    let selected = &mut app.seasons.selected;
+    egui::ComboBox::from_label(translate!("seasons.select", &app.translations))
+    .selected_text(selected.and_then(|index| archived.get(index)).map(|season| season.name.as_str()).unwrap_or_default())
+    .show_ui(ui, |ui| {
+        for (index, season) in archived.iter().enumerate() {
+            ui.selectable_value(selected, Some(index), &season.name);
+        }
+    });
+
+    let Some(season) = app.seasons.selected.and_then(|index| app.seasons.archived.as_ref()?.get(index)) else {
+        return;
+    };
+
+    ui.push_id("seasons.table", |ui| {
+        let row_height = app.row_height();
+        let table = TableBuilder::new(ui).columns(Column::auto().at_least(100.0), 5);
+
+        table.header(20.0, |mut header| {
+            header.col(|ui| {
+                ui.strong(translate!("seasons.given_name", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("seasons.sur_name", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("seasons.year", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("seasons.gender", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("seasons.belt", &app.translations));
+            });
+        }).body(|mut body| {
+            for athlete in &season.athletes {
+                body.row(row_height, |mut row| {
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(athlete.get_given_name());
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(athlete.get_sur_name());
+                    });
+                    row.col(|ui| {
+                        ui.label(athlete.get_birth_year().to_string());
+                    });
+                    row.col(|ui| {
+                        ui.label(translate!(&format!("register.table.gender_category.{}", athlete.get_gender().render()), &app.translations));
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(translate!(&format!("add.belt.{}", athlete.get_belt().serialise()), &app.translations));
+                    });
+                });
+            }
+        });
+    });
+}
+
+fn archive(app: &mut EMelderApp, overwrite: bool) {
+    app.seasons.confirm_overwrite = false;
+    app.seasons.error = None;
+    match archive_season(&app.seasons.name, &app.athletes, overwrite) {
+        Ok(()) => {
+            if app.seasons.carry_over {
+                // the athletes carried over might have moved up an age-category in the new season
+                app.check_aged_out();
+            }
+            else {
+                app.athletes.clear();
+                app.save_athletes();
+            }
+            app.seasons.archived = None;
+            app.seasons.selected = None;
+        }
+        Err(Error::Io { source, .. }) if source.kind() == std::io::ErrorKind::AlreadyExists => app.seasons.confirm_overwrite = true,
+        Err(err) => {
+            log::warn!("failed to archive season, due to {err}");
+            app.seasons.error = Some(err.translation_key());
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use egui::{Context, Key, KeyboardShortcut, Modifiers, Ui};
+
+use crate::utils::translate;
+use super::EMelderApp;
+
+pub(super) const SAVE: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::S);
+pub(super) const REGISTER: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Enter);
+
+/// Whether `shortcut` was pressed, it is consumed, so only one action is triggered by it.
+pub(super) fn pressed(ctx: &Context, shortcut: &KeyboardShortcut) -> bool {
+    ctx.input_mut(|input| input.consume_shortcut(shortcut))
+}
+
+/// The tooltip advertising `shortcut`, its keys are named as usual on the platform, e.g. ⌘S on macOS.
+pub(super) fn shortcut_hint(app: &EMelderApp, ui: &Ui, translation_key: &str, shortcut: &KeyboardShortcut) -> String {
+    format!("{} {}", translate!(translation_key, &app.translations), ui.ctx().format_shortcut(shortcut))
+}
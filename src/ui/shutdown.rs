@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+use egui::{Key, Modifiers};
+
+use crate::utils::translate;
+use super::EMelderApp;
+use super::email::finish_sending;
+use super::plugins::finish_plugin;
+use super::registering::finish_writing;
+use super::restore::finish_backup;
+
+// the app is closed anyway, if writing hangs, e.g. on an unreachable network-drive
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(15);
+
+impl EMelderApp {
+    /// Collects the finished background-tasks, also while their page is not shown, e.g. on the lock-screen.
+    pub(super) fn collect_finished_tasks(&mut self) {
+        if self.registering.writing.as_ref().is_some_and(|writing| !writing.is_running()) {
+            finish_writing(self);
+        }
+        finish_backup(self);
+        finish_sending(self);
+        finish_plugin(self);
+        if let Some(paste_import) = &mut self.paste_import {
+            paste_import.finish_parsing();
+        }
+    }
+
+    fn has_pending_tasks(&self) -> bool {
+        self.registering.writing.as_ref().is_some_and(|writing| writing.is_running())
+            || self.restore.is_backing_up()
+            || self.registering.email.as_ref().is_some_and(|email| email.is_sending())
+            || self.registering.plugin_task.as_ref().is_some_and(|task| task.is_running())
+            || self.paste_import.as_ref().is_some_and(|paste_import| paste_import.is_parsing())
+    }
+
+    /// Delays closing the app until the background-tasks are done, so no registration or backup is left half-done.
+    pub(super) fn handle_shutdown(&mut self, ctx: &egui::Context) {
+        if ctx.input_mut(|input| input.consume_key(Modifiers::COMMAND, Key::Q)) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+        let pending = self.has_pending_tasks();
+        if self.closing.is_none() && pending && ctx.input(|input| input.viewport().close_requested()) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.closing = Some(Instant::now());
+        }
+        let Some(closing) = self.closing else {
+            return;
+        };
+        if !pending || closing.elapsed() >= SHUTDOWN_TIMEOUT {
+            if pending {
+                log::warn!("closing while background-tasks are still running");
+            }
+            // `closing` is kept, so this request is not cancelled again
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            return;
+        }
+        egui::Window::new(translate!("application.closing.title", &self.translations))
+        .collapsible(false).resizable(false).show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(translate!("application.closing.text", &self.translations));
+            });
+        });
+        ctx.request_repaint_after(Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,16 @@
+use egui::{RichText, Ui};
+
+use crate::tournament_info::{SortColumn, SortingState};
+
+/// The header of a sortable column, the arrow shows the direction of the sorting, returns whether it was clicked.
+pub(super) fn sortable_header(ui: &mut Ui, text: String, column: SortColumn, sorting: &mut SortingState) -> bool {
+    let arrow = match sorting.column {
+        Some(sorted) if sorted == column => if sorting.descending { " ⏷" } else { " ⏶" },
+        _ => ""
+    };
+    let clicked = ui.add(egui::Button::new(RichText::new(format!("{text}{arrow}")).strong()).frame(false)).clicked();
+    if clicked {
+        sorting.toggle(column);
+    }
+    clicked
+}
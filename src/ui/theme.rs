@@ -0,0 +1,63 @@
+use egui::ecolor::Hsva;
+use egui::{Color32, Response, Stroke, Ui, Visuals};
+
+/// Stronger contrasts than the dark theme, the colours of warnings and errors are taken from the Okabe-Ito palette,
+/// which stays distinguishable with the common colour vision deficiencies.
+pub(super) fn high_contrast_visuals() -> Visuals {
+    let mut visuals = Visuals::dark();
+    visuals.panel_fill = Color32::BLACK;
+    visuals.window_fill = Color32::BLACK;
+    visuals.extreme_bg_color = Color32::BLACK;
+    visuals.faint_bg_color = Color32::from_gray(40);
+    visuals.window_stroke = Stroke::new(2.0, Color32::WHITE);
+    for widget in [&mut visuals.widgets.noninteractive, &mut visuals.widgets.inactive, &mut visuals.widgets.hovered,
+        &mut visuals.widgets.active, &mut visuals.widgets.open] {
+        widget.fg_stroke = Stroke::new(widget.fg_stroke.width.max(1.5), Color32::WHITE);
+        widget.bg_stroke = Stroke::new(widget.bg_stroke.width.max(1.5), Color32::WHITE);
+    }
+    visuals.widgets.inactive.bg_fill = Color32::from_gray(20);
+    visuals.widgets.inactive.weak_bg_fill = Color32::from_gray(20);
+    visuals.widgets.hovered.weak_bg_fill = Color32::from_gray(70);
+    visuals.selection.bg_fill = Color32::from_rgb(0, 114, 178);
+    visuals.selection.stroke = Stroke::new(2.0, Color32::WHITE);
+    visuals.hyperlink_color = Color32::from_rgb(86, 180, 233);
+    visuals.warn_fg_color = Color32::from_rgb(240, 228, 66);
+    visuals.error_fg_color = Color32::from_rgb(230, 159, 0);
+    visuals
+}
+
+// the symbols tell warnings and errors apart without relying on their colour
+
+pub(super) fn warning_label(ui: &mut Ui, text: impl std::fmt::Display) -> Response {
+    ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {text}"))
+}
+
+pub(super) fn error_label(ui: &mut Ui, text: impl std::fmt::Display) -> Response {
+    ui.colored_label(ui.visuals().error_fg_color, format!("✖ {text}"))
+}
+
+/// Outlines the inputs added afterwards in the colour of errors, e.g. for required fields left empty.
+pub(super) fn mark_invalid(ui: &mut Ui) {
+    let stroke = Stroke::new(1.5, ui.visuals().error_fg_color);
+    let widgets = &mut ui.visuals_mut().widgets;
+    for widget in [&mut widgets.inactive, &mut widgets.hovered, &mut widgets.active] {
+        widget.bg_stroke = stroke;
+    }
+}
+
+/// A stable tint per output-file, so the same category keeps its colour between registrations.
+pub(super) fn category_color(age_category: &str, gender_category: &str) -> Color32 {
+    // FNV-1a, as the hashers of the standard library are not stable between releases
+    let hash = format!("{age_category}|{gender_category}").bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3));
+    // the golden ratio spreads similar hashes over the hues
+    #[allow(clippy::cast_precision_loss)]
+    let hue = ((hash % 1000) as f32 * 0.618_034).fract();
+    Color32::from(Hsva::new(hue, 0.6, 0.9, 1.0)).gamma_multiply(0.25)
+}
+
+/// Tints the background of a table-cell, including the spacing to the neighbouring cells.
+pub(super) fn tint_cell(ui: &Ui, color: Color32) {
+    let spacing = ui.spacing().item_spacing;
+    ui.painter().rect_filled(ui.max_rect().expand2(spacing / 2.0), 0.0, color);
+}
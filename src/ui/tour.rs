@@ -0,0 +1,121 @@
+use egui::{Id, Order, Rect, Response, Stroke};
+
+use crate::utils::translate;
+use super::EMelderApp;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TourStep {
+    Search,
+    Add,
+    Register
+}
+
+impl TourStep {
+    fn translation_key(self) -> &'static str {
+        match self {
+            Self::Search => "tour.search",
+            Self::Add => "tour.add",
+            Self::Register => "tour.register"
+        }
+    }
+
+    fn next(self) -> Option<Self> {
+        match self {
+            Self::Search => Some(Self::Add),
+            Self::Add => Some(Self::Register),
+            Self::Register => None
+        }
+    }
+}
+
+/// The guided tour through the first registration, each step highlights the widget to use next.
+#[derive(Debug, Default)]
+pub(super) struct Tour {
+    step: Option<TourStep>,
+    // the widget of the current step, as drawn in this frame
+    target: Option<Rect>
+}
+
+impl Tour {
+    pub(super) fn highlight(&mut self, step: TourStep, response: &Response) {
+        // e.g. the first of the add-buttons
+        if self.step == Some(step) && self.target.is_none() {
+            self.target = Some(response.rect);
+        }
+    }
+}
+
+impl EMelderApp {
+    pub fn start_tour(&mut self) {
+        self.tour = Tour { step: Some(TourStep::Search), target: None };
+    }
+
+    pub fn is_touring(&self) -> bool {
+        self.tour.step.is_some()
+    }
+
+    /// Moves on, if the tour is at `step`, as the user did what the step asked for.
+    pub(super) fn advance_tour(&mut self, step: TourStep) {
+        if self.tour.step != Some(step) {
+            return;
+        }
+        self.tour.step = step.next();
+        if self.tour.step.is_none() {
+            self.finish_tour();
+        }
+    }
+
+    fn finish_tour(&mut self) {
+        self.tour = Tour::default();
+        // the tour is only started by itself once
+        if !self.config.tour_completed {
+            self.config.tour_completed = true;
+            self.save_configs();
+        }
+    }
+}
+
+/// Draws the highlight and the explanation of the current step, has to be called after the highlighted widgets.
+pub fn show_tour(app: &mut EMelderApp, ctx: &egui::Context) {
+    let Some(step) = app.tour.step else {
+        return;
+    };
+    match step {
+        TourStep::Search if !app.registering.search.is_empty() => app.advance_tour(step),
+        TourStep::Add if !app.registering.athletes.is_empty() => app.advance_tour(step),
+        _ => {}
+    }
+    let Some(step) = app.tour.step else {
+        return;
+    };
+    let Some(target) = app.tour.target.take() else {
+        return;
+    };
+
+    let painter = ctx.layer_painter(egui::LayerId::new(Order::Foreground, Id::new("tour")));
+    painter.rect_stroke(target.expand(4.0), 4.0, Stroke::new(3.0, ctx.style().visuals.warn_fg_color));
+    let mut next = false;
+    let mut skip = false;
+    egui::Area::new(Id::new("tour_popup")).order(Order::Foreground).fixed_pos(target.left_bottom() + egui::vec2(0.0, 8.0))
+    .show(ctx, |ui| {
+        egui::Frame::popup(ui.style()).show(ui, |ui| {
+            ui.set_max_width(300.0);
+            ui.label(translate!(step.translation_key(), &app.translations));
+            ui.horizontal(|ui| {
+                let next_key = if step.next().is_some() { "tour.next" } else { "tour.finish" };
+                if ui.button(translate!(next_key, &app.translations)).clicked() {
+                    next = true;
+                }
+                if ui.button(translate!("tour.skip", &app.translations)).clicked() {
+                    skip = true;
+                }
+            });
+        });
+    });
+    if next {
+        app.advance_tour(step);
+    }
+    if skip {
+        app.finish_tour();
+    }
+}
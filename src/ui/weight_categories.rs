@@ -0,0 +1,106 @@
+use egui::{TextWrapMode, Ui};
+use egui_extras::{Column, TableBuilder};
+
+use crate::tournament_info::WeightCategory;
+use crate::utils::translate;
+use super::EMelderApp;
+use super::shortcuts::{shortcut_hint, SAVE};
+
+#[allow(clippy::too_many_lines)]
+pub fn show_weight_categories(app: &mut EMelderApp, ui: &mut Ui) {
+    if app.athletes.is_empty() {
+        ui.label(translate!("weight_categories.empty", &app.translations));
+        return;
+    }
+
+    if app.new_weight_categories.len() != app.athletes.len() {
+        app.new_weight_categories = app.athletes.iter().map(|athlete| athlete.get_weight_category().to_string()).collect();
+    }
+
+    let mut copy_down = None;
+    ui.push_id("weight_categories.table", |ui| {
+        let row_height = app.row_height();
+        let table = TableBuilder::new(ui).columns(Column::auto().at_least(100.0), 5)
+            .column(Column::auto().at_least(50.0));
+
+        table.header(20.0, |mut header| {
+            header.col(|ui| {
+                ui.strong(translate!("weight_categories.given_name", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("weight_categories.sur_name", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("weight_categories.year", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("weight_categories.current", &app.translations));
+            });
+            header.col(|ui| {
+                ui.strong(translate!("weight_categories.new", &app.translations));
+            });
+            header.col(|_ui| {});
+        }).body(|mut body| {
+            for (index, (athlete, new_weight_category)) in app.athletes.iter()
+                .zip(app.new_weight_categories.iter_mut()).enumerate() {
+                body.row(row_height, |mut row| {
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(athlete.get_given_name());
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        ui.label(athlete.get_sur_name());
+                    });
+                    row.col(|ui| {
+                        ui.label(athlete.get_birth_year().to_string());
+                    });
+                    row.col(|ui| {
+                        ui.label(athlete.get_weight_category().to_string());
+                    });
+                    row.col(|ui| {
+                        ui.text_edit_singleline(new_weight_category);
+                    });
+                    row.col(|ui| {
+                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                        if ui.button(translate!("weight_categories.copy_down", &app.translations)).clicked() {
+                            copy_down = Some(index);
+                        }
+                    });
+                });
+            }
+        });
+    });
+
+    if let Some(index) = copy_down.filter(|index| *index < app.new_weight_categories.len()) {
+        let value = app.new_weight_categories[index].clone();
+        for new_weight_category in &mut app.new_weight_categories[index + 1..] {
+            new_weight_category.clone_from(&value);
+        }
+    }
+
+    if ui.button(translate!("weight_categories.save", &app.translations))
+        .on_hover_text(shortcut_hint(app, ui, "shortcut.save", &SAVE)).clicked() {
+        save_weight_categories(app);
+    }
+
+    if app.weight_categories_invalid {
+        ui.label(translate!("weight_categories.invalid", &app.translations));
+    }
+}
+
+/// Applies the edited weight-categories to the athletes, as long as all of them are valid.
+pub(super) fn save_weight_categories(app: &mut EMelderApp) {
+    let parsed: Option<Vec<WeightCategory>> = app.new_weight_categories.iter()
+        .map(|weight_category| WeightCategory::from_str(weight_category)).collect();
+    if let Some(parsed) = parsed {
+        for (athlete, weight_category) in app.athletes.iter_mut().zip(parsed) {
+            *athlete.get_weight_category_mut() = weight_category;
+        }
+        app.weight_categories_invalid = false;
+        app.save_athletes();
+    }
+    else {
+        app.weight_categories_invalid = true;
+    }
+}
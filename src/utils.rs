@@ -1,19 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
-#[cfg(not(feature="unstable"))]
-use std::fs::create_dir_all;
-use std::fs::File;
+use std::fs::{create_dir_all, read_dir, File};
 use std::io;
-#[cfg(not(feature="unstable"))]
 use std::io::Read;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
+use chrono::{Local, NaiveDate};
 use notify_rust::Timeout;
 use serde::Deserialize;
 use serde_json::Map;
+use sha2::{Digest, Sha256};
 
-use crate::tournament_info::{Athlete, Club, GenderCategory, Tournament};
+use crate::error::Error;
+use crate::tournament_info::{parse_tournament, Athlete, Club, CsvEncoding, CsvExport, Draft, GeneratedFile, GenderCategory, HistoryEntry,
+    Season, Tournament, TournamentEncoding, WeightCategory};
 use crate::ui::app::Config;
 
 #[cfg(not(feature = "unstable"))]
@@ -33,9 +36,15 @@ static API_LINK: &str = "https://api.github.com/repos/UchiWerfer/e-melder-gui/re
 static ILLEGAL_CHARS: &str = "<>:\"/\\|?*\0";
 #[cfg(not(target_os="windows"))]
 static ILLEGAL_CHARS: &str = "/\0";
+// the limits of Windows are applied everywhere, since the files usually end up on a Windows-machine
+static MAX_PATH: usize = 260;
+static RESERVED_NAMES: [&str; 22] = ["CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7",
+    "COM8", "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9"];
 pub const DEFAULT_BIRTH_YEAR: u16 = 2010;
 pub const LOWER_BOUND_BIRTH_YEAR: u16 = 1900;
 pub const UPPER_BOUND_BIRTH_YEAR: u16 = 2100;
+pub const BACKUP_CHECKSUMS: &str = "checksums.json";
+pub const DRAFT_EXTENSION: &str = "emreg";
 pub const DEFAULT_WINDOW_SIZE: [f32; 2] = [1100.0, 600.0];
 lazy_static::lazy_static! {
     pub static ref LEGAL_GENDER_CATEGORIES: enum_map::EnumMap<GenderCategory, &'static [GenderCategory]> = enum_map::enum_map! {
@@ -65,80 +74,615 @@ pub fn write_club(path: impl AsRef<Path>, club: &Club) -> io::Result<()> {
     Ok(serde_json::to_writer(club_file, club)?)
 }
 
+pub fn read_history(path: impl AsRef<Path>) -> io::Result<Vec<HistoryEntry>> {
+    let history_file = File::options().read(true).open(path)?;
+    Ok(serde_json::from_reader(history_file)?)
+}
+
+pub fn write_history(path: impl AsRef<Path>, history: &[HistoryEntry]) -> io::Result<()> {
+    let history_file = File::options().write(true).create(true).truncate(true).open(path)?;
+    Ok(serde_json::to_writer(history_file, history)?)
+}
+
+/// The folder managed by the app for the notes' attachments of a registration, next to the history-file.
+pub fn get_attachments_dir(history_file: &Path, entry: &HistoryEntry) -> PathBuf {
+    let dir_name = format!("{} {}", entry.get_date().format("%Y-%m-%d"), replace_illegal_chars(entry.get_name()));
+    history_file.parent().unwrap_or_else(|| Path::new(".")).join("attachments").join(dir_name)
+}
+
+/// Copies `source` into `attachments_dir`, a file of the same name is kept by numbering the copy.
+pub fn attach_file(attachments_dir: &Path, source: &Path) -> Result<PathBuf, Error> {
+    create_dir_all(attachments_dir).map_err(Error::io(format!("failed to create {}", attachments_dir.display())))?;
+    let file_name = source.file_name().ok_or_else(|| Error::Validation(format!("{} is no file", source.display())))?;
+    let mut target = attachments_dir.join(file_name);
+    let stem = Path::new(file_name).file_stem().unwrap_or_default().to_string_lossy().into_owned();
+    let extension = Path::new(file_name).extension().map(|extension| format!(".{}", extension.to_string_lossy())).unwrap_or_default();
+    let mut number = 2;
+    while target.exists() {
+        target = attachments_dir.join(format!("{stem} ({number}){extension}"));
+        number += 1;
+    }
+    std::fs::copy(source, &target).map_err(Error::io(format!("failed to copy {}", source.display())))?;
+    Ok(target)
+}
+
+/// Copies the invitation into the folder of the registration, so it is kept, even if the picked file is moved,
+/// returns its path within that folder.
+pub fn copy_invitation(attachments_dir: &Path, invitation: &Path) -> Result<PathBuf, Error> {
+    // the invitation of a reopened registration is in the folder already
+    let copy = if invitation.parent() == Some(attachments_dir) {
+        invitation.to_path_buf()
+    }
+    else {
+        attach_file(attachments_dir, invitation)?
+    };
+    Ok(PathBuf::from(copy.file_name().unwrap_or_default()))
+}
+
+/// The invitation of the registration, entries of older versions refer to the picked file itself.
+pub fn get_invitation_file(history_file: &Path, entry: &HistoryEntry) -> Option<PathBuf> {
+    // an absolute path replaces the folder
+    entry.get_invitation().map(|invitation| get_attachments_dir(history_file, entry).join(invitation))
+}
+
+/// The files attached to a registration, sorted by name, a missing folder has none.
+pub fn list_attachments(attachments_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(attachments_dir) else {
+        return Vec::new();
+    };
+    let mut attachments: Vec<PathBuf> = entries.filter_map(Result::ok).map(|entry| entry.path()).filter(|path| path.is_file()).collect();
+    attachments.sort();
+    attachments
+}
+
+pub fn read_draft(path: impl AsRef<Path>) -> io::Result<Draft> {
+    let draft_file = File::options().read(true).open(path)?;
+    Ok(serde_json::from_reader(draft_file)?)
+}
+
+pub fn write_draft(path: impl AsRef<Path>, draft: &Draft) -> io::Result<()> {
+    let draft_file = File::options().write(true).create(true).truncate(true).open(path)?;
+    Ok(serde_json::to_writer_pretty(draft_file, draft)?)
+}
+
+/// The registration in progress is kept here, while the app is closed.
+pub fn get_draft_file() -> Result<PathBuf, Error> {
+    Ok(get_config_dir()?.join("e-melder").join(format!("draft.{DRAFT_EXTENSION}")))
+}
+
+pub fn get_shared_draft_file(shared_dir: &Path, name: &str, date: NaiveDate, user: &str) -> PathBuf {
+    // one file per user, so coaches never overwrite each other's drafts in the shared folder
+    shared_dir.join(replace_illegal_chars(&format!("{name} {} {user}.{DRAFT_EXTENSION}", date.format("%Y-%m-%d"))))
+}
+
+/// Reads the drafts in `shared_dir` for the tournament `name` on `date`, unreadable files are skipped.
+pub fn read_shared_drafts(shared_dir: &Path, name: &str, date: NaiveDate) -> io::Result<Vec<(PathBuf, Draft)>> {
+    let mut drafts = Vec::new();
+    for entry in read_dir(shared_dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != DRAFT_EXTENSION) {
+            continue;
+        }
+        match read_draft(&path) {
+            Ok(draft) => {
+                if draft.name == name && draft.date == date {
+                    drafts.push((path, draft));
+                }
+            }
+            Err(err) => {
+                log::warn!("failed to read shared draft {}, due to {err}", path.display());
+            }
+        }
+    }
+    drafts.sort_by(|(path, _), (other, _)| path.cmp(other));
+    Ok(drafts)
+}
+
+pub fn get_default_draft_user() -> String {
+    env::var("USERNAME").or_else(|_| env::var("USER")).unwrap_or_default()
+}
+
+pub fn read_generated_files(path: impl AsRef<Path>) -> io::Result<Vec<GeneratedFile>> {
+    let generated_file = File::options().read(true).open(path)?;
+    Ok(serde_json::from_reader(generated_file)?)
+}
+
+pub fn write_generated_files(path: impl AsRef<Path>, generated_files: &[GeneratedFile]) -> io::Result<()> {
+    let generated_file = File::options().write(true).create(true).truncate(true).open(path)?;
+    Ok(serde_json::to_writer(generated_file, generated_files)?)
+}
+
+pub fn hash_file(path: impl AsRef<Path>) -> io::Result<String> {
+    let mut file = File::options().read(true).open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+// PINs are short, so their hash is slow to compute to make guessing them from the config-file expensive
+const PIN_ROUNDS: u32 = 100_000;
+const PIN_SCHEME: &str = "pbkdf2-sha256";
+
+/// A PIN, which is only kept as salted hash, so it cannot be read from the config-file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pin {
+    // empty without a PIN, otherwise "pbkdf2-sha256$<rounds>$<salt>$<hash>"
+    stored: String
+}
+
+impl Pin {
+    pub fn new(pin: &str) -> Self {
+        if pin.is_empty() {
+            return Self::default();
+        }
+        let salt = uuid::Uuid::new_v4().simple().to_string();
+        let hash = hash_pin(&salt, pin, PIN_ROUNDS);
+        Self { stored: format!("{PIN_SCHEME}${PIN_ROUNDS}${salt}${hash}") }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stored.is_empty()
+    }
+
+    /// Whether `pin` is the one set, without a PIN set every input is accepted.
+    pub fn verify(&self, pin: &str) -> bool {
+        let mut parts = self.stored.split('$');
+        match (parts.next(), parts.next().and_then(|rounds| rounds.parse().ok()), parts.next(), parts.next(), parts.next()) {
+            (Some(PIN_SCHEME), Some(rounds), Some(salt), Some(hash), None) => hash_pin(salt, pin, rounds) == hash,
+            _ => self.is_empty()
+        }
+    }
+}
+
+fn hash_pin(salt: &str, pin: &str, rounds: u32) -> String {
+    pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(pin.as_bytes(), salt.as_bytes(), rounds).iter()
+        .map(|byte| format!("{byte:02x}")).collect()
+}
+
+impl serde::Serialize for Pin {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.stored)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pin {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stored = String::deserialize(deserializer)?;
+        // configs of older versions hold the PIN itself, it is hashed with the next save
+        if stored.is_empty() || stored.starts_with(&format!("{PIN_SCHEME}$")) {
+            Ok(Self { stored })
+        }
+        else {
+            Ok(Self::new(&stored))
+        }
+    }
+}
+
 fn string_to_iso_8859_1_bytes(s: &str) -> Vec<u8> {
-    s.chars().map(|c| { c as u8 }).collect()
+    // characters beyond ISO-8859-1 cannot be encoded
+    s.chars().map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?')).collect()
+}
+
+// the characters of 0x80 to 0x9f, which are control-characters in ISO-8859-1
+const WINDOWS_1252: [Option<char>; 32] = [Some('€'), None, Some('‚'), Some('ƒ'), Some('„'), Some('…'), Some('†'), Some('‡'),
+    Some('ˆ'), Some('‰'), Some('Š'), Some('‹'), Some('Œ'), None, Some('Ž'), None, None, Some('‘'), Some('’'), Some('“'), Some('”'),
+    Some('•'), Some('–'), Some('—'), Some('˜'), Some('™'), Some('š'), Some('›'), Some('œ'), None, Some('ž'), Some('Ÿ')];
+
+pub(crate) fn string_to_windows_1252_bytes(s: &str) -> Vec<u8> {
+    s.chars().map(|c| {
+        if let Some(index) = WINDOWS_1252.iter().position(|special| *special == Some(c)) {
+            return 0x80 + u8::try_from(index).unwrap_or_default();
+        }
+        match u8::try_from(u32::from(c)) {
+            Ok(byte) if !(0x80..0xa0).contains(&byte) => byte,
+            _ => b'?'
+        }
+    }).collect()
+}
+
+pub fn encode_tournament(tournament: &Tournament) -> Vec<u8> {
+    let contents = tournament.render();
+    match tournament.get_encoding() {
+        TournamentEncoding::Latin1 => string_to_iso_8859_1_bytes(&contents),
+        TournamentEncoding::Windows1252 => string_to_windows_1252_bytes(&contents),
+        TournamentEncoding::Utf8 => contents.into_bytes()
+    }
+}
+
+fn decode_tournament(bytes: &[u8]) -> String {
+    // files of the official application might be UTF-8, Windows-1252 is a superset of the printable ISO-8859-1
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return text.to_owned();
+    }
+    bytes.iter().map(|byte| match byte {
+        0x80..=0x9f => WINDOWS_1252[usize::from(byte - 0x80)].unwrap_or(char::from(*byte)),
+        _ => char::from(*byte)
+    }).collect()
+}
+
+pub fn write_tournament(path: impl AsRef<Path>, tournament: &Tournament) -> io::Result<()> {
+    let mut file = File::options().write(true).create(true).truncate(true).open(path)?;
+    file.write_all(&encode_tournament(tournament))?;
+    file.sync_all()
+}
+
+/// Reads a tournament-file in any of the encodings written.
+pub fn read_tournament(path: impl AsRef<Path>) -> Result<Tournament, Error> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).map_err(Error::io(format!("failed to read {}", path.display())))?;
+    let text = decode_tournament(&bytes);
+    parse_tournament(&text).ok_or_else(|| Error::Validation(format!("{} is no valid tournament-file", path.display())))
+}
+
+/// The tournament-files directly in `dir`, e.g. also the ones written by the official application.
+pub fn find_tournament_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir).map_err(Error::io(format!("failed to read {}", dir.display())))?
+        .filter_map(Result::ok).map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("dm4")))
+        .collect();
+    paths.sort();
+    Ok(paths)
 }
 
-fn write_tournament(path: impl AsRef<Path>, tournament: &Tournament) -> io::Result<()> {
+pub fn read_invitation_text(path: impl AsRef<Path>) -> Result<String, Error> {
+    let path = path.as_ref();
+    let document = lopdf::Document::load(path).map_err(|err| Error::Validation(format!("{}: {err}", path.display())))?;
+    let pages: Vec<u32> = document.get_pages().into_keys().collect();
+    document.extract_text(&pages).map_err(|err| Error::Validation(format!("{}: {err}", path.display())))
+}
+
+pub fn write_csv_export(path: impl AsRef<Path>, export: &CsvExport, contents: &str) -> io::Result<()> {
+    let bytes = match export.encoding {
+        CsvEncoding::Utf8 => contents.as_bytes().to_vec(),
+        CsvEncoding::Utf8Bom => [&[0xef, 0xbb, 0xbf], contents.as_bytes()].concat(),
+        CsvEncoding::Latin1 => string_to_iso_8859_1_bytes(contents)
+    };
     let mut file = File::options().write(true).create(true).truncate(true).open(path)?;
-    file.write_all(&string_to_iso_8859_1_bytes(&tournament.render()))?;
+    file.write_all(&bytes)
+}
+
+fn verify_tournament(path: &Path, file_name: &str, tournament: &Tournament) -> Result<(), Error> {
+    // detects files, which did not reach the disk as written
+    let mut contents = Vec::new();
+    File::options().read(true).open(path).and_then(|mut file| file.read_to_end(&mut contents))
+        .map_err(Error::io(format!("failed to read back {file_name}")))?;
+    if contents != encode_tournament(tournament) {
+        return Err(Error::Validation(format!("{file_name}: the written contents differ from the expected ones")));
+    }
     Ok(())
 }
 
+pub fn get_seasons_dir() -> Result<PathBuf, Error> {
+    Ok(get_config_dir()?.join("e-melder").join("seasons"))
+}
+
+pub fn get_season_file(season: &str) -> Result<PathBuf, Error> {
+    Ok(get_seasons_dir()?.join(format!("{}.json", replace_illegal_chars(season))))
+}
+
+pub fn list_seasons() -> Result<Vec<Season>, Error> {
+    let seasons_dir = get_seasons_dir()?;
+    if !seasons_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut seasons = Vec::new();
+    let entries = std::fs::read_dir(&seasons_dir).map_err(Error::io(format!("failed to read {}", seasons_dir.display())))?;
+    for entry in entries {
+        let path = entry.map_err(Error::io(format!("failed to read {}", seasons_dir.display())))?.path();
+        seasons.push(read_season(&path).map_err(Error::io(format!("failed to read season {}", path.display())))?);
+    }
+    seasons.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(seasons)
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SeasonFile {
+    Season(Season),
+    // seasons of older versions were archived as bare athletes-files, named by the season
+    Athletes(Vec<Athlete>)
+}
+
+pub fn read_season(path: impl AsRef<Path>) -> io::Result<Season> {
+    let path = path.as_ref();
+    let season_file = File::options().read(true).open(path)?;
+    Ok(match serde_json::from_reader(season_file)? {
+        SeasonFile::Season(season) => season,
+        SeasonFile::Athletes(athletes) => Season {
+            name: path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default(), athletes
+        }
+    })
+}
+
+/// Writes the season, an existing file is only replaced with `overwrite`,
+/// e.g. seasons differing only in characters illegal in file-names share one.
+pub fn write_season(path: impl AsRef<Path>, season: &Season, overwrite: bool) -> io::Result<()> {
+    let season_file = File::options().write(true).create(true).truncate(true).create_new(!overwrite).open(path)?;
+    Ok(serde_json::to_writer(season_file, season)?)
+}
+
+pub fn archive_season(season: &str, athletes: &[Athlete], overwrite: bool) -> Result<(), Error> {
+    let seasons_dir = get_seasons_dir()?;
+    create_dir_all(&seasons_dir).map_err(Error::io(format!("failed to create {}", seasons_dir.display())))?;
+    write_season(get_season_file(season)?, &Season { name: season.to_owned(), athletes: athletes.to_vec() }, overwrite)
+        .map_err(Error::io(format!("failed to archive season {season}")))
+}
+
+pub fn write_roster(path: impl AsRef<Path>, roster: &str) -> io::Result<()> {
+    let mut file = File::options().write(true).create(true).truncate(true).open(path)?;
+    file.write_all(roster.as_bytes())
+}
+
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
 fn replace_illegal_chars(s: &str) -> String {
     s.replace(|c| ILLEGAL_CHARS.contains(c), "_")
 }
 
+fn get_home_dir() -> Result<PathBuf, Error> {
+    home::home_dir().ok_or_else(|| Error::Config(String::from("could not locate config directory")))
+}
+
 #[cfg(target_os="linux")]
-pub fn get_config_dir() -> io::Result<PathBuf> {
+pub fn get_config_dir() -> Result<PathBuf, Error> {
     // try using $XDG_CONFIG_HOME, otherwise use ~/.config
     let xdg_config = env::var("XDG_CONFIG_HOME");
     if let Ok(path) = xdg_config {
         if path.is_empty() {
-            Ok(home::home_dir().ok_or(io::Error::new(io::ErrorKind::NotFound, "could not locate config directory"))?
-            .join(".config"))
+            Ok(get_home_dir()?.join(".config"))
         }
         else {
             Ok(PathBuf::from(path))
         }
     }
     else {
-        Ok(home::home_dir().ok_or(io::Error::new(io::ErrorKind::NotFound, "could not locate config directory"))?
-            .join(".config"))
+        Ok(get_home_dir()?.join(".config"))
     }
 }
 
 #[cfg(not(target_os="linux"))]
-pub fn get_config_dir() -> io::Result<PathBuf> {
+pub fn get_config_dir() -> Result<PathBuf, Error> {
     // try using %APPDATA%, use %HOME% instead
     let app_data = env::var("APPDATA");
     if let Ok(path) = app_data {
         Ok(PathBuf::from(path))
     }
     else {
-        home::home_dir().ok_or(io::Error::new(io::ErrorKind::NotFound, "could not locate config directory"))
+        get_home_dir()
     }
 }
 
-pub fn get_config_file() -> io::Result<PathBuf> {
+pub fn get_config_file() -> Result<PathBuf, Error> {
     let base_dir = get_config_dir()?;
     Ok(base_dir.join("e-melder/config.json"))
 }
 
+pub fn is_valid_email(mail: &str) -> bool {
+    // only a syntax-check, whether the address exists can only be tested by sending a mail
+    let Some((local, domain)) = mail.split_once('@') else {
+        return false;
+    };
+    !local.is_empty() && !domain.contains('@') && !mail.chars().any(char::is_whitespace)
+        && domain.contains('.') && domain.split('.').all(|label| !label.is_empty())
+}
+
+pub fn normalize_phone(phone: &str) -> Option<String> {
+    // E.164, numbers without country-code are assumed to be german
+    // the trunk-prefix is often written in parentheses after the country-code, e.g. +49 (0)30 ...
+    let phone = phone.replace("(0)", "");
+    let mut digits: String = phone.chars().filter(|c| !matches!(c, ' ' | '-' | '/' | '(' | ')' | '.')).collect();
+    if let Some(rest) = digits.strip_prefix("00") {
+        digits = format!("+{rest}");
+    }
+    else if let Some(rest) = digits.strip_prefix('0') {
+        digits = format!("+49{rest}");
+    }
+    let number = digits.strip_prefix('+')?;
+    // E.164 allows at most 15 digits and country-codes do not start with 0
+    if number.len() < 6 || number.len() > 15 || number.starts_with('0') || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(digits)
+}
+
+pub fn get_default_history_file() -> PathBuf {
+    // used for configs written before the history-file was introduced
+    get_config_dir().map(|config_dir| config_dir.join("e-melder").join("history.json")).unwrap_or_else(|err| {
+        log::warn!("failed to get config-directory, due to {err}");
+        PathBuf::from("history.json")
+    })
+}
+
+pub fn get_default_generated_file() -> PathBuf {
+    // used for configs written before the list of generated files was introduced
+    get_config_dir().map(|config_dir| config_dir.join("e-melder").join("generated.json")).unwrap_or_else(|err| {
+        log::warn!("failed to get config-directory, due to {err}");
+        PathBuf::from("generated.json")
+    })
+}
+
 pub fn translate_fn<'a>(translation_key: &str, translations: &'a HashMap<String, String>) -> Option<&'a str> {
     translations.get(translation_key).map(String::as_str)
 }
 
-pub fn write_tournaments(tournaments: &[Tournament], configs: &Config) -> io::Result<()> {
+/// How files of an earlier registration for the same tournament are handled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExistingFiles {
+    Overwrite,
+    // the new files get a number appended, e.g. "BezirksturnierU13 (w) (2).dm4"
+    Rename,
+    // the tournaments with existing files are not written at all
+    Keep
+}
+
+/// The file-names of `tournaments`, which exist in the tournament-basedir already.
+pub fn existing_tournament_files(tournaments: &[Tournament], tournament_base: &Path, template: &str) -> Vec<String> {
+    tournaments.iter().filter_map(|tournament| get_tournament_file_name(tournament_base, template, tournament).ok()).filter(|file_name| tournament_base.join(file_name).exists()).collect()
+}
+
+fn unique_file_name(dir: &Path, file_name: &str) -> String {
+    let stem = file_name.strip_suffix(".dm4").unwrap_or(file_name);
+    (2..).map(|number| format!("{stem} ({number}).dm4")).find(|candidate| !dir.join(candidate).exists())
+        .unwrap_or_else(|| file_name.to_owned())
+}
+
+pub fn write_tournaments(tournaments: &[Tournament], configs: &Config, existing: ExistingFiles, progress: &Progress)
+-> Result<Vec<PathBuf>, Error> {
+    // returns the paths of the written files in the order of the tournaments
     if tournaments.is_empty() {
-        return Ok(());
+        return Ok(Vec::new());
     }
     let tournament_base_value = &configs.tournament_basedir;
     let tournament_base = PathBuf::from(tournament_base_value);
-    
+    progress.set_items(tournaments.iter().map(|tournament| {
+        format!("{} ({})", tournament.get_age_category(), tournament.get_gender_abbreviations().render(tournament.get_gender_category()))
+    }).collect());
+
+    // all files are written to a staging-directory first, so a failing file does not leave a half-done registration
+    let staging_dir = get_staging_dir(&tournament_base);
+    create_dir_all(&staging_dir).map_err(Error::io("failed to create staging-directory"))?;
+    let result = write_staged_tournaments(tournaments, &tournament_base, &configs.file_name_template, &staging_dir,
+        existing, progress);
+    if let Err(err) = std::fs::remove_dir_all(&staging_dir) {
+        log::warn!("failed to remove staging-directory, due to {err}");
+    }
+    result
+}
+
+pub fn get_staging_dir(tournament_base: &Path) -> PathBuf {
+    tournament_base.join(format!(".e-melder-staging-{}", std::process::id()))
+}
+
+pub const DEFAULT_FILE_NAME_TEMPLATE: &str = "{name}{age} ({gender}).dm4";
+
+/// The file-name of `tournament` by `template`, which knows the placeholders {name}, {date}, {place}, {age} and {gender}.
+pub fn get_tournament_file_name(tournament_base: &Path, template: &str, tournament: &Tournament) -> Result<String, Error> {
+    let mut template = template.trim().to_owned();
+    if !template.to_lowercase().ends_with(".dm4") {
+        template.push_str(".dm4");
+    }
+    let rendered = template.replace("{date}", &tournament.get_date().format("%Y-%m-%d").to_string())
+        .replace("{place}", tournament.get_place()).replace("{age}", tournament.get_age_category())
+        .replace("{gender}", tournament.get_gender_abbreviations().render(tournament.get_gender_category()));
+    // only the name is shortened, so the categories still tell the files apart
+    let names = rendered.matches("{name}").count();
+    let without_name = rendered.replace("{name}", "");
+    // the path has to fit in the staging-directory, which is longer than the tournament-basedir,
+    // one character is needed for the separator and one for the terminating null
+    let used = get_staging_dir(tournament_base).to_string_lossy().chars().count() + without_name.chars().count() + 2;
+    let available = MAX_PATH.checked_sub(used).ok_or_else(|| {
+        Error::Validation(String::from("the tournament-basedir is too long to write files into it"))
+    })?.checked_div(names).unwrap_or(usize::MAX);
+    let mut name = tournament.get_name().to_owned();
+    if name.chars().count() > available {
+        name = name.chars().take(available).collect();
+        log::info!("shortened the name of the tournament to {name} to stay within the maximal path-length");
+    }
+
+    let file_name = replace_illegal_chars(&rendered.replace("{name}", &name));
+    // Windows ignores the extension and trailing spaces for device-names, e.g. "nul .x" is the device
+    let stem = file_name.split('.').next().unwrap_or_default().trim_end();
+    if RESERVED_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem)) {
+        return Ok(format!("_{file_name}"));
+    }
+    Ok(file_name)
+}
+
+fn stage_tournament(tournament: &Tournament, tournament_base: &Path, template: &str, staging_dir: &Path)
+-> Result<String, Error> {
+    let file_name = get_tournament_file_name(tournament_base, template, tournament)?;
+    write_tournament(staging_dir.join(&file_name), tournament)
+        .map_err(Error::io(format!("failed to write {file_name}")))?;
+    verify_tournament(&staging_dir.join(&file_name), &file_name, tournament)?;
+    Ok(file_name)
+}
+
+fn write_staged_tournaments(tournaments: &[Tournament], tournament_base: &Path, template: &str, staging_dir: &Path,
+    existing: ExistingFiles, progress: &Progress) -> Result<Vec<PathBuf>, Error> {
+    let mut file_names = Vec::new();
     for tournament in tournaments {
-        let path = tournament_base.join(format!("{}{} ({}).dm4", replace_illegal_chars(tournament.get_name()),
-            replace_illegal_chars(tournament.get_age_category()), tournament.get_gender_category().render()));
-        write_tournament(path, tournament)?;
+        if progress.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+        let file_name = stage_tournament(tournament, tournament_base, template, staging_dir)
+            .inspect_err(|err| progress.fail(err.to_string()))?;
+        progress.advance();
+        file_names.push(file_name);
     }
 
+    let paths: Vec<PathBuf> = file_names.iter().map(|file_name| {
+        if existing == ExistingFiles::Rename && tournament_base.join(file_name).exists() {
+            tournament_base.join(unique_file_name(tournament_base, file_name))
+        }
+        else {
+            tournament_base.join(file_name)
+        }
+    }).collect();
+    // the files moved already and the overwritten ones, so all of them are undone, when a later file cannot be moved
+    let mut moved: Vec<(&Path, Option<PathBuf>)> = Vec::new();
+    for (file_name, path) in file_names.iter().zip(&paths) {
+        match move_staged_file(&staging_dir.join(file_name), path, &staging_dir.join(format!("{file_name}.previous"))) {
+            Ok(previous) => moved.push((path, previous)),
+            Err(err) => {
+                undo_moves(&moved);
+                return Err(Error::io(format!("failed to move {file_name} into the tournament-basedir"))(err));
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Moves the staged file to `path` and returns where the file overwritten by it was kept.
+fn move_staged_file(staged: &Path, path: &Path, previous: &Path) -> io::Result<Option<PathBuf>> {
+    if !path.is_file() {
+        std::fs::rename(staged, path)?;
+        return Ok(None);
+    }
+    std::fs::rename(path, previous)?;
+    if let Err(err) = std::fs::rename(staged, path) {
+        if let Err(err) = std::fs::rename(previous, path) {
+            log::warn!("failed to restore {}, due to {err}", path.display());
+        }
+        return Err(err);
+    }
+    Ok(Some(previous.to_path_buf()))
+}
+
+fn undo_moves(moved: &[(&Path, Option<PathBuf>)]) {
+    for (path, previous) in moved.iter().rev() {
+        let result = match previous {
+            Some(previous) => std::fs::rename(previous, path),
+            None => std::fs::remove_file(path)
+        };
+        if let Err(err) = result {
+            log::warn!("failed to undo writing {}, due to {err}", path.display());
+        }
+    }
+}
+
+/// Writes JSON-schemas of the config-, athletes- and club-files, so external tools can validate the files they generate.
+pub fn write_schemas(dir: &Path) -> Result<(), Error> {
+    create_dir_all(dir).map_err(Error::io(format!("failed to create {}", dir.display())))?;
+    let schemas = [
+        (dir.join("config.schema.json"), schemars::schema_for!(Config)),
+        (dir.join("athletes.schema.json"), schemars::schema_for!(Vec<Athlete>)),
+        (dir.join("club.schema.json"), schemars::schema_for!(Club))
+    ];
+    for (path, schema) in schemas {
+        let file = File::options().write(true).create(true).truncate(true).open(&path)
+            .map_err(Error::io(format!("failed to open {}", path.display())))?;
+        serde_json::to_writer_pretty(file, &schema).map_err(Error::parse(format!("failed to serialise {}", path.display())))?;
+    }
     Ok(())
 }
 
-pub fn write_configs(configs: &Config) -> io::Result<()> {
+pub fn write_configs(configs: &Config) -> Result<(), Error> {
     let config_file = get_config_file()?;
-    let file = File::options().write(true).truncate(true).open(&config_file)?;
-    serde_json::to_writer(file, configs).map_err(Into::into)
+    let file = File::options().write(true).truncate(true).open(&config_file)
+        .map_err(Error::io(format!("failed to open {}", config_file.display())))?;
+    serde_json::to_writer(file, configs).map_err(Error::parse("failed to serialise configs"))
 }
 
 #[macro_export]
@@ -181,10 +725,11 @@ macro_rules! translate_raw {
 
 pub use translate;
 
-pub fn get_configs() -> io::Result<Config> {
+pub fn get_configs() -> Result<Config, Error> {
     let config_file = get_config_file()?;
-    let file = File::options().read(true).open(config_file)?;
-    serde_json::from_reader(file).map_err(Into::into)
+    let file = File::options().read(true).open(&config_file)
+        .map_err(Error::io(format!("failed to open {}", config_file.display())))?;
+    serde_json::from_reader(file).map_err(Error::parse(format!("invalid config-file {}", config_file.display())))
 }
 
 lazy_static::lazy_static! {
@@ -196,20 +741,91 @@ lazy_static::lazy_static! {
     };
 }
 
-pub fn get_default_config() -> io::Result<(String, PathBuf)> {
-    let athletes_file = get_config_dir()?.join("e-melder").join("athletes.json");
-    let club_file = get_config_dir()?.join("e-melder").join("club.json");
-    let tournament_basedir = home::home_dir().ok_or(io::Error::other("users does not have a home-directory"))?.join("e-melder");
+pub fn get_default_config() -> Result<(String, PathBuf), Error> {
+    let config_dir = get_config_dir()?.join("e-melder");
+    let athletes_file = config_dir.join("athletes.json");
+    let club_file = config_dir.join("club.json");
+    let history_file = config_dir.join("history.json");
+    let generated_file = config_dir.join("generated.json");
+    let tournament_basedir = home::home_dir().ok_or_else(|| Error::Config(String::from("user does not have a home-directory")))?
+        .join("e-melder");
     let mut default_config = Map::new();
     default_config.insert(String::from("lang"), "de".into());
     default_config.insert(String::from("dark-mode"), false.into());
     default_config.insert(String::from("club-file"), club_file.to_str().expect("unreachable").into());
     default_config.insert(String::from("athletes-file"), athletes_file.to_str().expect("unreachable").into());
+    default_config.insert(String::from("history-file"), history_file.to_str().expect("unreachable").into());
+    default_config.insert(String::from("generated-file"), generated_file.to_str().expect("unreachable").into());
     default_config.insert(String::from("tournament-basedir"), tournament_basedir.to_str().expect("unreachable").into());
     default_config.insert(String::from("default-gender-category"), "g".into());
     Ok((serde_json::to_string(&default_config).expect("unreachable"), tournament_basedir))
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ItemState {
+    Pending,
+    Done,
+    Failed(String)
+}
+
+#[derive(Debug, Default)]
+pub struct Progress {
+    done: AtomicUsize,
+    total: AtomicUsize,
+    cancelled: AtomicBool,
+    // optional, the items are processed in order, so `done` is the index of the current one
+    items: Mutex<Vec<(String, ItemState)>>
+}
+
+impl Progress {
+    pub fn set_total(&self, total: usize) {
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    pub fn set_items(&self, items: Vec<String>) {
+        self.set_total(items.len());
+        if let Ok(mut progress_items) = self.items.lock() {
+            *progress_items = items.into_iter().map(|item| (item, ItemState::Pending)).collect();
+        }
+    }
+
+    pub fn advance(&self) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed);
+        self.set_item_state(done, ItemState::Done);
+    }
+
+    pub fn fail(&self, message: String) {
+        self.set_item_state(self.done.load(Ordering::Relaxed), ItemState::Failed(message));
+    }
+
+    fn set_item_state(&self, index: usize, state: ItemState) {
+        if let Some(item) = self.items.lock().ok().as_mut().and_then(|items| items.get_mut(index)) {
+            item.1 = state;
+        }
+    }
+
+    pub fn items(&self) -> Vec<(String, ItemState)> {
+        self.items.lock().map(|items| items.clone()).unwrap_or_default()
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fraction(&self) -> f32 {
+        let total = self.total.load(Ordering::Relaxed);
+        if total == 0 {
+            return 0.0;
+        }
+        self.done.load(Ordering::Relaxed) as f32 / total as f32
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
 #[derive(Debug)]
 pub enum UpdateAvailability {
     UpdateAvailable,
@@ -224,28 +840,31 @@ impl From<bool> for UpdateAvailability {
     }
 }
 
-pub fn check_update_available(current_version: &str) -> io::Result<UpdateAvailability> {
+pub fn check_update_available(current_version: &str) -> Result<UpdateAvailability, Error> {
     if current_version == "unstable" {
         return Ok(UpdateAvailability::RunningUnstable);
     }
     let body = reqwest::blocking::Client::builder().user_agent("").build().map_err(|err| {
-        io::Error::other(err)
+        Error::Network(format!("failed to build http-client: {err}"))
     })?.get(API_LINK).send().map_err(|err| {
-        io::Error::other(err)
+        Error::Network(format!("failed to request the latest release: {err}"))
     })?.text().map_err(|err| {
-        io::Error::other(err)
+        Error::Network(format!("failed to receive the latest release: {err}"))
     })?;
-    let parsed: serde_json::Value = serde_json::from_str(&body)?;
-    let version_value = parsed.get("tag_name").ok_or(io::Error::other("did not get \"tag_name\" attribute in api-response"))?;
-    let version = version_value.as_str().ok_or(io::Error::other("\"tag_name\" attribute is not a string"))?;
+    let parsed: serde_json::Value = serde_json::from_str(&body).map_err(Error::parse("invalid api-response"))?;
+    let version_value = parsed.get("tag_name")
+        .ok_or_else(|| Error::Network(String::from("did not get \"tag_name\" attribute in api-response")))?;
+    let version = version_value.as_str()
+        .ok_or_else(|| Error::Network(String::from("\"tag_name\" attribute is not a string")))?;
     Ok(((String::from("v") + current_version) != version).into())
 }
 
 #[cfg(not(feature="unstable"))]
-pub fn write_language(language: &str, translations: &str) -> io::Result<()> {
+pub fn write_language(language: &str, translations: &str) -> Result<(), Error> {
     let lang_file_path = get_config_dir()?.join("e-melder/lang").join(format!("{language}.json"));
-    let mut lang_file = File::options().read(false).write(true).truncate(true).create(true).open(lang_file_path)?;
-    lang_file.write_all(translations.as_bytes())
+    File::options().read(false).write(true).truncate(true).create(true).open(&lang_file_path)
+        .and_then(|mut lang_file| lang_file.write_all(translations.as_bytes()))
+        .map_err(Error::io(format!("failed to write {}", lang_file_path.display())))
 }
 
 pub fn crash() -> ! {
@@ -269,8 +888,154 @@ pub fn crash() -> ! {
     panic!()
 }
 
+pub fn get_plugins_dir() -> Result<PathBuf, Error> {
+    Ok(get_config_dir()?.join("e-melder").join("plugins"))
+}
+
+pub fn get_backup_dir(previous_version: &str) -> Result<PathBuf, Error> {
+    // the version is read from a file, so it is not trusted as part of a path
+    let previous_version: String = previous_version.trim().chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' }).collect();
+    Ok(get_config_dir()?.join("e-melder").join("backups")
+        .join(format!("{previous_version}-{}", Local::now().format("%Y-%m-%d_%H-%M-%S"))))
+}
+
+/// Copies the config-file and the files of athletes, club and history referenced by it into `backup_dir`,
+/// together with their checksums for verifying the backup before restoring it.
+pub fn backup_files(backup_dir: &Path, config_file: &Path, progress: &Progress) -> Result<(), Error> {
+    let file = File::options().read(true).open(config_file)
+        .map_err(Error::io(format!("failed to open {}", config_file.display())))?;
+    let configs: Config = serde_json::from_reader(file)
+        .map_err(Error::parse(format!("invalid config-file {}", config_file.display())))?;
+    // e.g. no history before the first registration
+    let files: Vec<(&Path, PathBuf)> = std::iter::once((config_file, backup_dir.join("config.json")))
+        .chain(backed_up_files(&configs, backup_dir)).filter(|(path, _)| path.exists()).collect();
+    progress.set_items(files.iter().map(|(path, _)| path.display().to_string()).collect());
+
+    create_dir_all(backup_dir).map_err(Error::io(format!("failed to create {}", backup_dir.display())))?;
+    let mut backed_up = Vec::new();
+    for (path, backup) in files {
+        if progress.is_cancelled() {
+            // an incomplete backup must not be restored later
+            if let Err(err) = std::fs::remove_dir_all(backup_dir) {
+                log::warn!("failed to remove cancelled backup, due to {err}");
+            }
+            return Err(Error::Cancelled);
+        }
+        let result = if path == config_file {
+            copy_config_without_secrets(config_file, &backup)
+        }
+        else {
+            std::fs::copy(path, &backup).map(|_| ()).map_err(Error::io(format!("failed to back up {}", path.display())))
+        };
+        result.inspect_err(|err| progress.fail(err.to_string()))?;
+        progress.advance();
+        backed_up.push(backup);
+    }
+
+    let mut checksums = BTreeMap::new();
+    for backup in backed_up {
+        let checksum = hash_file(&backup).map_err(Error::io(format!("failed to read {}", backup.display())))?;
+        checksums.insert(backup.file_name().unwrap_or_default().to_string_lossy().into_owned(), checksum);
+    }
+    let checksums_file = backup_dir.join(BACKUP_CHECKSUMS);
+    let file = File::options().write(true).create(true).truncate(true).open(&checksums_file)
+        .map_err(Error::io(format!("failed to create {}", checksums_file.display())))?;
+    serde_json::to_writer_pretty(file, &checksums).map_err(Error::parse(format!("failed to write {}", checksums_file.display())))
+}
+
+// the paths of the secrets in the config-file, the PIN is only hashed, but short enough to be guessed from its hash
+const CONFIG_SECRETS: [&[&str]; 3] = [&["email", "password"], &["api-token"], &["admin-pin"]];
+
+/// Copies the config-file without its secrets, as they are stored in plain text and backups are often kept elsewhere.
+fn copy_config_without_secrets(config_file: &Path, backup: &Path) -> Result<(), Error> {
+    let file = File::options().read(true).open(config_file)
+        .map_err(Error::io(format!("failed to open {}", config_file.display())))?;
+    let mut configs: serde_json::Value = serde_json::from_reader(file)
+        .map_err(Error::parse(format!("invalid config-file {}", config_file.display())))?;
+    for secret in CONFIG_SECRETS {
+        let Some((name, parents)) = secret.split_last() else {
+            continue;
+        };
+        let parent = parents.iter().try_fold(&mut configs, |value, parent| value.get_mut(*parent));
+        if let Some(parent) = parent.and_then(serde_json::Value::as_object_mut) {
+            parent.remove(*name);
+        }
+    }
+    let file = File::options().write(true).create(true).truncate(true).open(backup)
+        .map_err(Error::io(format!("failed to back up {}", config_file.display())))?;
+    serde_json::to_writer(file, &configs).map_err(Error::parse(format!("failed to back up {}", config_file.display())))
+}
+
+fn backed_up_files<'a>(configs: &'a Config, backup_dir: &Path) -> [(&'a Path, PathBuf); 3] {
+    [(&configs.athletes_file, backup_dir.join("athletes.json")), (&configs.club_file, backup_dir.join("club.json")),
+        (&configs.history_file, backup_dir.join("history.json"))]
+}
+
+/// Checks the files of the backup against its checksums, backups without checksums are not trusted.
+pub fn verify_backup(backup_dir: &Path) -> Result<BTreeMap<String, String>, Error> {
+    let checksums_file = backup_dir.join(BACKUP_CHECKSUMS);
+    let file = File::options().read(true).open(&checksums_file)
+        .map_err(Error::io(format!("failed to open {}", checksums_file.display())))?;
+    let checksums: BTreeMap<String, String> = serde_json::from_reader(file)
+        .map_err(Error::parse(format!("invalid checksums {}", checksums_file.display())))?;
+    for (name, checksum) in &checksums {
+        // the names are read from a file, so they must not point outside of the backup
+        if Path::new(name).file_name().is_none_or(|file_name| file_name != name.as_str()) {
+            return Err(Error::Validation(format!("invalid file name {name} in {}", checksums_file.display())));
+        }
+        let path = backup_dir.join(name);
+        if hash_file(&path).map_err(Error::io(format!("failed to read {}", path.display())))? != *checksum {
+            return Err(Error::Validation(format!("{} does not match its checksum", path.display())));
+        }
+    }
+    Ok(checksums)
+}
+
+/// Overwrites the files of athletes, club and history with the ones of the backup, after verifying it.
+/// The config is kept, as the backup might refer to files at other places.
+pub fn restore_backup(backup_dir: &Path, configs: &Config) -> Result<(), Error> {
+    let checksums = verify_backup(backup_dir)?;
+    for (path, backup) in backed_up_files(configs, backup_dir) {
+        // files, which did not exist at the time of the backup, are kept
+        if !backup.file_name().is_some_and(|name| checksums.contains_key(name.to_string_lossy().as_ref())) {
+            continue;
+        }
+        std::fs::copy(&backup, path).map_err(Error::io(format!("failed to restore {}", path.display())))?;
+    }
+    Ok(())
+}
+
+/// Removes all but the `keep` most recent backups in `dir`, whose names start with `prefix` and end with a timestamp.
+pub fn prune_backups(dir: &Path, prefix: &str, keep: usize) -> io::Result<()> {
+    let mut backups = Vec::new();
+    for entry in read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with(prefix) && entry.file_type()?.is_dir() {
+            backups.push(entry.path());
+        }
+    }
+    // the timestamps sort like their names
+    backups.sort();
+    let outdated = backups.len().saturating_sub(keep);
+    for backup in &backups[..outdated] {
+        std::fs::remove_dir_all(backup)?;
+    }
+    Ok(())
+}
+
+/// The version, which ran last, none before the first run.
+pub fn read_latest_version() -> Result<Option<String>, Error> {
+    let latest_version_path = get_config_dir()?.join("e-melder/latest");
+    match std::fs::read_to_string(&latest_version_path) {
+        Ok(latest_version) => Ok(Some(latest_version)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(Error::io("failed to read latest-version-file")(err))
+    }
+}
+
 #[cfg(not(feature="unstable"))]
-pub fn update_translations() -> io::Result<()> {
+pub fn update_translations() -> Result<(), Error> {
     let latest_version_path = match get_config_dir() {
         Ok(config_dir) => config_dir,
         Err(err) => {
@@ -306,15 +1071,16 @@ pub fn update_translations() -> io::Result<()> {
         }
 
         let mut latest_version_file =  File::options().create(true).write(true).truncate(true)
-            .open(&latest_version_path)?;
-        latest_version_file.write_all(VERSION.as_bytes())?;
+            .open(&latest_version_path).map_err(Error::io("failed to open latest-version-file"))?;
+        latest_version_file.write_all(VERSION.as_bytes()).map_err(Error::io("failed to write latest-version-file"))?;
     }
     else {
-        let mut latest_version_file = File::options().read(true).open(&latest_version_path)?;
+        let mut latest_version_file = File::options().read(true).open(&latest_version_path)
+            .map_err(Error::io("failed to open latest-version-file"))?;
         // x.y.z usually requires 5 bytes, one per '.' and one each for x, y and z.
         // 1 extra bytes in case of unexpectedly long versions
         let mut latest_version = String::with_capacity(6);
-        latest_version_file.read_to_string(&mut latest_version)?;
+        latest_version_file.read_to_string(&mut latest_version).map_err(Error::io("failed to read latest-version-file"))?;
         if latest_version != VERSION {
             let lang_dir = get_config_dir()?.join("e-melder/lang");
                     
@@ -340,18 +1106,43 @@ pub fn update_translations() -> io::Result<()> {
             }
 
             drop(latest_version_file);
-            let mut latest_version_file = File::options().write(true).truncate(true).open(&latest_version_path)?;
-            latest_version_file.write_all(VERSION.as_bytes())?;
+            let mut latest_version_file = File::options().write(true).truncate(true).open(&latest_version_path)
+                .map_err(Error::io("failed to open latest-version-file"))?;
+            latest_version_file.write_all(VERSION.as_bytes()).map_err(Error::io("failed to write latest-version-file"))?;
         }
     }
 
     Ok(())
 }
 
-pub fn get_translations(lang: &str) -> io::Result<HashMap<String, String>> {
-    let lang_file_name = get_config_dir()?.join("e-melder").join("lang").join(format!("{lang}.json"));
-    let lang_file = File::options().read(true).open(lang_file_name)?;
-    serde_json::from_reader(lang_file).map_err(Into::into)
+pub fn get_languages() -> Result<Vec<String>, Error> {
+    let lang_dir = get_config_dir()?.join("e-melder").join("lang");
+    if !lang_dir.exists() {
+        // recreate the lang-directory instead of failing, e.g. if it was deleted by the user
+        create_dir_all(&lang_dir).map_err(Error::io(format!("failed to create {}", lang_dir.display())))?;
+        #[cfg(not(feature="unstable"))]
+        {
+            write_language("en", DEFAULT_TRANSLATIONS_EN)?;
+            write_language("de", DEFAULT_TRANSLATIONS_DE)?;
+        }
+    }
+    let mut languages = Vec::new();
+    let entries = std::fs::read_dir(&lang_dir).map_err(Error::io(format!("failed to read {}", lang_dir.display())))?;
+    for entry in entries {
+        let entry = entry.map_err(Error::io(format!("failed to read {}", lang_dir.display())))?;
+        if let Some(language) = entry.path().file_stem().and_then(|stem| stem.to_str()) {
+            languages.push(language.to_owned());
+        }
+    }
+    Ok(languages)
+}
+
+pub fn get_translations(lang: &str) -> Result<HashMap<String, String>, Error> {
+    let lang_file_name = get_config_dir()?
+        .join("e-melder").join("lang").join(format!("{lang}.json"));
+    let lang_file = File::options().read(true).open(&lang_file_name)
+        .map_err(Error::io(format!("failed to open {}", lang_file_name.display())))?;
+    serde_json::from_reader(lang_file).map_err(Error::parse(format!("invalid translations {}", lang_file_name.display())))
 }
 
 #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -364,3 +1155,14 @@ pub fn deserialize_gender_category<'de, D>(deserializer: D) -> Result<GenderCate
 where D: serde::Deserializer<'de> {
     GenderCategory::from_str(&String::deserialize(deserializer)?).ok_or(serde::de::Error::custom("Invalid Gender category"))
 }
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+pub fn serialize_weight_category<S>(weight_category: &WeightCategory, serializer: S) -> Result<S::Ok, S::Error>
+where S: serde::Serializer {
+    serializer.serialize_str(&weight_category.to_string())
+}
+
+pub fn deserialize_weight_category<'de, D>(deserializer: D) -> Result<WeightCategory, D::Error>
+where D: serde::Deserializer<'de> {
+    WeightCategory::from_str(&String::deserialize(deserializer)?).ok_or(serde::de::Error::custom("Invalid Weight category"))
+}
@@ -0,0 +1,874 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{Datelike, Days, Local, NaiveDate};
+
+use e_melder_gui::tournament_info::{diff_athletes, find_conflicts, merge_athletes, merge_drafts, new_draft_athletes,
+    summarize_diff, AgeCategoryHint, Athlete, AthleteDiff, AthleteField, Belt, Club, Coach, Competition, CsvExport, DiffSummary, Draft, Exam, ExamCandidate, GenderCategory,
+    RegisteringAthlete, Season, Tournament, TournamentEncoding, WeightCategory};
+use e_melder_gui::api::{parse_request, ApiRequest};
+use e_melder_gui::desktop_integration::{install_desktop_entry, is_desktop_entry_installed, uninstall_desktop_entry};
+use e_melder_gui::email::{EmailSettings, RegistrationEmail};
+use e_melder_gui::error::Error;
+use e_melder_gui::export::csv::write_exam_csv;
+use e_melder_gui::export::pdf::write_exam_list;
+use e_melder_gui::exporter::{discover_plugins, Exporter, PluginExporter, Registration};
+use e_melder_gui::ui::app::{Config, Theme};
+use e_melder_gui::ui::EMelderApp;
+use e_melder_gui::utils::{backup_files, encode_tournament, get_shared_draft_file, hash_file, read_athletes, read_draft,
+    read_generated_files, prune_backups, read_history, read_season, read_tournament, restore_backup, verify_backup, read_shared_drafts, write_athletes, write_draft,
+    write_schemas, write_season, write_tournament, write_tournaments, ExistingFiles, ItemState, Pin, Progress, DRAFT_EXTENSION};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("e-melder-test-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("tournaments")).expect("failed to create temporary directory");
+    dir
+}
+
+fn config(dir: &Path) -> Config {
+    serde_json::from_value(serde_json::json!({
+        "lang": "en",
+        "dark-mode": false,
+        "athletes-file": dir.join("athletes.json"),
+        "club-file": dir.join("club.json"),
+        "history-file": dir.join("history.json"),
+        "generated-file": dir.join("generated.json"),
+        "tournament-basedir": dir.join("tournaments")
+    })).expect("invalid config")
+}
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 10, 12).expect("invalid date")
+}
+
+fn add_athletes(app: &mut EMelderApp) {
+    app.add_athlete(Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012, Belt::Kyu7,
+        WeightCategory::default(), GenderCategory::Female));
+    app.add_athlete(Athlete::new(String::from("Max"), String::from("Mustermann"), 2010, Belt::Kyu5,
+        WeightCategory::default(), GenderCategory::Male));
+}
+
+fn set_categories(athlete: &mut RegisteringAthlete, age_category: &str, weight_category: &str) {
+    *athlete.get_age_category_mut() = String::from(age_category);
+    *athlete.get_weight_category_mut() = String::from(weight_category);
+}
+
+#[test]
+fn added_athletes_are_persisted() {
+    let dir = temp_dir("added");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+
+    let athletes = read_athletes(dir.join("athletes.json")).expect("failed to read athletes");
+    assert_eq!(athletes.len(), 2);
+    assert_eq!(athletes[0].get_id(), app.get_athletes()[0].get_id());
+
+    // a restarted app has to know the same athletes
+    let app = EMelderApp::headless(config(&dir), false);
+    assert_eq!(app.get_athletes()[1].get_id(), athletes[1].get_id());
+}
+
+#[test]
+fn weight_categories_survive_restarts() {
+    let dir = temp_dir("weight");
+    // files of older versions have no weight, their athletes get the default one
+    let mut legacy = serde_json::to_value([Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012, Belt::Kyu7,
+        WeightCategory::from_str("-40").expect("invalid weight category"), GenderCategory::Female)]).expect("invalid athletes");
+    legacy[0].as_object_mut().expect("athlete is no object").remove("weight");
+    fs::write(dir.join("athletes.json"), legacy.to_string()).expect("failed to write athletes");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    assert!(app.get_athletes()[0].get_weight_category().is_default());
+
+    app.add_athlete(Athlete::new(String::from("Max"), String::from("Mustermann"), 2010, Belt::Kyu5,
+        WeightCategory::from_str("-46").expect("invalid weight category"), GenderCategory::Male));
+    let app = EMelderApp::headless(config(&dir), false);
+    assert_eq!(app.get_athletes()[1].get_weight_category().to_string(), "-46");
+}
+
+#[cfg(unix)]
+#[test]
+fn plugins_receive_the_registration_on_stdin() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = temp_dir("plugins");
+    let plugins_dir = dir.join("plugins");
+    fs::create_dir_all(&plugins_dir).expect("failed to create plugins-directory");
+    let plugin = plugins_dir.join("copy.sh");
+    fs::write(&plugin, "#!/bin/sh\ncat > registration.json\n").expect("failed to write plugin");
+    fs::set_permissions(&plugin, fs::Permissions::from_mode(0o755)).expect("failed to make plugin executable");
+    fs::write(plugins_dir.join("README.txt"), "not a plugin").expect("failed to write readme");
+
+    let plugins = discover_plugins(&plugins_dir).expect("failed to discover plugins");
+    assert_eq!(plugins.len(), 1);
+    assert_eq!(plugins[0].name(), "copy");
+
+    let mut athlete = RegisteringAthlete::from_athlete(&Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012,
+        Belt::Kyu7, WeightCategory::default(), GenderCategory::Female));
+    set_categories(&mut athlete, "U13", "-36");
+    let registration = Registration { name: "Bezirksturnier", place: "Musterstadt", date: date(), club: &Club::default(),
+        athletes: &[athlete] };
+    plugins[0].export(&registration, &dir).expect("failed to run plugin");
+    let written: serde_json::Value = serde_json::from_str(&fs::read_to_string(dir.join("registration.json"))
+        .expect("plugin wrote nothing")).expect("invalid registration");
+    assert_eq!(written["name"], "Bezirksturnier");
+    assert_eq!(written["athletes"][0]["weight"], "-36");
+}
+
+#[cfg(unix)]
+#[test]
+fn hanging_plugins_are_killed() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = temp_dir("plugins-timeout");
+    let plugin = dir.join("hang.sh");
+    fs::write(&plugin, "#!/bin/sh\nexec sleep 10\n").expect("failed to write plugin");
+    fs::set_permissions(&plugin, fs::Permissions::from_mode(0o755)).expect("failed to make plugin executable");
+
+    let started = std::time::Instant::now();
+    let result = PluginExporter::new(plugin).run(b"{}".to_vec(), &dir, Duration::from_millis(200));
+    assert!(matches!(result, Err(Error::Validation(_))));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn schemas_describe_the_files_on_disk() {
+    let dir = temp_dir("schemas");
+    write_schemas(&dir).expect("failed to write schemas");
+    let schema: serde_json::Value = serde_json::from_str(&fs::read_to_string(dir.join("athletes.schema.json"))
+        .expect("failed to read schema")).expect("invalid schema");
+    let properties = &schema["definitions"]["Athlete"]["properties"];
+    for property in ["given", "sur", "belt", "weight", "year", "gender"] {
+        assert!(properties.get(property).is_some(), "{property} is missing");
+    }
+    assert!(dir.join("config.schema.json").exists());
+    assert!(dir.join("club.schema.json").exists());
+}
+
+#[test]
+fn themes_of_older_configs_follow_dark_mode() {
+    let dir = temp_dir("theme");
+    let mut config = config(&dir);
+    assert_eq!(config.theme(), Theme::Light);
+    config.dark_mode = true;
+    assert_eq!(config.theme(), Theme::Dark);
+
+    config.set_theme(Theme::HighContrast);
+    let config: Config = serde_json::from_value(serde_json::to_value(&config).expect("invalid config")).expect("invalid config");
+    assert_eq!(config.theme(), Theme::HighContrast);
+    // older versions still get a dark theme
+    assert!(config.dark_mode);
+}
+
+#[test]
+fn registering_writes_one_file_per_category() {
+    let dir = temp_dir("register");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    set_categories(app.add_to_registering(0).expect("athlete missing"), "U13", "-36");
+    set_categories(app.add_to_registering(1).expect("athlete missing"), "U15", "-50");
+    app.register_blocking();
+
+    let mut files: Vec<String> = fs::read_dir(dir.join("tournaments")).expect("failed to read tournament-directory")
+        .map(|entry| entry.expect("failed to read entry").file_name().to_string_lossy().into_owned()).collect();
+    files.sort();
+    assert_eq!(files, ["BezirksturnierU13 (w).dm4", "BezirksturnierU15 (m).dm4"]);
+
+    let contents = fs::read(dir.join("tournaments").join("BezirksturnierU13 (w).dm4")).expect("failed to read tournament-file");
+    let contents: String = contents.iter().map(|byte| char::from(*byte)).collect();
+    assert!(contents.contains("Turnier=Bezirksturnier"));
+    assert!(contents.contains("Datum=12.10.2025"));
+    assert!(contents.contains("\"Mustermann\",\"Erika\""));
+
+    let generated = read_generated_files(dir.join("generated.json")).expect("failed to read generated files");
+    assert_eq!(generated.len(), 2);
+    assert_eq!(generated[0].get_sha256(), hash_file(generated[0].get_path()).expect("failed to hash generated file"));
+}
+
+#[test]
+fn written_registrations_can_be_reopened() {
+    let dir = temp_dir("reopen");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    set_categories(app.add_to_registering(0).expect("athlete missing"), "U13", "-36");
+    set_categories(app.add_to_registering(1).expect("athlete missing"), "U15", "-50");
+    app.register_blocking();
+
+    let history = read_history(dir.join("history.json")).expect("failed to read history");
+    assert_eq!(history[0].get_files().len(), 2);
+    assert_eq!(history[0].get_files()[0].get_path(), dir.join("tournaments").join("BezirksturnierU13 (w).dm4"));
+
+    // the history is read again, as after a restart
+    let mut app = EMelderApp::headless(config(&dir), false);
+    let athletes = app.reopen_history_entry(0).expect("history-entry missing");
+    assert_eq!(athletes.iter().map(|athlete| (athlete.get_age_category(), athlete.get_weight_category())).collect::<Vec<_>>(),
+        [("U13", "-36"), ("U15", "-50")]);
+}
+
+#[test]
+fn demo_data_leaves_the_real_files_alone() {
+    let dir = temp_dir("demo");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    app.load_demo().expect("failed to load demo-data");
+    assert!(app.is_demo());
+    assert_eq!(app.get_athletes().len(), 8);
+    app.add_athlete(Athlete::new(String::from("Lena"), String::from("Probe"), 2014, Belt::Kyu8, WeightCategory::default(),
+        GenderCategory::Female));
+    assert_eq!(read_athletes(dir.join("athletes.json")).expect("failed to read athletes").len(), 2);
+
+    app.exit_demo();
+    assert!(!app.is_demo());
+    assert_eq!(app.get_athletes().len(), 2);
+}
+
+#[test]
+fn repeated_errors_are_coalesced() {
+    let dir = temp_dir("notify");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    assert!(app.notify_error(String::from("failed to write athletes")));
+    assert!(!app.notify_error(String::from("failed to write athletes")));
+    assert!(app.notify_error(String::from("failed to write club")));
+}
+
+#[test]
+fn failing_to_move_a_file_undoes_the_others() {
+    let dir = temp_dir("move-rollback");
+    let config = config(&dir);
+    let tournaments: Vec<Tournament> = ["U13", "U15"].into_iter().map(|age_category| Tournament::new(String::from("Bezirksturnier"),
+        date(), String::from("Musterstadt"), String::from(age_category), GenderCategory::Female, Club::default(), Vec::new())).collect();
+    let earlier = dir.join("tournaments").join("BezirksturnierU13 (w).dm4");
+    fs::write(&earlier, "frühere Meldung").expect("failed to write earlier file");
+    // a folder cannot be overwritten by the file of the second tournament
+    fs::create_dir_all(dir.join("tournaments").join("BezirksturnierU15 (w).dm4").join("blockiert")).expect("failed to create folder");
+
+    assert!(write_tournaments(&tournaments, &config, ExistingFiles::Overwrite, &Progress::default()).is_err());
+    assert_eq!(fs::read_to_string(&earlier).expect("earlier file missing"), "frühere Meldung");
+    assert!(dir.join("tournaments").join("BezirksturnierU15 (w).dm4").is_dir());
+}
+
+#[test]
+fn progress_reports_the_state_of_each_file() {
+    let progress = Progress::default();
+    progress.set_items(vec![String::from("U13 (w)"), String::from("U13 (m)"), String::from("U15 (w)")]);
+    progress.advance();
+    progress.fail(String::from("disk full"));
+    assert_eq!(progress.items().into_iter().map(|(_, state)| state).collect::<Vec<_>>(),
+        [ItemState::Done, ItemState::Failed(String::from("disk full")), ItemState::Pending]);
+}
+
+#[test]
+fn api_creates_and_exports_registrations() {
+    let dir = temp_dir("api");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    let id = app.get_athletes()[0].get_id();
+    let upcoming = (Local::now().date_naive() + Days::new(30)).to_string();
+
+    let unknown = serde_json::json!({ "name": "Bezirksturnier", "place": "Musterstadt", "date": upcoming,
+        "athletes": [{ "id": uuid::Uuid::new_v4(), "age-category": "U13", "weight-category": "-36" }] }).to_string();
+    let request = parse_request("POST", "/registration", Some("Bearer geheim"), &unknown, "geheim").expect("invalid request");
+    assert_eq!(app.handle_api_request(request).status, 404);
+
+    let body = serde_json::json!({ "name": "Bezirksturnier", "place": "Musterstadt", "date": upcoming,
+        "athletes": [{ "id": id, "age-category": "U13", "weight-category": "-36" }] }).to_string();
+    let request = parse_request("POST", "/registration", Some("Bearer geheim"), &body, "geheim").expect("invalid request");
+    assert_eq!(app.handle_api_request(request).status, 201);
+    assert_eq!(app.handle_api_request(ApiRequest::Export).status, 202);
+    app.finish_writing_blocking();
+    assert_eq!(fs::read_dir(dir.join("tournaments")).expect("failed to read tournament-directory").count(), 1);
+
+    // the registration in the app is not replaced unseen
+    let request = parse_request("POST", "/registration", Some("Bearer geheim"), &body, "geheim").expect("invalid request");
+    assert_eq!(app.handle_api_request(request).status, 409);
+}
+
+#[test]
+fn api_exports_need_the_confirmations_of_the_app() {
+    let dir = temp_dir("api-unconfirmed");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    let id = app.get_athletes()[0].get_id();
+    let body = serde_json::json!({ "name": "Bezirksturnier", "place": "Musterstadt", "date": date().to_string(),
+        "athletes": [{ "id": id, "age-category": "U13", "weight-category": "-36" }] }).to_string();
+    let request = parse_request("POST", "/registration", Some("Bearer geheim"), &body, "geheim").expect("invalid request");
+    assert_eq!(app.handle_api_request(request).status, 201);
+
+    // a tournament in the past is asked about in the app
+    assert_eq!(app.handle_api_request(ApiRequest::Export).status, 409);
+    let upcoming = Local::now().date_naive() + Days::new(30);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), upcoming);
+    assert_eq!(app.handle_api_request(ApiRequest::Export).status, 202);
+    app.finish_writing_blocking();
+
+    // as is an athlete registered for another tournament on the same day
+    app.set_tournament(String::from("Pokalturnier"), String::from("Beispielheim"), upcoming);
+    assert_eq!(app.handle_api_request(ApiRequest::Export).status, 409);
+    assert_eq!(fs::read_dir(dir.join("tournaments")).expect("failed to read tournament-directory").count(), 1);
+}
+
+#[test]
+fn invalid_weight_category_writes_nothing() {
+    let dir = temp_dir("invalid");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    set_categories(app.add_to_registering(0).expect("athlete missing"), "U13", "36");
+    app.register_blocking();
+
+    assert_eq!(fs::read_dir(dir.join("tournaments")).expect("failed to read tournament-directory").count(), 0);
+    assert!(!dir.join("history.json").exists());
+}
+
+#[test]
+fn registration_is_recorded_for_conflict_detection() {
+    let dir = temp_dir("history");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    set_categories(app.add_to_registering(0).expect("athlete missing"), "U13", "-36");
+    app.register_blocking();
+
+    let history = read_history(dir.join("history.json")).expect("failed to read history");
+    let registering = [RegisteringAthlete::from_athlete(&app.get_athletes()[0]),
+        RegisteringAthlete::from_athlete(&app.get_athletes()[1])];
+    let conflicts = find_conflicts(&history, &registering, date());
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].1.get_given_name(), "Erika");
+    assert!(find_conflicts(&history, &registering, date().succ_opt().expect("invalid date")).is_empty());
+}
+
+#[test]
+fn drafts_keep_the_selection() {
+    let dir = temp_dir("draft");
+    let mut athlete = RegisteringAthlete::from_athlete(&Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012,
+        Belt::Kyu7, WeightCategory::default(), GenderCategory::Female));
+    set_categories(&mut athlete, "U13", "-36");
+    let draft = Draft { name: String::from("Bezirksturnier"), place: String::from("Musterstadt"), date: date(), athletes: vec![athlete], competition: Competition::Individual, coach: Coach::default() };
+    let path = dir.join(format!("draft.{DRAFT_EXTENSION}"));
+    write_draft(&path, &draft).expect("failed to write draft");
+
+    let imported = read_draft(&path).expect("failed to read draft");
+    assert_eq!(imported.name, "Bezirksturnier");
+    assert_eq!(imported.date, date());
+    assert_eq!(imported.athletes[0].get_given_name(), "Erika");
+    assert_eq!(imported.athletes[0].get_age_category(), "U13");
+    assert_eq!(imported.athletes[0].get_weight_category(), "-36");
+}
+
+#[test]
+fn shared_drafts_are_merged_per_tournament() {
+    let dir = temp_dir("shared");
+    let erika = RegisteringAthlete::from_athlete(&Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012,
+        Belt::Kyu7, WeightCategory::default(), GenderCategory::Female));
+    let max = RegisteringAthlete::from_athlete(&Athlete::new(String::from("Max"), String::from("Mustermann"), 2010,
+        Belt::Kyu5, WeightCategory::default(), GenderCategory::Male));
+    // the same athlete entered on another machine has another id
+    let other_erika = RegisteringAthlete::from_athlete(&Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012,
+        Belt::Kyu7, WeightCategory::default(), GenderCategory::Female));
+    let draft = |athletes| Draft { name: String::from("Bezirksturnier"), place: String::from("Musterstadt"), date: date(), athletes, competition: Competition::Individual, coach: Coach::default() };
+    write_draft(get_shared_draft_file(&dir, "Bezirksturnier", date(), "jugend"), &draft(vec![other_erika, max]))
+        .expect("failed to write draft");
+    let other_date = date().succ_opt().expect("invalid date");
+    write_draft(get_shared_draft_file(&dir, "Bezirksturnier", other_date, "jugend"),
+        &Draft { date: other_date, ..draft(Vec::new()) }).expect("failed to write draft");
+
+    let mut drafts = read_shared_drafts(&dir, "Bezirksturnier", date()).expect("failed to read shared drafts");
+    assert_eq!(drafts.len(), 1);
+    let mut ours = vec![erika];
+    assert_eq!(new_draft_athletes(&ours, &drafts[0].1.athletes).len(), 1);
+    assert_eq!(merge_drafts(&mut ours, drafts.remove(0).1.athletes), 1);
+    assert_eq!(ours[1].get_given_name(), "Max");
+}
+
+#[test]
+fn out_of_range_athletes_are_not_registered() {
+    let dir = temp_dir("range");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    assert!(app.add_to_registering(2).is_none());
+}
+
+#[test]
+fn failed_saving_keeps_athletes() {
+    let dir = temp_dir("unwritable");
+    let mut configs = config(&dir);
+    configs.athletes_file = dir.join("missing").join("athletes.json");
+    let mut app = EMelderApp::headless(configs, false);
+    add_athletes(&mut app);
+    assert_eq!(app.get_athletes().len(), 2);
+    assert!(!dir.join("missing").exists());
+}
+
+#[test]
+fn merging_athletes_keeps_own_ids() {
+    let ours = vec![Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012, Belt::Kyu7,
+        WeightCategory::default(), GenderCategory::Female)];
+    // the other file was written before ids were introduced, so its athletes are matched by their names
+    let mut legacy = serde_json::to_value([Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012, Belt::Kyu6,
+        WeightCategory::default(), GenderCategory::Female), Athlete::new(String::from("Max"), String::from("Mustermann"),
+        2010, Belt::Kyu5, WeightCategory::default(), GenderCategory::Male)]).expect("failed to serialise athletes");
+    for athlete in legacy.as_array_mut().expect("no array") {
+        athlete.as_object_mut().expect("no object").remove("id");
+    }
+    let mut theirs: Vec<Athlete> = serde_json::from_value(legacy).expect("failed to deserialise athletes");
+    *theirs[1].get_group_mut() = String::from("Montag");
+
+    let diffs = diff_athletes(&ours, &theirs);
+    assert_eq!(diffs.len(), 2);
+    assert!(matches!(&diffs[0], AthleteDiff::Changed { index: 0, fields, .. } if fields == &[AthleteField::Belt]));
+    assert!(matches!(&diffs[1], AthleteDiff::Added(athlete) if athlete.get_given_name() == "Max"));
+
+    let mut merged = ours.clone();
+    merge_athletes(&mut merged, diffs);
+    assert_eq!(merged.len(), 2);
+    assert_eq!(merged[0].get_id(), ours[0].get_id());
+    assert_eq!(*merged[0].get_belt(), Belt::Kyu6);
+    assert!(diff_athletes(&merged, &theirs).is_empty());
+}
+
+#[test]
+fn merging_the_file_of_another_laptop_matches_the_athletes_by_name() {
+    let dir = temp_dir("merge");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    // the other laptop assigned its own ids to the same athletes
+    let theirs = [Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012, Belt::Kyu6,
+        WeightCategory::default(), GenderCategory::Female), Athlete::new(String::from("Max"), String::from("Mustermann"),
+        2010, Belt::Kyu5, WeightCategory::default(), GenderCategory::Male), Athlete::new(String::from("Moritz"),
+        String::from("Mustermann"), 2014, Belt::Kyu8, WeightCategory::default(), GenderCategory::Male)];
+    let file = dir.join("other-laptop.json");
+    write_athletes(&file, &theirs).expect("failed to write athletes");
+    app.load_merge_file(file).expect("failed to load the other file");
+
+    let diffs = app.merge_diffs();
+    assert_eq!(diffs.len(), 2);
+    assert!(matches!(&diffs[0], (0, AthleteDiff::Changed { index: 0, fields, .. }) if fields == &[AthleteField::Belt]));
+    assert!(matches!(&diffs[1], (2, AthleteDiff::Added(athlete)) if athlete.get_given_name() == "Moritz"));
+    let own_ids: Vec<_> = app.get_athletes().iter().map(Athlete::get_id).collect();
+    app.commit_merge();
+    assert_eq!(app.get_athletes().len(), 3);
+    assert_eq!(app.get_athletes()[..2].iter().map(Athlete::get_id).collect::<Vec<_>>(), own_ids);
+    assert_eq!(*app.get_athletes()[0].get_belt(), Belt::Kyu6);
+    assert!(app.merge_diffs().is_empty());
+    assert_eq!(read_athletes(dir.join("athletes.json")).expect("failed to read athletes").len(), 3);
+}
+
+#[test]
+fn backups_contain_config_and_referenced_files() {
+    let dir = temp_dir("backup");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    let config_file = dir.join("config.json");
+    let mut configs = config(&dir);
+    configs.email.password = String::from("geheim");
+    configs.api_token = String::from("api-geheimnis");
+    configs.admin_pin = Pin::new("4711");
+    fs::write(&config_file, serde_json::to_string(&configs).expect("failed to serialise config"))
+        .expect("failed to write config-file");
+
+    let backup_dir = dir.join("backups").join("3.3.0");
+    let progress = Progress::default();
+    backup_files(&backup_dir, &config_file, &progress).expect("failed to back up files");
+    // the secrets are stored in plain text, so they are left out
+    let backed_up_config = fs::read_to_string(backup_dir.join("config.json")).expect("failed to read backed up config");
+    assert!(!backed_up_config.contains("geheim"));
+    assert!(!backed_up_config.contains("api-token"));
+    assert!(!backed_up_config.contains("admin-pin"));
+    assert!(backed_up_config.contains("athletes-file"));
+    verify_backup(&backup_dir).expect("backup does not match its checksums");
+    assert_eq!(read_athletes(backup_dir.join("athletes.json")).expect("failed to read backed up athletes").len(), 2);
+    // files, which do not exist yet, are skipped
+    assert!(!backup_dir.join("history.json").exists());
+    assert!(progress.items().iter().all(|(_, state)| *state == ItemState::Done));
+    assert!((progress.fraction() - 1.0).abs() < f32::EPSILON);
+
+    // a cancelled backup is removed, so it cannot be restored incompletely
+    let cancelled = Progress::default();
+    cancelled.cancel();
+    let cancelled_dir = dir.join("backups").join("cancelled");
+    assert!(matches!(backup_files(&cancelled_dir, &config_file, &cancelled), Err(Error::Cancelled)));
+    assert!(!cancelled_dir.exists());
+}
+
+#[test]
+fn restoring_verifies_the_backup_first() {
+    let dir = temp_dir("restore");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    let config_file = dir.join("config.json");
+    fs::write(&config_file, serde_json::to_string(&config(&dir)).expect("failed to serialise config"))
+        .expect("failed to write config-file");
+    let backup_dir = dir.join("backups").join("scheduled-2025-10-12_02-00-00");
+    backup_files(&backup_dir, &config_file, &Progress::default()).expect("failed to back up files");
+
+    let mut athletes = read_athletes(dir.join("athletes.json")).expect("failed to read athletes");
+    athletes.remove(0);
+    *athletes[0].get_group_mut() = String::from("Wettkampf");
+    fs::write(dir.join("athletes.json"), serde_json::to_string(&athletes).expect("failed to serialise athletes"))
+        .expect("failed to write athletes");
+    let backup = read_athletes(backup_dir.join("athletes.json")).expect("failed to read backed up athletes");
+    assert_eq!(summarize_diff(&athletes, &backup), DiffSummary { added: 1, removed: 0, changed: 1 });
+
+    // a damaged backup does not overwrite anything
+    let backed_up = fs::read(backup_dir.join("athletes.json")).expect("failed to read backed up athletes");
+    fs::write(backup_dir.join("athletes.json"), serde_json::to_string(&backup[..1]).expect("failed to serialise athletes"))
+        .expect("failed to write athletes");
+    assert!(verify_backup(&backup_dir).is_err());
+    assert!(restore_backup(&backup_dir, &config(&dir)).is_err());
+    assert_eq!(read_athletes(dir.join("athletes.json")).expect("failed to read athletes").len(), 1);
+
+    fs::write(backup_dir.join("athletes.json"), backed_up).expect("failed to write athletes");
+    restore_backup(&backup_dir, &config(&dir)).expect("failed to restore backup");
+    assert_eq!(read_athletes(dir.join("athletes.json")).expect("failed to read athletes").len(), 2);
+}
+
+#[test]
+fn only_the_latest_scheduled_backups_are_kept() {
+    let dir = temp_dir("prune");
+    let backups_dir = dir.join("backups");
+    for name in ["scheduled-2025-10-10_02-00-00", "scheduled-2025-10-12_02-00-00", "scheduled-2025-10-11_02-00-00", "3.3.0"] {
+        fs::create_dir_all(backups_dir.join(name)).expect("failed to create backup");
+    }
+    prune_backups(&backups_dir, "scheduled-", 2).expect("failed to prune backups");
+    let mut backups: Vec<String> = fs::read_dir(&backups_dir).expect("failed to read backup-directory")
+        .map(|entry| entry.expect("failed to read entry").file_name().to_string_lossy().into_owned()).collect();
+    backups.sort();
+    // backups before updates are not pruned
+    assert_eq!(backups, ["3.3.0", "scheduled-2025-10-11_02-00-00", "scheduled-2025-10-12_02-00-00"]);
+}
+
+#[test]
+fn age_category_hints_prefill_the_registration() {
+    let dir = temp_dir("hints");
+    let mut config = config(&dir);
+    config.age_category_hints.push(AgeCategoryHint { birth_year: 2012, age_category: String::from("U14") });
+    let mut app = EMelderApp::headless(config, false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    assert_eq!(app.add_to_registering(0).expect("athlete missing").get_age_category(), "U14");
+    // athletes without a hint get the age-category of the federation
+    assert_eq!(app.add_to_registering(1).expect("athlete missing").get_age_category(), "U18");
+}
+
+#[test]
+fn outdated_weight_categories_are_updated() {
+    let dir = temp_dir("aged-out");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    // U15 this year, -31 is a class of the U13 only
+    let birth_year = u16::try_from(Local::now().year() - 13).expect("invalid birth year");
+    app.add_athlete(Athlete::new(String::from("Max"), String::from("Mustermann"), birth_year, Belt::Kyu6,
+        WeightCategory::from_str("-31").expect("invalid weight category"), GenderCategory::Male));
+    app.add_athlete(Athlete::new(String::from("Moritz"), String::from("Mustermann"), birth_year, Belt::Kyu6,
+        WeightCategory::from_str("-40").expect("invalid weight category"), GenderCategory::Male));
+    assert_eq!(app.check_aged_out(), 1);
+
+    app.update_aged_out();
+    assert_eq!(app.get_athletes()[0].get_weight_category().to_string(), "-34");
+    assert_eq!(read_athletes(dir.join("athletes.json")).expect("failed to read athletes")[0].get_weight_category().to_string(), "-34");
+    assert_eq!(app.check_aged_out(), 0);
+}
+
+#[test]
+fn registering_again_keeps_the_earlier_files() {
+    let dir = temp_dir("register-again");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    set_categories(app.add_to_registering(0).expect("athlete missing"), "U13", "-36");
+    app.register_blocking();
+    app.register_blocking();
+
+    let mut files: Vec<String> = fs::read_dir(dir.join("tournaments")).expect("failed to read tournament-directory")
+        .map(|entry| entry.expect("failed to read entry").file_name().to_string_lossy().into_owned()).collect();
+    files.sort();
+    assert_eq!(files, ["BezirksturnierU13 (w) (2).dm4", "BezirksturnierU13 (w).dm4"]);
+}
+
+#[test]
+fn files_are_attached_to_registrations() {
+    let dir = temp_dir("attachments");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    set_categories(app.add_to_registering(0).expect("athlete missing"), "U13", "-36");
+    app.register_blocking();
+
+    let results = dir.join("results.pdf");
+    fs::write(&results, "%PDF").expect("failed to write results");
+    let first = app.attach_to_history_entry(0, &results).expect("failed to attach results");
+    let second = app.attach_to_history_entry(0, &results).expect("failed to attach results");
+    assert_ne!(first, second);
+    assert!(first.starts_with(dir.join("attachments")));
+    // sorted by name, "results (2).pdf" comes first
+    assert_eq!(app.get_history_attachments(0), [second, first]);
+    assert!(app.attach_to_history_entry(1, &results).is_err());
+}
+
+#[test]
+fn invitations_are_kept_with_their_registration() {
+    let dir = temp_dir("invitation");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    set_categories(app.add_to_registering(0).expect("athlete missing"), "U13", "-36");
+    app.register_blocking();
+
+    let invitation = dir.join("Ausschreibung.pdf");
+    fs::write(&invitation, "%PDF").expect("failed to write invitation");
+    app.set_history_invitation(0, &invitation).expect("failed to attach invitation");
+    // the picked file might be moved or deleted afterwards
+    fs::remove_file(&invitation).expect("failed to remove invitation");
+    let copy = app.get_history_invitation(0).expect("invitation missing");
+    assert!(copy.starts_with(dir.join("attachments")) && copy.exists());
+    // the history refers to the copy relative to the folder of the registration
+    let history = fs::read_to_string(dir.join("history.json")).expect("failed to read history");
+    assert!(history.contains("\"invitation\":\"Ausschreibung.pdf\""));
+
+    // a reopened registration keeps its copy instead of copying it again
+    app.reopen_history_entry(0).expect("history-entry missing");
+    app.register_blocking();
+    assert_eq!(app.get_history_invitation(1), Some(copy));
+    assert_eq!(app.get_history_attachments(1).len(), 1);
+}
+
+#[test]
+fn history_is_exported_with_the_categories() {
+    let dir = temp_dir("history-csv");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    set_categories(app.add_to_registering(0).expect("athlete missing"), "U13", "-36");
+    set_categories(app.add_to_registering(1).expect("athlete missing"), "U15", "-50");
+    app.register_blocking();
+
+    let history = read_history(dir.join("history.json")).expect("failed to read history");
+    let csv = CsvExport::default().render_history(&history);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("Datum;Turnier;Ort;Nachname"));
+    assert!(lines.contains(&"12.10.2025;Bezirksturnier;Musterstadt;Mustermann;Erika;2012;w;7. Kyu;U13;w;-36"));
+}
+
+#[test]
+fn registrations_of_the_official_application_are_imported() {
+    let dir = temp_dir("foreign");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    set_categories(app.add_to_registering(0).expect("athlete missing"), "U13", "-36");
+    app.register_blocking();
+    // e.g. written by the official application before migrating
+    let tournaments = dir.join("tournaments");
+    fs::copy(tournaments.join("BezirksturnierU13 (w).dm4"), tournaments.join("Altes Turnier.dm4")).expect("failed to copy file");
+    fs::write(tournaments.join("kaputt.dm4"), "kein Turnier").expect("failed to write file");
+
+    assert_eq!(app.scan_foreign_registrations().expect("failed to scan"), 1);
+    app.import_foreign_registrations();
+    let history = read_history(dir.join("history.json")).expect("failed to read history");
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].get_files()[0].get_path(), tournaments.join("Altes Turnier.dm4"));
+    assert_eq!(app.scan_foreign_registrations().expect("failed to scan"), 0);
+}
+
+#[test]
+fn tournament_files_are_written_in_the_selected_encoding() {
+    let dir = temp_dir("encoding");
+    let date = NaiveDate::from_ymd_opt(2025, 10, 12).expect("invalid date");
+    let athletes = vec![Athlete::new(String::from("Łukasz"), String::from("Šimić"), 2012, Belt::Kyu7,
+        WeightCategory::from_str("-37").expect("invalid weight category"), GenderCategory::Male)];
+    let mut tournament = Tournament::new(String::from("Bezirksturnier"), date, String::from("Musterstadt"), String::from("U13"),
+        GenderCategory::Male, Club::default(), athletes);
+
+    let contains = |bytes: &[u8], expected: &[u8]| bytes.windows(expected.len()).any(|window| window == expected);
+    // ISO-8859-1 cannot encode either name, Windows-1252 knows the "Š" at least
+    assert!(contains(&encode_tournament(&tournament), b"\"?imi?\",\"?ukasz\""));
+    tournament.set_encoding(TournamentEncoding::Windows1252);
+    assert!(contains(&encode_tournament(&tournament), b"\"\x8aimi?\",\"?ukasz\""));
+
+    tournament.set_encoding(TournamentEncoding::Utf8);
+    let path = dir.join("Bezirksturnier.dm4");
+    write_tournament(&path, &tournament).expect("failed to write tournament-file");
+    let read = read_tournament(&path).expect("failed to read tournament-file");
+    assert_eq!(read.get_athletes()[0].get_given_name(), "Łukasz");
+    assert_eq!(read.get_athletes()[0].get_sur_name(), "Šimić");
+}
+
+#[test]
+fn inactive_app_is_locked_until_the_pin_is_entered() {
+    let dir = temp_dir("auto-lock");
+    let mut config = config(&dir);
+    config.auto_lock.enabled = true;
+    config.auto_lock.minutes = 5;
+    config.auto_lock.pin = Pin::new("1234");
+    let mut app = EMelderApp::headless(config, false);
+    let now = std::time::Instant::now();
+    assert!(!app.lock_if_inactive(now + std::time::Duration::from_secs(4 * 60)));
+    assert!(app.lock_if_inactive(now + std::time::Duration::from_secs(5 * 60)));
+
+    assert!(!app.unlock("4321"));
+    assert!(app.is_locked());
+    assert!(app.unlock("1234"));
+    assert!(!app.is_locked());
+}
+
+#[test]
+fn entry_lists_are_written_next_to_the_tournament_files() {
+    let dir = temp_dir("entry-list");
+    let mut config = config(&dir);
+    config.entry_list_pdf = true;
+    config.entry_list_csv = true;
+    let mut app = EMelderApp::headless(config, false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    set_categories(app.add_to_registering(0).expect("athlete missing"), "U13", "-36");
+    app.register_blocking();
+
+    let document = lopdf::Document::load(dir.join("tournaments").join("BezirksturnierU13 (w).pdf")).expect("failed to read entry list");
+    let text = document.extract_text(&[1]).expect("failed to extract text");
+    assert!(text.contains("Mustermann"));
+    assert!(text.contains("7. Kyu"));
+    assert!(text.contains("-36"));
+
+    // UTF-8 with BOM by default, as spreadsheet-applications expect it
+    let csv = fs::read_to_string(dir.join("tournaments").join("BezirksturnierU13 (w).csv")).expect("failed to read CSV");
+    assert_eq!(csv, "\u{feff}Nachname;Vorname;Jahrgang;Gürtel;Gewichtsklasse;Altersklasse;Wertungsklasse\r\n\
+        Mustermann;Erika;2012;7. Kyu;-36;U13;w\r\n");
+}
+
+#[test]
+fn opened_drafts_can_be_registered() {
+    let dir = temp_dir("open-file");
+    let mut athlete = RegisteringAthlete::from_athlete(&Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012,
+        Belt::Kyu7, WeightCategory::default(), GenderCategory::Female));
+    set_categories(&mut athlete, "U13", "-36");
+    let draft = Draft { name: String::from("Bezirksturnier"), place: String::from("Musterstadt"), date: date(), athletes: vec![athlete], competition: Competition::Individual, coach: Coach::default() };
+    let path = dir.join(format!("Bezirksturnier.{DRAFT_EXTENSION}"));
+    write_draft(&path, &draft).expect("failed to write draft");
+
+    let mut app = EMelderApp::headless(config(&dir), false);
+    app.open_file(&path);
+    app.register_blocking();
+    assert!(dir.join("tournaments").join("BezirksturnierU13 (w).dm4").exists());
+}
+
+#[test]
+fn registration_emails_attach_the_tournament_files() {
+    let dir = temp_dir("email");
+    let path = dir.join("tournaments").join("BezirksturnierU13 (w).dm4");
+    fs::write(&path, "Turnier=Bezirksturnier").expect("failed to write tournament-file");
+    let settings = EmailSettings { enabled: true, host: String::from("smtp.example.org"), from: String::from("trainer@example.org"),
+        recipient: String::from("meldung@example.org, kasse@example.org"), ..EmailSettings::default() };
+    assert!(settings.is_configured());
+
+    let email = RegistrationEmail::new(&settings, "Bezirksturnier", date(), "Musterstadt", "JC Musterstadt", vec![path]);
+    assert_eq!(email.subject, "Meldung JC Musterstadt: Bezirksturnier am 12.10.2025");
+    let message = String::from_utf8(email.to_message(&settings).expect("invalid message").formatted()).expect("invalid message");
+    assert!(message.contains("To: meldung@example.org, kasse@example.org"));
+    assert!(message.contains("filename=\"BezirksturnierU13 (w).dm4\""));
+}
+
+#[test]
+fn desktop_entries_are_installed_and_removed() {
+    let dir = temp_dir("desktop-entry");
+    let data_dir = dir.join("share");
+    install_desktop_entry(&data_dir, Path::new("/opt/e-melder/e-melder")).expect("failed to install desktop-entry");
+    assert!(is_desktop_entry_installed(&data_dir));
+    let package = fs::read_to_string(data_dir.join("mime").join("packages").join("e-melder.xml")).expect("failed to read MIME-types");
+    assert!(package.contains("<glob pattern=\"*.dm4\"/>"));
+    assert!(data_dir.join("icons").join("hicolor").join("scalable").join("apps").join("e-melder.svg").exists());
+
+    uninstall_desktop_entry(&data_dir).expect("failed to uninstall desktop-entry");
+    assert!(!is_desktop_entry_installed(&data_dir));
+}
+
+#[test]
+fn last_registration_prefills_the_categories() {
+    let dir = temp_dir("last-registration");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), date());
+    set_categories(app.add_to_registering(0).expect("athlete missing"), "U13", "-36");
+    app.register_blocking();
+
+    // the categories are remembered across restarts
+    let mut app = EMelderApp::headless(config(&dir), false);
+    app.set_tournament(String::from("Kreisturnier"), String::from("Musterstadt"), date().succ_opt().expect("invalid date"));
+    let athlete = app.add_to_registering(0).expect("athlete missing");
+    assert_eq!((athlete.get_age_category(), athlete.get_weight_category()), ("U13", "-36"));
+    // athletes without a registration get the defaults
+    assert_eq!(app.add_to_registering(1).expect("athlete missing").get_weight_category(), WeightCategory::default().to_string());
+
+    // the age-category of the last year is outdated
+    app.set_tournament(String::from("Bezirksturnier"), String::from("Musterstadt"), NaiveDate::from_ymd_opt(2026, 10, 11).expect("invalid date"));
+    let athlete = app.add_to_registering(0).expect("athlete missing");
+    assert_eq!((athlete.get_age_category(), athlete.get_weight_category()), ("U15", "-36"));
+}
+
+#[test]
+fn registrations_without_name_or_place_are_rejected() {
+    let dir = temp_dir("api-missing");
+    let mut app = EMelderApp::headless(config(&dir), false);
+    add_athletes(&mut app);
+    let id = app.get_athletes()[0].get_id();
+
+    let body = serde_json::json!({ "name": "Bezirksturnier", "place": " ", "date": "2025-10-12",
+        "athletes": [{ "id": id, "age-category": "U13", "weight-category": "-36" }] }).to_string();
+    let request = parse_request("POST", "/registration", Some("Bearer geheim"), &body, "geheim").expect("invalid request");
+    assert_eq!(app.handle_api_request(request).status, 201);
+    assert_eq!(app.handle_api_request(ApiRequest::Export).status, 400);
+    assert_eq!(fs::read_dir(dir.join("tournaments")).expect("failed to read tournament-directory").count(), 0);
+}
+
+#[test]
+fn kyu_exams_are_exported_with_the_examined_belts() {
+    let dir = temp_dir("exam");
+    let athletes = [
+        Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012, Belt::Kyu7, WeightCategory::default(), GenderCategory::Female),
+        Athlete::new(String::from("Max"), String::from("Mustermann"), 2006, Belt::Kyu1, WeightCategory::default(), GenderCategory::Male)
+    ];
+    // the 1. Kyu is followed by the Dan-examinations of the federation
+    assert!(ExamCandidate::from_athlete(&athletes[1]).is_none());
+    let mut candidate = ExamCandidate::from_athlete(&athletes[0]).expect("no candidate");
+    assert_eq!(candidate.get_target(), Belt::Kyu6);
+    assert_eq!(candidate.possible_targets().len(), 6);
+    *candidate.get_target_mut() = Belt::Kyu5;
+    let exam = Exam { date: date(), place: String::from("Musterstadt"), candidates: vec![candidate] };
+
+    write_exam_list(&dir.join("exam.pdf"), &exam, &Club::default(), &std::collections::HashMap::new()).expect("failed to write exam list");
+    let document = lopdf::Document::load(dir.join("exam.pdf")).expect("failed to read exam list");
+    let text = document.extract_text(&[1]).expect("failed to extract text");
+    assert!(text.contains("Mustermann"));
+    assert!(text.contains("5. Kyu"));
+
+    write_exam_csv(&dir.join("exam.csv"), &exam, &Club::default()).expect("failed to write exam CSV");
+    let csv = fs::read_to_string(dir.join("exam.csv")).expect("failed to read CSV");
+    assert_eq!(csv, "\u{feff}Nachname;Vorname;Jahrgang;Gürtel;Prüfung zum;Datum;Ort;Verein\r\n\
+        Mustermann;Erika;2012;7. Kyu;5. Kyu;12.10.2025;Musterstadt;\r\n");
+}
+
+#[test]
+fn archived_seasons_keep_their_name_and_are_not_overwritten() {
+    let dir = temp_dir("seasons");
+    let athletes = vec![Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012, Belt::Kyu7, WeightCategory::default(),
+        GenderCategory::Female)];
+    let path = dir.join("2024_25.json");
+    write_season(&path, &Season { name: String::from("2024/25"), athletes: athletes.clone() }, false).expect("failed to write season");
+    let read = read_season(&path).expect("failed to read season");
+    assert_eq!(read.name, "2024/25");
+    assert_eq!(read.athletes.len(), 1);
+
+    let err = write_season(&path, &Season { name: String::from("2024_25"), athletes: Vec::new() }, false).expect_err("season overwritten");
+    assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    assert_eq!(read_season(&path).expect("failed to read season").name, "2024/25");
+    write_season(&path, &Season { name: String::from("2024_25"), athletes: Vec::new() }, true).expect("failed to overwrite season");
+    assert!(read_season(&path).expect("failed to read season").athletes.is_empty());
+
+    // seasons of older versions are bare athletes-files
+    let legacy = dir.join("2023.json");
+    fs::write(&legacy, serde_json::to_string(&athletes).expect("failed to serialise athletes")).expect("failed to write season");
+    assert_eq!(read_season(&legacy).expect("failed to read season").name, "2023");
+}
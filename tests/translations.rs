@@ -0,0 +1,109 @@
+// the bundled translations are not available for the "unstable"-version
+#![cfg(not(feature = "unstable"))]
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use e_melder_gui::tournament_info::{Belt, GenderCategory};
+use e_melder_gui::utils::{DEFAULT_TRANSLATIONS_DE, DEFAULT_TRANSLATIONS_EN};
+
+fn translations(raw: &str) -> HashMap<String, String> {
+    serde_json::from_str(raw).expect("invalid translations")
+}
+
+fn source_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).expect("failed to read source-directory") {
+        let path = entry.expect("failed to read entry").path();
+        if path.is_dir() {
+            source_files(&path, files);
+        }
+        else if path.extension().is_some_and(|extension| extension == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+fn string_literals(source: &str) -> Vec<(&str, &str)> {
+    // returns each literal together with the code preceding it on the same line
+    let mut ret = Vec::new();
+    for line in source.lines() {
+        let mut rest = line;
+        let mut offset = 0;
+        while let Some(start) = rest.find('"') {
+            let Some(len) = rest[start + 1..].find('"') else {
+                break;
+            };
+            ret.push((&line[..offset + start], &rest[start + 1..start + 1 + len]));
+            offset += start + len + 2;
+            rest = &line[offset..];
+        }
+    }
+    ret
+}
+
+fn is_key(literal: &str, namespaces: &BTreeSet<&str>) -> bool {
+    let Some((namespace, rest)) = literal.split_once('.') else {
+        return false;
+    };
+    namespaces.contains(namespace) && !rest.is_empty()
+        && literal.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.' || c == '{' || c == '}')
+}
+
+fn used_keys() -> BTreeSet<String> {
+    let en = translations(DEFAULT_TRANSLATIONS_EN);
+    let namespaces: BTreeSet<&str> = en.keys().filter_map(|key| key.split_once('.').map(|(namespace, _)| namespace)).collect();
+    let mut files = Vec::new();
+    source_files(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut files);
+
+    let mut keys = BTreeSet::new();
+    for file in files {
+        let source = fs::read_to_string(&file).expect("failed to read source-file");
+        for (before, literal) in string_literals(&source) {
+            // ids of widgets and file-names share the naming-scheme of translation-keys
+            if ["push_id(", "push_id((", "from_id_salt(", "id_salt((", "Id::new((", "make_persistent_id(", "set_file_name(",
+                "join(", "PathBuf::from(", "_scroll_offset("].iter().any(|call| before.ends_with(call)) {
+                continue;
+            }
+            if is_key(literal, &namespaces) {
+                keys.insert(literal.to_owned());
+            }
+        }
+    }
+
+    let mut belts = vec![Belt::Kyu9];
+    while belts.last() != Some(&Belt::Dan10) {
+        belts.push(belts.last().expect("unreachable").inc());
+    }
+    let gender_categories = [GenderCategory::Female, GenderCategory::Male, GenderCategory::Mixed];
+    keys.into_iter().flat_map(|key| {
+        if key == "add.belt.{}" {
+            belts.iter().map(|belt| format!("add.belt.{}", belt.serialise())).collect()
+        }
+        else if key == "register.table.gender_category.{}" {
+            gender_categories.iter().map(|gender| format!("register.table.gender_category.{}", gender.render())).collect()
+        }
+        else {
+            assert!(!key.contains("{}"), "unexpanded translation-key {key}");
+            vec![key]
+        }
+    }).collect()
+}
+
+#[test]
+fn used_keys_are_translated() {
+    let keys = used_keys();
+    assert!(keys.contains("application.title"));
+    for (lang, raw) in [("en", DEFAULT_TRANSLATIONS_EN), ("de", DEFAULT_TRANSLATIONS_DE)] {
+        let translations = translations(raw);
+        let missing: Vec<&String> = keys.iter().filter(|key| !translations.contains_key(*key)).collect();
+        assert!(missing.is_empty(), "missing translations for {lang}: {missing:?}");
+    }
+}
+
+#[test]
+fn languages_have_the_same_keys() {
+    let en: BTreeSet<String> = translations(DEFAULT_TRANSLATIONS_EN).into_keys().collect();
+    let de: BTreeSet<String> = translations(DEFAULT_TRANSLATIONS_DE).into_keys().collect();
+    assert_eq!(en.symmetric_difference(&de).collect::<Vec<_>>(), Vec::<&String>::new());
+}
@@ -0,0 +1,512 @@
+use std::path::Path;
+
+use chrono::NaiveDate;
+
+use e_melder_gui::api::{parse_request, ApiRequest};
+use e_melder_gui::desktop_integration::{association_commands, desktop_entry, shortcut_script, unassociation_commands};
+use e_melder_gui::error::Error;
+use e_melder_gui::export::ical::{add_event, render_event};
+use e_melder_gui::tournament_info::{check_tournament_date, find_duplicate_registrations, normalize_age_category, normalize_athlete_names, normalize_name, parse_invitation, parse_tournament,
+    parse_pasted_athletes, registering_athletes_to_tournaments, registering_teams_to_tournaments, render_fee, suggest_age_category, suggest_belt, suggest_clubs, AgeCategoryFee, Athlete, Belt, Club, Coach, CsvColumn, CsvExport,
+    CsvField, DirectoryClub, Document, Fees, GenderAbbreviations, GenderCategory, RefereeLicense, RegisteringAthlete, SortColumn, SortingState, SurNameDisplay, Tournament, UnusualDate, WeightCategory, WebsiteRoster};
+use e_melder_gui::ui::app::Schedule;
+use e_melder_gui::utils::{get_staging_dir, get_tournament_file_name, is_valid_email, normalize_phone, Pin, Progress, DEFAULT_FILE_NAME_TEMPLATE};
+
+#[test]
+fn api_requests_need_the_token() {
+    assert_eq!(parse_request("GET", "/athletes", None, "", "geheim").err().map(|response| response.status), Some(401));
+    assert_eq!(parse_request("GET", "/athletes", Some("Bearer falsch"), "", "geheim").err().map(|response| response.status), Some(401));
+    // an empty token would let everyone in
+    assert_eq!(parse_request("GET", "/athletes", Some("Bearer "), "", "").err().map(|response| response.status), Some(401));
+    assert!(matches!(parse_request("GET", "/athletes", Some("Bearer geheim"), "", "geheim"), Ok(ApiRequest::ListAthletes)));
+    assert_eq!(parse_request("DELETE", "/athletes", Some("Bearer geheim"), "", "geheim").err().map(|response| response.status),
+        Some(404));
+    assert_eq!(parse_request("POST", "/registration", Some("Bearer geheim"), "{}", "geheim").err().map(|response| response.status),
+        Some(400));
+}
+
+#[test]
+fn valid_emails() {
+    assert!(is_valid_email("vorstand@judo-musterstadt.de"));
+    assert!(is_valid_email("erika.mustermann+judo@mail.example.org"));
+}
+
+#[test]
+fn invalid_emails() {
+    for mail in ["", "judo-musterstadt.de", "@judo-musterstadt.de", "vorstand@", "vorstand@localhost",
+        "vorstand@@judo-musterstadt.de", "vor stand@judo-musterstadt.de", "vorstand@judo..de", "vorstand@judo.de."] {
+        assert!(!is_valid_email(mail), "{mail} should be invalid");
+    }
+}
+
+#[test]
+fn phones_are_normalized() {
+    assert_eq!(normalize_phone("030 1234567").as_deref(), Some("+49301234567"));
+    assert_eq!(normalize_phone("0171/123 45-67").as_deref(), Some("+491711234567"));
+    assert_eq!(normalize_phone("+49 (0)30 1234567").as_deref(), Some("+49301234567"));
+    assert_eq!(normalize_phone("0043 1 234567").as_deref(), Some("+431234567"));
+    assert_eq!(normalize_phone("+49301234567").as_deref(), Some("+49301234567"));
+}
+
+#[test]
+fn invalid_phones_are_not_normalized() {
+    for phone in ["", "1234567", "030 12 34 ab", "+0301234567", "0123", "+49 1234 5678 9012 3456"] {
+        assert_eq!(normalize_phone(phone), None, "{phone} should not be normalized");
+    }
+}
+
+#[test]
+fn pasted_athletes_are_parsed() {
+    let text = "Erika\tMustermann\t2012\t7. Kyu\tw\t-36\tMontag\n\nMax;Mustermann;2010;kyu5;M\nVorname;Nachname;Jahrgang;Gürtel;Geschlecht\n";
+    let progress = Progress::default();
+    let athletes = parse_pasted_athletes(text, &progress);
+    assert_eq!(athletes.len(), 3);
+    assert!((progress.fraction() - 1.0).abs() < f32::EPSILON);
+    let erika = athletes[0].as_ref().expect("valid line");
+    assert_eq!(erika.get_belt(), &Belt::Kyu7);
+    assert_eq!(erika.get_weight_category().to_string(), "-36");
+    assert_eq!(erika.get_group(), "Montag");
+    let max = athletes[1].as_ref().expect("valid line");
+    assert_eq!(max.get_gender(), GenderCategory::Male);
+    assert!(athletes[2].is_none());
+
+    let cancelled = Progress::default();
+    cancelled.cancel();
+    assert!(parse_pasted_athletes(text, &cancelled).is_empty());
+}
+
+#[test]
+fn age_categories_are_normalized() {
+    for age_category in ["U15", "u15", "U 15", " U15 männlich", "u15w", "U15 W"] {
+        assert_eq!(normalize_age_category(age_category), "U15");
+    }
+    assert_eq!(normalize_age_category("ü30"), "Ü30");
+    assert_eq!(normalize_age_category("  frauen  "), "Frauen");
+    assert_eq!(normalize_age_category("MÄNNER offen"), "Männer Offen");
+    // combined age-categories are separate tournaments
+    assert_eq!(normalize_age_category("u18/u21"), "U18/U21");
+    assert_eq!(normalize_age_category("U13-U15 weiblich"), "U13-U15");
+    assert_ne!(normalize_age_category("U18/U21"), normalize_age_category("U18"));
+}
+
+fn file_name(base: &Path, template: &str, name: &str, gender_category: GenderCategory) -> Result<String, Error> {
+    let date = NaiveDate::from_ymd_opt(2025, 10, 12).expect("invalid date");
+    let tournament = Tournament::new(String::from(name), date, String::from("Musterstadt"), String::from("U13"), gender_category,
+        Club::default(), Vec::new());
+    get_tournament_file_name(base, template, &tournament)
+}
+
+#[test]
+fn tournament_file_names_are_safe() {
+    let base = Path::new("/tmp/tournaments");
+    assert_eq!(file_name(base, DEFAULT_FILE_NAME_TEMPLATE, "Bezirksturnier", GenderCategory::Female).expect("valid name"),
+        "BezirksturnierU13 (w).dm4");
+    assert_eq!(file_name(base, DEFAULT_FILE_NAME_TEMPLATE, "nul.", GenderCategory::Male).expect("valid name"), "_nul.U13 (m).dm4");
+    let long = file_name(base, DEFAULT_FILE_NAME_TEMPLATE, &"Turnier".repeat(100), GenderCategory::Male).expect("valid name");
+    assert!(long.ends_with("U13 (m).dm4"));
+    assert!(get_staging_dir(base).join(long).to_string_lossy().chars().count() < 260);
+    assert!(matches!(file_name(&base.join("x".repeat(300)), DEFAULT_FILE_NAME_TEMPLATE, "Turnier", GenderCategory::Male),
+        Err(Error::Validation(_))));
+}
+
+#[test]
+fn tournament_file_names_follow_the_template() {
+    let base = Path::new("/tmp/tournaments");
+    assert_eq!(file_name(base, "{date} {name} {place} {age}-{gender}", "Bezirksturnier", GenderCategory::Female)
+        .expect("valid name"), "2025-10-12 Bezirksturnier Musterstadt U13-w.dm4");
+    // illegal characters of the template are replaced as well
+    assert_eq!(file_name(base, "{name}/{age} ({gender}).DM4", "Bezirksturnier", GenderCategory::Male).expect("valid name"),
+        "Bezirksturnier_U13 (m).DM4");
+
+    // the gender is abbreviated as in the file, as the organizers sort the files by it
+    let date = NaiveDate::from_ymd_opt(2025, 10, 12).expect("invalid date");
+    let mut tournament = Tournament::new(String::from("Bezirksturnier"), date, String::from("Musterstadt"), String::from("U13"),
+        GenderCategory::Female, Club::default(), Vec::new());
+    tournament.set_gender_abbreviations(GenderAbbreviations::Mfx);
+    assert_eq!(get_tournament_file_name(base, DEFAULT_FILE_NAME_TEMPLATE, &tournament).expect("valid name"), "BezirksturnierU13 (f).dm4");
+}
+
+#[test]
+fn clubs_are_suggested_by_name_town_and_number() {
+    let directory = [DirectoryClub::new(String::from("JC Musterstadt"), 1_234_567, String::from("Musterstadt")),
+        DirectoryClub::new(String::from("TSV Beispielhausen"), 7_654_321, String::from("Beispielhausen"))];
+    let names = |query| suggest_clubs(&directory, query).iter().map(|club| club.get_name().to_owned()).collect::<Vec<_>>();
+    assert_eq!(names("muster"), ["JC Musterstadt"]);
+    assert_eq!(names("765"), ["TSV Beispielhausen"]);
+    assert_eq!(names("beispielhausen"), ["TSV Beispielhausen"]);
+    assert!(names("  ").is_empty());
+}
+
+#[test]
+fn referee_licenses_expire_after_their_last_day() {
+    let expiry = NaiveDate::from_ymd_opt(2025, 12, 31).expect("invalid date");
+    let license = RefereeLicense::new(String::from("Bezirk"), expiry);
+    assert!(license.is_valid_on(expiry));
+    assert!(!license.is_valid_on(expiry.succ_opt().expect("invalid date")));
+}
+
+#[test]
+fn lapsed_documents_are_reported() {
+    let mut athlete = Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012, Belt::Kyu7,
+        WeightCategory::default(), GenderCategory::Female);
+    let expiry = NaiveDate::from_ymd_opt(2025, 6, 30).expect("invalid date");
+    *athlete.get_document_expiry_mut(Document::MedicalCertificate) = Some(expiry);
+    assert!(athlete.lapsed_documents(expiry).is_empty());
+    assert_eq!(athlete.lapsed_documents(expiry.succ_opt().expect("invalid date")), [Document::MedicalCertificate]);
+}
+
+#[test]
+fn default_weight_categories_are_flagged() {
+    let mut athlete = RegisteringAthlete::from_athlete(&Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012,
+        Belt::Kyu7, WeightCategory::default(), GenderCategory::Female));
+    assert!(athlete.has_default_weight_category());
+    *athlete.get_weight_category_mut() = String::from("-40");
+    assert!(!athlete.has_default_weight_category());
+    *athlete.get_weight_category_mut() = String::from("+10");
+    assert!(!athlete.has_default_weight_category());
+}
+
+#[test]
+fn names_entered_via_dead_keys_are_composed() {
+    // "Nguyễn" and "Bénédicte" as committed by some IMEs and dead keys: base letters followed by combining marks
+    let athlete = Athlete::new(String::from("Be\u{301}ne\u{301}dicte"), String::from("Nguye\u{302}\u{303}n"), 2012, Belt::Kyu7,
+        WeightCategory::default(), GenderCategory::Female);
+    assert_eq!(athlete.get_given_name(), "Bénédicte");
+    assert_eq!(athlete.get_sur_name(), "Nguyễn");
+    assert_eq!(normalize_name("Nguyễn"), "Nguyễn");
+
+    let mut athletes = [athlete];
+    assert!(!normalize_athlete_names(&mut athletes));
+}
+
+#[test]
+fn website_rosters_only_publish_allowed_details() {
+    let athletes = [Athlete::new(String::from("Erika"), String::from("<Mustermann>"), 2012, Belt::Kyu7,
+        WeightCategory::default(), GenderCategory::Female)];
+    let mut website_roster = WebsiteRoster::default();
+    assert_eq!(website_roster.render_html(&athletes),
+        "<ul class=\"e-melder-roster\">\n<li><span class=\"name\">Erika &lt;.</span><span class=\"belt\">7. Kyu</span></li>\n</ul>\n");
+
+    website_roster.sur_name = SurNameDisplay::Full;
+    website_roster.belt = false;
+    website_roster.photo_url = String::from("https://judo-musterstadt.de/fotos/{given}.jpg");
+    let json: serde_json::Value = serde_json::from_str(&website_roster.render_json(&athletes)).expect("invalid roster");
+    assert_eq!(json, serde_json::json!([{ "name": "Erika <Mustermann>",
+        "photo": "https://judo-musterstadt.de/fotos/Erika.jpg" }]));
+}
+
+#[test]
+fn scheduled_jobs_are_due_once_per_night() {
+    let at = |day, hour| NaiveDate::from_ymd_opt(2025, 10, day).and_then(|date| date.and_hms_opt(hour, 0, 0))
+        .expect("invalid date");
+    let mut schedule = Schedule::default();
+    assert!(schedule.is_due(at(12, 10)));
+
+    schedule.last_run = Some(at(12, 2));
+    assert!(!schedule.is_due(at(12, 23)));
+    assert!(!schedule.is_due(at(13, 1)));
+    assert!(schedule.is_due(at(13, 2)));
+    // the app was closed for some nights, so the jobs run once on the next start
+    assert!(schedule.is_due(at(16, 9)));
+}
+
+#[test]
+fn csv_exports_quote_values_containing_the_delimiter() {
+    let mut athlete = RegisteringAthlete::from_athlete(&Athlete::new(String::from("Anna; Maria"), String::from("Mustermann"), 2012,
+        Belt::Kyu7, WeightCategory::default(), GenderCategory::Female));
+    *athlete.get_age_category_mut() = String::from("U13");
+    let export = CsvExport {
+        columns: vec![CsvColumn { field: CsvField::GivenName, header: String::from("Vorname") },
+            CsvColumn { field: CsvField::Belt, header: String::from("Gürtel") },
+            CsvColumn { field: CsvField::AgeCategory, header: String::from("AK") }],
+        ..CsvExport::default()
+    };
+    let date = NaiveDate::from_ymd_opt(2025, 10, 12).expect("invalid date");
+    assert_eq!(export.render(&[athlete], &Club::default(), "Bezirksturnier", date),
+        "Vorname;Gürtel;AK\r\n\"Anna; Maria\";7. Kyu;U13\r\n");
+}
+
+#[test]
+fn tournament_files_are_read_back() {
+    let athletes = vec![
+        Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012, Belt::Kyu7,
+            WeightCategory::from_str("-36").expect("invalid weight category"), GenderCategory::Female),
+        Athlete::new(String::from("Anna"), String::from("Musterfrau"), 2013, Belt::Kyu6,
+            WeightCategory::from_str("+57").expect("invalid weight category"), GenderCategory::Female)
+    ];
+    let date = NaiveDate::from_ymd_opt(2025, 10, 12).expect("invalid date");
+    let tournament = Tournament::new(String::from("Bezirksturnier"), date, String::from("Musterstadt"), String::from("U13"),
+        GenderCategory::Female, Club::default(), athletes.clone());
+    let parsed = parse_tournament(&tournament.render()).expect("invalid tournament-file");
+    assert_eq!((parsed.get_name(), parsed.get_place(), parsed.get_date()), ("Bezirksturnier", "Musterstadt", date));
+    assert_eq!((parsed.get_age_category(), parsed.get_gender_category()), ("U13", GenderCategory::Female));
+
+    // known athletes keep their ids, the limit of over-categories is not written
+    let registering = parsed.to_registering_athletes(&athletes[..1]);
+    assert_eq!(registering[0].get_id(), athletes[0].get_id());
+    assert_eq!(registering.iter().map(|athlete| (athlete.get_given_name(), athlete.get_weight_category(), athlete.get_age_category()))
+        .collect::<Vec<_>>(), [("Erika", "-36", "U13"), ("Anna", "+57", "U13")]);
+
+    assert!(parse_tournament("Turnier=Bezirksturnier").is_none());
+}
+
+#[test]
+fn age_categories_follow_the_year_of_the_tournament() {
+    let date = NaiveDate::from_ymd_opt(2025, 10, 12).expect("invalid date");
+    assert_eq!(suggest_age_category(2015, GenderCategory::Female, date), Some("U11"));
+    assert_eq!(suggest_age_category(2013, GenderCategory::Female, date), Some("U13"));
+    assert_eq!(suggest_age_category(2012, GenderCategory::Male, date), Some("U15"));
+    assert_eq!(suggest_age_category(2008, GenderCategory::Male, date), Some("U18"));
+    assert_eq!(suggest_age_category(2005, GenderCategory::Female, date), Some("U21"));
+    assert_eq!(suggest_age_category(2004, GenderCategory::Male, date), Some("Männer"));
+    assert_eq!(suggest_age_category(1990, GenderCategory::Female, date), Some("Frauen"));
+    assert_eq!(suggest_age_category(1990, GenderCategory::Mixed, date), None);
+    // the birthday within the year does not matter
+    let january = NaiveDate::from_ymd_opt(2025, 1, 2).expect("invalid date");
+    assert_eq!(suggest_age_category(2013, GenderCategory::Male, january), Some("U13"));
+}
+
+#[test]
+fn invitations_are_parsed_for_date_and_place() {
+    let text = "Ausschreibung Bezirksturnier U13\nMeldeschluss: 01.10.2025\nDatum: Samstag, 12. 10. 2025\nOrt: Sporthalle Musterstadt\n";
+    let metadata = parse_invitation(text);
+    assert_eq!(metadata.date, NaiveDate::from_ymd_opt(2025, 10, 12));
+    assert_eq!(metadata.place.as_deref(), Some("Sporthalle Musterstadt"));
+
+    // without labels, the first date of the text is taken
+    assert_eq!(parse_invitation("Turnier am 05.04.2026 in Musterstadt").date, NaiveDate::from_ymd_opt(2026, 4, 5));
+}
+
+#[test]
+fn belts_are_suggested_for_beginners() {
+    assert_eq!(suggest_belt(2019, 2025, 8, 1), Belt::Kyu9);
+    assert_eq!(suggest_belt(2017, 2025, 8, 1), Belt::Kyu8);
+    assert_eq!(suggest_belt(2015, 2025, 8, 1), Belt::Kyu6);
+    assert_eq!(suggest_belt(2015, 2025, 8, 2), Belt::Kyu7);
+    // a year before the first exam, which does not round towards it
+    assert_eq!(suggest_belt(2018, 2025, 8, 2), Belt::Kyu9);
+    // older athletes are not guessed beyond the orange belt
+    assert_eq!(suggest_belt(1990, 2025, 8, 1), Belt::Kyu5);
+}
+
+#[test]
+fn unofficial_weight_categories_get_the_nearest_official_one() {
+    assert_eq!(WeightCategory::nearest_official("-37", "U13", GenderCategory::Male), None);
+    assert_eq!(WeightCategory::nearest_official("-35", "U13", GenderCategory::Male), Some("-37"));
+    assert_eq!(WeightCategory::nearest_official("-35", "u13", GenderCategory::Female), Some("-36"));
+    assert_eq!(WeightCategory::nearest_official("+60", "U15", GenderCategory::Female), Some("-63"));
+    assert_eq!(WeightCategory::nearest_official("+70", "U15", GenderCategory::Female), Some("+63"));
+    // the adults have official classes as well
+    assert_eq!(WeightCategory::nearest_official("-85", "Männer", GenderCategory::Male), Some("-90"));
+    assert_eq!(WeightCategory::nearest_official("-50", "frauen", GenderCategory::Female), Some("-52"));
+    // younger age categories have no official classes, default classes are reported otherwise
+    assert_eq!(WeightCategory::nearest_official("-25", "U11", GenderCategory::Male), None);
+    assert_eq!(WeightCategory::nearest_official("-10", "U13", GenderCategory::Male), None);
+}
+
+#[test]
+fn file_associations_open_the_app_with_the_file() {
+    let commands = association_commands(Path::new(r"C:\Program Files\e-melder\e-melder.exe"));
+    assert!(commands.contains(&["add", r"HKCU\Software\Classes\.dm4", "/ve", "/d", "e-melder.dm4", "/f"].map(String::from).to_vec()));
+    assert!(commands.contains(&["add", r"HKCU\Software\Classes\e-melder.emreg\shell\open\command", "/ve", "/d",
+        r#""C:\Program Files\e-melder\e-melder.exe" "%1""#, "/f"].map(String::from).to_vec()));
+    // everything added is removed again
+    assert_eq!(unassociation_commands().len(), 4);
+}
+
+#[test]
+fn shortcut_paths_are_quoted_for_powershell() {
+    let script = shortcut_script(Path::new(r"C:\Users\O'Brien\Desktop\e-melder.lnk"), Path::new(r"C:\e-melder\e-melder.exe"));
+    assert!(script.contains(r"CreateShortcut('C:\Users\O''Brien\Desktop\e-melder.lnk')"));
+    assert!(script.contains(r"$shortcut.TargetPath = 'C:\e-melder\e-melder.exe'"));
+}
+
+#[test]
+fn desktop_entries_escape_the_path_of_the_app() {
+    let entry = desktop_entry(Path::new("/home/erika/Judo $Verein/e-melder"));
+    assert!(entry.contains("Exec=\"/home/erika/Judo \\\\$Verein/e-melder\" %f\n"));
+    assert!(entry.contains("MimeType=application/x-e-melder-tournament;application/x-e-melder-draft;\n"));
+}
+
+#[test]
+fn calendar_events_are_replaced_when_registering_again() {
+    let date = NaiveDate::from_ymd_opt(2025, 10, 12).expect("invalid date");
+    let calendar = add_event("", "Bezirksturnier", "Musterstadt, Halle 2", date);
+    assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(calendar.contains("DTSTART;VALUE=DATE:20251012\r\nDTEND;VALUE=DATE:20251013\r\n"));
+    assert!(calendar.contains("LOCATION:Musterstadt\\, Halle 2\r\n"));
+
+    let calendar = add_event(&calendar, "Bezirksturnier", "Musterstadt", date);
+    assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 1);
+    let calendar = add_event(&calendar, "Landesturnier", "Musterstadt", date);
+    assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 2);
+    assert!(calendar.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+}
+
+#[test]
+fn long_calendar_lines_are_folded() {
+    let date = NaiveDate::from_ymd_opt(2025, 10, 12).expect("invalid date");
+    let event = render_event(&"Internationales Jugendturnier um den Pokal der Stadt Musterstadt".repeat(2), "Musterstadt", date);
+    assert!(event.split("\r\n").all(|line| line.len() <= 75));
+    assert!(event.contains("\r\n "));
+}
+
+#[test]
+fn fees_fall_back_to_the_one_per_athlete() {
+    let fees = Fees { per_athlete: 1000, per_age_category: vec![AgeCategoryFee { age_category: String::from("U18"), fee: 1500 }] };
+    assert!(fees.is_configured());
+    assert_eq!(fees.fee("U13"), 1000);
+    // the age-categories are compared as normalized
+    assert_eq!(fees.fee("u 18"), 1500);
+    assert_eq!(render_fee(1250), "12.50 €");
+    assert!(!Fees::default().is_configured());
+
+    let date = NaiveDate::from_ymd_opt(2025, 10, 12).expect("invalid date");
+    let athletes = vec![Athlete::new(String::from("Max"), String::from("Mustermann"), 2009, Belt::Kyu5, WeightCategory::default(),
+        GenderCategory::Male); 2];
+    let tournament = Tournament::new(String::from("Bezirksturnier"), date, String::from("Musterstadt"), String::from("U18"),
+        GenderCategory::Male, Club::default(), athletes);
+    assert_eq!(fees.per_tournament(&[tournament]), vec![(String::from("U18"), GenderCategory::Male, 2, 3000)]);
+}
+
+#[test]
+fn athletes_can_be_registered_in_several_age_categories() {
+    let max = Athlete::new(String::from("Max"), String::from("Mustermann"), 2008, Belt::Kyu1, WeightCategory::default(),
+        GenderCategory::Male);
+    let registering = |age_category: &str| {
+        let mut athlete = RegisteringAthlete::from_athlete(&max);
+        *athlete.get_weight_category_mut() = String::from("-73");
+        *athlete.get_age_category_mut() = age_category.to_owned();
+        athlete
+    };
+    let mut athletes = vec![registering("U18"), registering("Männer")];
+    assert!(find_duplicate_registrations(&athletes).is_empty());
+    let tournaments = registering_athletes_to_tournaments(&athletes, "Turnier", NaiveDate::from_ymd_opt(2026, 3, 14).unwrap(), "Ort",
+        &Club::default()).unwrap();
+    assert_eq!(tournaments.len(), 2);
+    assert!(tournaments.iter().all(|tournament| tournament.get_athletes().len() == 1));
+
+    athletes.push(registering("u 18"));
+    assert_eq!(find_duplicate_registrations(&athletes), vec![2]);
+}
+
+#[test]
+fn teams_are_rendered_before_their_athletes_and_read_back() {
+    let registering = |given_name: &str, team: &str| {
+        let mut athlete = RegisteringAthlete::from_athlete(&Athlete::new(given_name.to_owned(), String::from("Mustermann"), 2008,
+            Belt::Kyu1, WeightCategory::default(), GenderCategory::Male));
+        *athlete.get_age_category_mut() = String::from("U18");
+        *athlete.get_team_mut() = team.to_owned();
+        athlete
+    };
+    let date = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+    // the quotes and the comma would break the file, so they are replaced
+    let mut athletes = vec![registering("Max", "Musterstadt I"), registering("Moritz", "\"Musterstadt\", II"), registering("Paul", "Musterstadt I")];
+    let tournaments = registering_teams_to_tournaments(&athletes, "Kata-Turnier", date, "Ort", &Club::default()).unwrap();
+    assert_eq!(tournaments.len(), 1);
+    let teams: Vec<(&str, usize)> = tournaments[0].get_teams().iter().map(|team| (team.get_name(), team.get_size())).collect();
+    assert_eq!(teams, [("Musterstadt I", 2), ("'Musterstadt' II", 1)]);
+
+    let text = tournaments[0].render();
+    assert!(text.contains("Type=M\n"));
+    assert!(text.contains("[Mannschaften]\n1=\"\"1\",\"Musterstadt I\"\"\n2=\"\"1\",\"'Musterstadt' II\"\"\nAnzahl=2\n"));
+    // the athletes follow their teams
+    let members: Vec<&str> = text.lines().skip_while(|line| *line != "[Teilnehmer]").skip(1).take(3).collect();
+    assert!(members[0].contains("\"Max\"") && members[0].ends_with(",\"1\"\""));
+    assert!(members[1].contains("\"Paul\"") && members[1].ends_with(",\"1\"\""));
+    assert!(members[2].contains("\"Moritz\"") && members[2].ends_with(",\"2\"\""));
+
+    let parsed = parse_tournament(&text).expect("failed to parse team-file");
+    assert_eq!(parsed.get_teams(), tournaments[0].get_teams());
+    let registering_athletes = parsed.to_registering_athletes(&[]);
+    let read_back: Vec<(&str, &str)> = registering_athletes.iter().map(|athlete| (athlete.get_given_name(), athlete.get_team())).collect();
+    assert_eq!(read_back, [("Max", "Musterstadt I"), ("Paul", "Musterstadt I"), ("Moritz", "'Musterstadt' II")]);
+
+    athletes.push(registering("Peter", " "));
+    assert!(registering_teams_to_tournaments(&athletes, "Kata-Turnier", date, "Ort", &Club::default()).is_none());
+}
+
+#[test]
+fn coaches_are_only_rendered_when_given() {
+    let athlete = Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012, Belt::Kyu7, WeightCategory::default(),
+        GenderCategory::Female);
+    let mut tournament = Tournament::new(String::from("Bezirksturnier"), NaiveDate::from_ymd_opt(2026, 3, 14).unwrap(),
+        String::from("Musterstadt"), String::from("U13"), GenderCategory::Female, Club::default(), vec![athlete]);
+    assert!(tournament.render().contains("Geschlecht=w\n[Vereine]"));
+
+    tournament.set_coach(Coach::new(String::from("Max Mustermann"), String::from("+4930123456")));
+    assert!(tournament.render().contains("Geschlecht=w\nBetreuer=Max Mustermann\nBetreuerTelefon=+4930123456\n[Vereine]"));
+}
+
+#[test]
+fn tournament_dates_outside_the_next_year_are_unusual() {
+    let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+    assert_eq!(check_tournament_date(today, today), None);
+    assert_eq!(check_tournament_date(NaiveDate::from_ymd_opt(2027, 10, 17).unwrap(), today), None);
+    assert_eq!(check_tournament_date(NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(), today), Some(UnusualDate::Past));
+    // a mis-clicked year
+    assert_eq!(check_tournament_date(NaiveDate::from_ymd_opt(2027, 10, 18).unwrap(), today), Some(UnusualDate::FarFuture));
+}
+
+#[test]
+fn sorting_cycles_through_ascending_descending_and_unsorted() {
+    let athlete = |given_name: &str, sur_name: &str, belt| Athlete::new(String::from(given_name), String::from(sur_name), 2012, belt,
+        WeightCategory::default(), GenderCategory::Male);
+    let athletes = vec![athlete("Paul", "schmidt", Belt::Kyu5), athlete("Max", "Mustermann", Belt::Kyu8), athlete("Moritz", "Schmidt", Belt::Dan1)];
+
+    let mut sorting = SortingState::default();
+    sorting.toggle(SortColumn::SurName);
+    let mut indices: Vec<usize> = (0..athletes.len()).collect();
+    sorting.sort(&athletes, &mut indices);
+    // the case is ignored and equal names keep their order
+    assert_eq!(indices, [1, 0, 2]);
+
+    sorting.toggle(SortColumn::SurName);
+    sorting.sort(&athletes, &mut indices);
+    assert_eq!(indices, [0, 2, 1]);
+
+    sorting.toggle(SortColumn::SurName);
+    assert_eq!(sorting, SortingState::default());
+
+    sorting.toggle(SortColumn::Belt);
+    let mut indices: Vec<usize> = (0..athletes.len()).collect();
+    sorting.sort(&athletes, &mut indices);
+    assert_eq!(indices, [1, 0, 2]);
+}
+
+#[test]
+fn athletes_are_told_apart_by_their_id_or_else_their_name() {
+    let erika = || Athlete::new(String::from("Erika"), String::from("Mustermann"), 2012, Belt::Kyu7, WeightCategory::default(),
+        GenderCategory::Female);
+    let (first, second) = (erika(), erika());
+    assert!(first.is_same_as(&first.clone()));
+    // namesakes with their own ids are different athletes
+    assert!(!first.is_same_as(&second));
+    assert!(!first.is_same_athlete(&RegisteringAthlete::from_athlete(&second)));
+
+    // athletes of files written before ids were introduced have none
+    let mut legacy = serde_json::to_value(&second).expect("failed to serialise athlete");
+    legacy.as_object_mut().expect("no object").remove("id");
+    let legacy: Athlete = serde_json::from_value(legacy).expect("failed to deserialise athlete");
+    assert!(first.is_same_as(&legacy) && legacy.is_same_as(&second));
+    assert!(legacy.is_same_athlete(&RegisteringAthlete::from_athlete(&first)));
+}
+
+#[test]
+fn pins_are_only_stored_as_salted_hash() {
+    let pin = Pin::new("geheim");
+    assert!(pin.verify("geheim") && !pin.verify("falsch"));
+    let stored = serde_json::to_string(&pin).expect("failed to serialise PIN");
+    assert!(!stored.contains("geheim"));
+    // PINs are short, so they are hashed by a slow key-derivation
+    assert!(stored.starts_with("\"pbkdf2-sha256$"));
+    // the hex-digits of the hash cannot contain the PIN, the same PIN gets another salt
+    assert_ne!(stored, serde_json::to_string(&Pin::new("geheim")).expect("failed to serialise PIN"));
+    assert!(serde_json::from_str::<Pin>(&stored).expect("failed to deserialise PIN").verify("geheim"));
+
+    // configs of older versions hold the PIN itself
+    let legacy: Pin = serde_json::from_str("\"geheim\"").expect("failed to deserialise PIN");
+    assert!(legacy.verify("geheim"));
+    assert!(!serde_json::to_string(&legacy).expect("failed to serialise PIN").contains("geheim"));
+    assert!(Pin::new("").is_empty() && Pin::default().verify(""));
+}